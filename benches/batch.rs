@@ -0,0 +1,65 @@
+#[macro_use]
+extern crate criterion;
+extern crate noice;
+
+use criterion::{black_box, Criterion, Throughput};
+use noice::{Fbm, NoiseFn, OpenSimplex, Perlin, RidgedMulti, Simplex, Value, Worley};
+
+criterion_group!(batch, bench_batch2, bench_batch3);
+criterion_main!(batch);
+
+const SAMPLES: usize = 4096;
+
+fn bench_batch2(c: &mut Criterion) {
+    let points: Vec<[f64; 2]> = (0..SAMPLES)
+        .map(|i| [(i % 64) as f64 * 0.1, (i / 64) as f64 * 0.1])
+        .collect();
+
+    let mut group = c.benchmark_group("batch 2d");
+    group.throughput(Throughput::Elements(SAMPLES as u64));
+    bench_generator(&mut group, "simplex", &Simplex::new(), &points);
+    bench_generator(&mut group, "perlin", &Perlin::new(), &points);
+    bench_generator(&mut group, "open simplex", &OpenSimplex::new(), &points);
+    bench_generator(&mut group, "value", &Value::new(), &points);
+    bench_generator(&mut group, "worley", &Worley::new(), &points);
+    group.finish();
+}
+
+fn bench_batch3(c: &mut Criterion) {
+    let points: Vec<[f64; 3]> = (0..SAMPLES)
+        .map(|i| [(i % 64) as f64 * 0.1, (i / 64) as f64 * 0.1, 0.5])
+        .collect();
+
+    let mut group = c.benchmark_group("batch 3d");
+    group.throughput(Throughput::Elements(SAMPLES as u64));
+    bench_generator(&mut group, "simplex", &Simplex::new(), &points);
+    bench_generator(&mut group, "perlin", &Perlin::new(), &points);
+    bench_generator(&mut group, "fbm", &Fbm::new(), &points);
+    bench_generator(&mut group, "ridged multi", &RidgedMulti::new(), &points);
+    group.finish();
+}
+
+// Measures `get_batch` against calling `get` for each point, so generators
+// with a vectorized batch path show the difference.
+fn bench_generator<T: Copy>(
+    group: &mut criterion::BenchmarkGroup<criterion::measurement::WallTime>,
+    name: &str,
+    noise: &dyn NoiseFn<T>,
+    points: &[T],
+) {
+    let mut output = vec![0.0; points.len()];
+
+    group.bench_function(format!("{} get_batch", name), |b| {
+        b.iter(|| {
+            noise.get_batch(black_box(points), &mut output);
+            black_box(&output);
+        })
+    });
+    group.bench_function(format!("{} get", name), |b| {
+        b.iter(|| {
+            for &point in points {
+                black_box(noise.get(black_box(point)));
+            }
+        })
+    });
+}
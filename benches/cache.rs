@@ -0,0 +1,46 @@
+#[macro_use]
+extern crate criterion;
+extern crate noice;
+
+use criterion::{black_box, Criterion};
+#[cfg(feature = "lru")]
+use noice::CachedNoise;
+use noice::{Add, Cache, Fbm, MultiFractal, Multiply, NoiseFn, Seedable, Turbulence};
+
+criterion_group!(cache, bench_turbulence_graph);
+criterion_main!(cache);
+
+fn bench_turbulence_graph(c: &mut Criterion) {
+    let fbm = Fbm::new().set_octaves(16);
+
+    bench_graph(c, "turbulence graph (16x16)", &fbm);
+    bench_graph(c, "turbulence graph with Cache (16x16)", &Cache::new(&fbm));
+    #[cfg(feature = "lru")]
+    bench_graph(
+        c,
+        "turbulence graph with CachedNoise (16x16)",
+        &CachedNoise::new(&fbm).set_resolution(0.0).unwrap(),
+    );
+}
+
+// Two turbulence nodes over the same source, whose outputs are both added and
+// multiplied. Each output sample queries the source at two points, twice
+// over, in alternating order, which a single-entry `Cache` can't take
+// advantage of.
+fn bench_graph(c: &mut Criterion, name: &str, source: &dyn NoiseFn<[f64; 3]>) {
+    let first = Turbulence::new(source).set_seed(1).set_roughness(1);
+    let second = Turbulence::new(source).set_seed(2).set_roughness(1);
+    let sum = Add::new(&first, &second);
+    let product = Multiply::new(&first, &second);
+    let graph = Add::new(&sum, &product);
+
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            for y in 0..16 {
+                for x in 0..16 {
+                    black_box(graph.get([x as f64 * 0.1, y as f64 * 0.1, 0.5]));
+                }
+            }
+        })
+    });
+}
@@ -0,0 +1,82 @@
+#[macro_use]
+extern crate criterion;
+extern crate noice;
+
+use criterion::{black_box, Criterion};
+use noice::{BasicMulti, Billow, Fbm, HybridMulti, NoiseFn, RidgedMulti};
+
+criterion_group!(
+    fractals,
+    bench_fbm3,
+    bench_billow3,
+    bench_basic_multi3,
+    bench_hybrid_multi3,
+    bench_ridged_multi3
+);
+criterion_group!(
+    fractals_64x64,
+    bench_fbm3_64x64,
+    bench_billow3_64x64,
+    bench_basic_multi3_64x64,
+    bench_hybrid_multi3_64x64,
+    bench_ridged_multi3_64x64
+);
+criterion_main!(fractals, fractals_64x64);
+
+fn bench_fbm3(c: &mut Criterion) {
+    bench_point(c, "fbm 3d", &Fbm::new());
+}
+
+fn bench_billow3(c: &mut Criterion) {
+    bench_point(c, "billow 3d", &Billow::new());
+}
+
+fn bench_basic_multi3(c: &mut Criterion) {
+    bench_point(c, "basic multi 3d", &BasicMulti::new());
+}
+
+fn bench_hybrid_multi3(c: &mut Criterion) {
+    bench_point(c, "hybrid multi 3d", &HybridMulti::new());
+}
+
+fn bench_ridged_multi3(c: &mut Criterion) {
+    bench_point(c, "ridged multi 3d", &RidgedMulti::new());
+}
+
+fn bench_fbm3_64x64(c: &mut Criterion) {
+    bench_64x64(c, "fbm 3d (64x64)", &Fbm::new());
+}
+
+fn bench_billow3_64x64(c: &mut Criterion) {
+    bench_64x64(c, "billow 3d (64x64)", &Billow::new());
+}
+
+fn bench_basic_multi3_64x64(c: &mut Criterion) {
+    bench_64x64(c, "basic multi 3d (64x64)", &BasicMulti::new());
+}
+
+fn bench_hybrid_multi3_64x64(c: &mut Criterion) {
+    bench_64x64(c, "hybrid multi 3d (64x64)", &HybridMulti::new());
+}
+
+fn bench_ridged_multi3_64x64(c: &mut Criterion) {
+    bench_64x64(c, "ridged multi 3d (64x64)", &RidgedMulti::new());
+}
+
+fn bench_point(c: &mut Criterion, name: &str, noise: &dyn NoiseFn<[f64; 3]>) {
+    c.bench_function(name, |b| {
+        b.iter(|| noise.get(black_box([42.0_f64, 37.0, 26.0])))
+    });
+}
+
+fn bench_64x64(c: &mut Criterion, name: &str, noise: &dyn NoiseFn<[f64; 3]>) {
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            for y in 0i8..64 {
+                for x in 0i8..64 {
+                    black_box(noise.get([x as f64, y as f64, x as f64]));
+                }
+            }
+        })
+    });
+}
@@ -0,0 +1,39 @@
+#[macro_use]
+extern crate criterion;
+extern crate noice;
+
+use criterion::{Criterion, Throughput};
+use noice::{
+    utils::{NoiseMapBuilder, PlaneMapBuilder},
+    Fbm, NoiseFn, Simplex,
+};
+
+criterion_group!(noise_map, bench_plane_map_512, bench_plane_map_2048);
+criterion_main!(noise_map);
+
+fn bench_plane_map_512(c: &mut Criterion) {
+    bench_plane_map(c, 512, 100);
+}
+
+fn bench_plane_map_2048(c: &mut Criterion) {
+    // Each iteration takes long enough that fewer samples still give a stable
+    // result.
+    bench_plane_map(c, 2048, 10);
+}
+
+fn bench_plane_map(c: &mut Criterion, size: usize, sample_size: usize) {
+    let simplex = Simplex::new();
+    let fbm = Fbm::new();
+    let sources: [(&str, &dyn NoiseFn<[f64; 3]>); 2] = [("simplex", &simplex), ("fbm", &fbm)];
+
+    let mut group = c.benchmark_group(format!("plane map ({}x{})", size, size));
+    group.sample_size(sample_size);
+    group.throughput(Throughput::Elements((size * size) as u64));
+
+    for (name, source) in sources.iter() {
+        let builder = PlaneMapBuilder::new(*source).set_size(size, size);
+        group.bench_function(*name, |b| b.iter(|| builder.build()));
+    }
+
+    group.finish();
+}
@@ -1,9 +1,9 @@
 #[macro_use]
 extern crate criterion;
-extern crate noise;
+extern crate noice;
 
 use criterion::{black_box, Criterion};
-use noise::{NoiseFn, Perlin};
+use noice::{NoiseFn, Perlin};
 
 criterion_group!(perlin, bench_perlin2, bench_perlin3, bench_perlin4);
 criterion_group!(
@@ -0,0 +1,104 @@
+#[macro_use]
+extern crate criterion;
+extern crate noice;
+
+use criterion::{black_box, Criterion};
+use noice::{NoiseFn, Simplex};
+
+criterion_group!(simplex, bench_simplex2, bench_simplex3, bench_simplex4);
+criterion_group!(
+    simplex_64x64,
+    bench_simplex2_64x64,
+    bench_simplex3_64x64,
+    bench_simplex4_64x64
+);
+criterion_group!(simplex_1m, bench_simplex2_1m);
+criterion_main!(simplex, simplex_64x64, simplex_1m);
+
+fn bench_simplex2(c: &mut Criterion) {
+    let simplex = Simplex::new();
+    c.bench_function("simplex 2d", |b| {
+        b.iter(|| simplex.get(black_box([42.0_f64, 37.0])))
+    });
+}
+
+fn bench_simplex3(c: &mut Criterion) {
+    let simplex = Simplex::new();
+    c.bench_function("simplex 3d", |b| {
+        b.iter(|| simplex.get(black_box([42.0_f64, 37.0, 26.0])))
+    });
+}
+
+fn bench_simplex4(c: &mut Criterion) {
+    let simplex = Simplex::new();
+    c.bench_function("simplex 4d", |b| {
+        b.iter(|| simplex.get(black_box([42.0_f64, 37.0, 26.0, 128.0])))
+    });
+}
+
+fn bench_simplex2_64x64(c: &mut Criterion) {
+    let simplex = Simplex::new();
+    c.bench_function("simplex 2d (64x64)", |b| {
+        b.iter(|| {
+            for y in 0i8..64 {
+                for x in 0i8..64 {
+                    black_box(simplex.get([x as f64, y as f64]));
+                }
+            }
+        })
+    });
+}
+
+fn bench_simplex3_64x64(c: &mut Criterion) {
+    let simplex = Simplex::new();
+    c.bench_function("simplex 3d (64x64)", |b| {
+        b.iter(|| {
+            for y in 0i8..64 {
+                for x in 0i8..64 {
+                    black_box(simplex.get([x as f64, y as f64, x as f64]));
+                }
+            }
+        })
+    });
+}
+
+fn bench_simplex4_64x64(c: &mut Criterion) {
+    let simplex = Simplex::new();
+    c.bench_function("simplex 4d (64x64)", |b| {
+        b.iter(|| {
+            for y in 0i8..64 {
+                for x in 0i8..64 {
+                    black_box(simplex.get([x as f64, y as f64, x as f64, y as f64]));
+                }
+            }
+        })
+    });
+}
+
+/// Compares evaluating one point at a time against `get_batch`, which takes
+/// the SIMD path when the `simd` feature is enabled.
+fn bench_simplex2_1m(c: &mut Criterion) {
+    let simplex = Simplex::new();
+    let points: Vec<[f64; 2]> = (0..1_000_000)
+        .map(|i| [(i % 1000) as f64 * 0.1, (i / 1000) as f64 * 0.1])
+        .collect();
+    let mut output = vec![0.0; points.len()];
+
+    let mut group = c.benchmark_group("simplex 2d (1M points)");
+    group.sample_size(10);
+    group.bench_function("scalar", |b| {
+        b.iter(|| {
+            for (point, value) in points.iter().zip(output.iter_mut()) {
+                *value = simplex.get(*point);
+            }
+            black_box(&output);
+        })
+    });
+    group.bench_function("batch", |b| {
+        b.iter(|| {
+            simplex.get_batch(black_box(&points), &mut output);
+            black_box(&output);
+        })
+    });
+    group.finish();
+}
@@ -1,9 +1,9 @@
 #[macro_use]
 extern crate criterion;
-extern crate noise;
+extern crate noice;
 
 use criterion::{black_box, Criterion};
-use noise::{NoiseFn, SuperSimplex};
+use noice::{NoiseFn, SuperSimplex};
 
 criterion_group!(super_simplex, bench_super_simplex2, bench_super_simplex3);
 criterion_group!(
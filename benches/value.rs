@@ -1,9 +1,9 @@
 #[macro_use]
 extern crate criterion;
-extern crate noise;
+extern crate noice;
 
 use criterion::{black_box, Criterion};
-use noise::{NoiseFn, Value};
+use noice::{NoiseFn, Value};
 
 criterion_group!(value, bench_value2, bench_value3, bench_value4);
 criterion_group!(
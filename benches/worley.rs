@@ -1,9 +1,9 @@
 #[macro_use]
 extern crate criterion;
-extern crate noise;
+extern crate noice;
 
 use criterion::{black_box, Criterion};
-use noise::{NoiseFn, Worley};
+use noice::{NoiseFn, Worley};
 
 criterion_group!(
     worley_range,
@@ -0,0 +1,47 @@
+//! Generates a looping waveform for a synthesizer by sampling noise around a
+//! circle, and writes two seconds of it as a 16-bit mono WAV file.
+use noice::{utils::*, Fbm, MultiFractal};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+};
+
+const SAMPLE_RATE: u32 = 44_100;
+const FREQUENCY: u32 = 220;
+
+fn main() -> io::Result<()> {
+    let fbm = Fbm::new().set_octaves(4);
+
+    // One cycle of the wave. As the circle closes on itself, the cycle
+    // repeats without clicks.
+    let cycle = sample_along_circle(&fbm, [0.0, 0.0], 1.5, (SAMPLE_RATE / FREQUENCY) as usize);
+
+    let samples: Vec<i16> = cycle
+        .iter()
+        .cycle()
+        .take(SAMPLE_RATE as usize * 2)
+        .map(|&value| (value.clamp(-1.0, 1.0) * f64::from(i16::MAX) * 0.8) as i16)
+        .collect();
+
+    let mut out = BufWriter::new(File::create("audio_waveform.wav")?);
+    let data_size = samples.len() as u32 * 2;
+
+    out.write_all(b"RIFF")?;
+    out.write_all(&(36 + data_size).to_le_bytes())?;
+    out.write_all(b"WAVEfmt ")?;
+    out.write_all(&16u32.to_le_bytes())?;
+    out.write_all(&1u16.to_le_bytes())?; // PCM
+    out.write_all(&1u16.to_le_bytes())?; // Mono
+    out.write_all(&SAMPLE_RATE.to_le_bytes())?;
+    out.write_all(&(SAMPLE_RATE * 2).to_le_bytes())?; // Bytes per second
+    out.write_all(&2u16.to_le_bytes())?; // Bytes per frame
+    out.write_all(&16u16.to_le_bytes())?; // Bits per sample
+    out.write_all(b"data")?;
+    out.write_all(&data_size.to_le_bytes())?;
+
+    for sample in samples {
+        out.write_all(&sample.to_le_bytes())?;
+    }
+
+    out.flush()
+}
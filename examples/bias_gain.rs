@@ -0,0 +1,19 @@
+use noice::{utils::*, Bias, Fbm, Gain};
+
+fn main() {
+    let fbm = Fbm::new();
+
+    for &parameter in &[0.2, 0.35, 0.65, 0.8] {
+        let bias = Bias::new(&fbm, parameter).unwrap();
+
+        PlaneMapBuilder::new(&bias)
+            .build()
+            .write_to_file(&format!("bias_{}.png", parameter));
+
+        let gain = Gain::new(&fbm, parameter).unwrap();
+
+        PlaneMapBuilder::new(&gain)
+            .build()
+            .write_to_file(&format!("gain_{}.png", parameter));
+    }
+}
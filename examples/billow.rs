@@ -1,8 +1,37 @@
 //! An example of using the Billow noise function
-use noice::{utils::*, Billow};
+use noice::{utils::*, Billow, MultiFractal, Worley};
 
 fn main() {
     PlaneMapBuilder::new(&Billow::new())
         .build()
         .write_to_file("billow.png");
+    PlaneMapBuilder::new(&Billow::new().set_weighted_strength(0.5))
+        .build()
+        .write_to_file("billow_weighted_strength=0.5.png");
+
+    // Folded octaves of Worley noise instead of Perlin noise.
+    PlaneMapBuilder::new(&Billow::with_base(Worley::new()).set_octaves(4))
+        .build()
+        .write_to_file("billow_worley.png");
+
+    // Place the same region side by side at increasing sharpness, from plain
+    // fBm on the left to fully folded billows on the right.
+    let sharpnesses = [0.0, 0.5, 1.0];
+    let (width, height) = (256, 256);
+    let mut strip = NoiseMap::new(width * sharpnesses.len(), height);
+
+    for (i, &sharpness) in sharpnesses.iter().enumerate() {
+        let billow = Billow::new().set_normalized(true).set_sharpness(sharpness);
+        let panel = PlaneMapBuilder::new(&billow)
+            .set_size(width, height)
+            .build();
+
+        for y in 0..height {
+            for x in 0..width {
+                strip.set_value(i * width + x, y, panel.get_value(x, y));
+            }
+        }
+    }
+
+    strip.write_to_file("billow_sharpness.png");
 }
@@ -7,4 +7,12 @@ fn main() {
     PlaneMapBuilder::new(&checker)
         .build()
         .write_to_file("checkerboard.png");
+
+    let checker = checker.set_size(2);
+
+    PlaneMapBuilder::new(&checker)
+        .set_x_bounds(-8.0, 8.0)
+        .set_y_bounds(-8.0, 8.0)
+        .build()
+        .write_to_file("checkerboard_size=2.png");
 }
@@ -0,0 +1,31 @@
+//! Renders the noise described by a configuration file.
+//!
+//! Run with a path to a `.ron` or `.toml` file, or without arguments to render
+//! `examples/configs/terrain.ron`.
+
+use noice::{config::NoiseCfg, utils::*};
+use std::{env, fs, path::Path};
+
+fn main() {
+    let path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "examples/configs/terrain.ron".to_owned());
+    let text = fs::read_to_string(&path).expect("failed to read the configuration");
+
+    let cfg = match Path::new(&path).extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => NoiseCfg::from_toml(&text).expect("invalid TOML configuration"),
+        _ => NoiseCfg::from_ron(&text).expect("invalid RON configuration"),
+    };
+    let noise = cfg.build_3d().expect("invalid noise parameters");
+
+    let noise_map = PlaneMapBuilder::new(&*noise)
+        .set_size(512, 512)
+        .set_x_bounds(-2.0, 2.0)
+        .set_y_bounds(-2.0, 2.0)
+        .build();
+
+    ImageRenderer::new()
+        .set_gradient(ColorGradient::new().build_terrain_gradient())
+        .render(&noise_map)
+        .write_to_file("config.png");
+}
@@ -0,0 +1,24 @@
+use noice::{utils::*, Constant, Cylinders, Divide, Perlin, ScaleBias};
+
+fn main() {
+    let cyl = Cylinders::new();
+    let perlin = Perlin::new();
+
+    // Keep the divisor away from zero, within [1,3].
+    let divisor = ScaleBias::new(&cyl).set_scale(1.0).set_bias(2.0);
+    let divide = Divide::new(&perlin, &divisor);
+
+    PlaneMapBuilder::new(&divide)
+        .build()
+        .write_to_file("divide.png");
+
+    // Divisors close to zero output the fallback value instead.
+    let one = Constant::new(1.0);
+    let divide = Divide::new(&one, &perlin)
+        .set_epsilon(0.05)
+        .set_fallback(0.0);
+
+    PlaneMapBuilder::new(&divide)
+        .build()
+        .write_to_file("divide_fallback.png");
+}
@@ -0,0 +1,57 @@
+//! Reproduces the warped fBm pattern from Inigo Quilez's article on domain
+//! warping: <https://iquilezles.org/articles/warp/>
+use noice::{utils::*, *};
+
+fn main() {
+    let fbm = Fbm::new().set_octaves(4).set_persistence(0.5);
+
+    // f(p) = fbm(p + 4 * fbm(p)), warping the input once.
+    let warped = DomainWarp::new(fbm.clone(), fbm.clone()).set_amplitude(4.0);
+
+    PlaneMapBuilder::new(&warped)
+        .set_size(512, 512)
+        .set_x_bounds(0.0, 4.0)
+        .set_y_bounds(0.0, 4.0)
+        .build()
+        .write_to_file("domain_warp.png");
+
+    // f(p) = fbm(p + 4 * fbm(p + 4 * fbm(p))), using a warped function as the
+    // warp itself.
+    let double_warped = DomainWarp::new(fbm.clone(), warped.clone())
+        .set_seed(4)
+        .set_amplitude(4.0);
+
+    let noise_map = PlaneMapBuilder::new(&double_warped)
+        .set_size(512, 512)
+        .set_x_bounds(0.0, 4.0)
+        .set_y_bounds(0.0, 4.0)
+        .build();
+
+    noise_map.write_to_file("domain_warp_double.png");
+
+    let gradient = ColorGradient::new()
+        .clear_gradient()
+        .add_gradient_point(-1.0, [26, 26, 26, 255])
+        .add_gradient_point(-0.4, [41, 87, 102, 255])
+        .add_gradient_point(0.0, [168, 168, 115, 255])
+        .add_gradient_point(0.4, [153, 102, 36, 255])
+        .add_gradient_point(1.0, [242, 242, 230, 255]);
+
+    ImageRenderer::new()
+        .set_gradient(gradient)
+        .render(&noise_map)
+        .write_to_file("domain_warp_colored.png");
+
+    // Progressive warp: each octave warps the result of the previous one at a
+    // higher frequency and a lower amplitude.
+    let progressive = DomainWarp::new(fbm.clone(), fbm)
+        .set_amplitude(1.0)
+        .set_octaves(4);
+
+    PlaneMapBuilder::new(&progressive)
+        .set_size(512, 512)
+        .set_x_bounds(0.0, 4.0)
+        .set_y_bounds(0.0, 4.0)
+        .build()
+        .write_to_file("domain_warp_progressive.png");
+}
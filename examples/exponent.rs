@@ -1,4 +1,4 @@
-use noice::{utils::*, Exponent, Perlin};
+use noice::{utils::*, Exponent, Perlin, ScaleBias};
 
 fn main() {
     let perlin = Perlin::new();
@@ -7,4 +7,13 @@ fn main() {
     PlaneMapBuilder::new(&exponent)
         .build()
         .write_to_file("exponent.png");
+
+    // A source that overshoots -1.0 to 1.0 must still give a smooth image,
+    // without NaN pixels or folded-over regions.
+    let overshoot = ScaleBias::new(&perlin).set_scale(2.0);
+    let exponent = Exponent::new(&overshoot).set_exponent(0.5);
+
+    PlaneMapBuilder::new(&exponent)
+        .build()
+        .write_to_file("exponent_out_of_range.png");
 }
@@ -5,4 +5,20 @@ fn main() {
     let fbm = Fbm::new();
 
     PlaneMapBuilder::new(&fbm).build().write_to_file("fbm.png");
+
+    // 4-dimensional fBm animates smoothly as the plane moves through the
+    // extra dimensions, and loops once it has circled back to its start.
+    let animation = LoopingPlaneBuilder::new(&fbm).set_radius(0.5);
+
+    for frame in 0..8 {
+        animation
+            .build_frame(frame as f64 / 8.0)
+            .write_to_file(&format!("fbm_4d_frame_{}.png", frame));
+    }
+
+    let fbm = fbm.set_weighted_strength(0.5);
+
+    PlaneMapBuilder::new(&fbm)
+        .build()
+        .write_to_file("fbm_weighted_strength=0.5.png");
 }
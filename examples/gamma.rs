@@ -0,0 +1,13 @@
+use noice::{utils::*, Fbm, Gamma};
+
+fn main() {
+    let fbm = Fbm::new();
+
+    for &gamma in &[0.5, 2.2] {
+        let curve = Gamma::new(&fbm).set_gamma(gamma);
+
+        PlaneMapBuilder::new(&curve)
+            .build()
+            .write_to_file(&format!("gamma_{}.png", gamma));
+    }
+}
@@ -0,0 +1,39 @@
+//! Builds terrain from two octaves of Worley noise, which lay out the
+//! continents, followed by four octaves of Simplex noise for the detail.
+use noice::{utils::*, *};
+
+fn main() {
+    let continents = Worley::new().set_displacement(1.0);
+
+    let mut layers = vec![
+        Layer::new(continents).set_frequency(1.0).set_amplitude(1.0),
+        Layer::new(continents).set_frequency(2.0).set_amplitude(0.5),
+    ];
+    for octave in 0..4 {
+        let frequency = 4.0 * 2.0_f64.powi(octave);
+        layers.push(
+            Layer::new(Simplex::new())
+                .set_frequency(frequency)
+                .set_amplitude(0.25 / frequency),
+        );
+    }
+
+    let terrain = LayeredFractal::new(layers)
+        .expect("layers are not empty")
+        .set_seed(7);
+
+    let noise_map = PlaneMapBuilder::new(&terrain)
+        .set_size(512, 512)
+        .set_x_bounds(-2.0, 2.0)
+        .set_y_bounds(-2.0, 2.0)
+        .build();
+
+    noise_map.write_to_file("layered_fractal.png");
+
+    let terrain_gradient = ColorGradient::new().build_terrain_gradient();
+
+    ImageRenderer::new()
+        .set_gradient(terrain_gradient)
+        .render(&noise_map)
+        .write_to_file("layered_fractal_terrain.png");
+}
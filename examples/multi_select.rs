@@ -0,0 +1,24 @@
+//! A four-band biome map, with ocean, plains, hills and mountains chosen by a
+//! single fBm control and blended at the coastlines and foothills.
+use noice::{utils::*, Billow, Constant, Fbm, MultiSelect, NoiseFn, RidgedMulti, ScaleBias};
+
+fn main() {
+    let control = Fbm::new();
+
+    let ocean = Constant::new(-0.8);
+    let plains_base = Billow::new();
+    let plains = ScaleBias::new(&plains_base).set_scale(0.1).set_bias(-0.2);
+    let hills_base = Fbm::new();
+    let hills = ScaleBias::new(&hills_base).set_scale(0.3).set_bias(0.2);
+    let mountains_base = RidgedMulti::new();
+    let mountains = ScaleBias::new(&mountains_base).set_scale(0.4).set_bias(0.6);
+
+    let sources: Vec<&dyn NoiseFn<[f64; 3]>> = vec![&ocean, &plains, &hills, &mountains];
+    let biomes = MultiSelect::new(&control, vec![-0.2, 0.1, 0.4], sources)
+        .expect("thresholds are increasing and there are four sources")
+        .set_falloff(0.05);
+
+    PlaneMapBuilder::new(&biomes)
+        .build()
+        .write_to_file("multi_select.png");
+}
@@ -0,0 +1,21 @@
+use noice::{utils::*, Fbm, Perlin};
+
+fn main() {
+    let fbm = Fbm::new();
+
+    RGBMapBuilder::from_seeds(&fbm, [0, 1, 2])
+        .set_size(512, 512)
+        .set_x_bounds(-2.0, 2.0)
+        .set_y_bounds(-2.0, 2.0)
+        .build()
+        .write_to_file("noise_map_rgb_seeds.png");
+
+    let perlin = Perlin::new();
+
+    RGBMapBuilder::from_noise_fn_with_offsets(&perlin, RGBMapBuilder::DEFAULT_OFFSETS)
+        .set_size(512, 512)
+        .set_x_bounds(-2.0, 2.0)
+        .set_y_bounds(-2.0, 2.0)
+        .build()
+        .write_to_file("noise_map_rgb_offsets.png");
+}
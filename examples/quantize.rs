@@ -0,0 +1,28 @@
+use noice::{utils::*, Fbm, Quantize, QuantizeMode};
+
+fn main() {
+    let fbm = Fbm::new();
+
+    for &levels in &[4, 8, 32] {
+        let quantize = Quantize::new(&fbm, levels).unwrap();
+
+        PlaneMapBuilder::new(&quantize)
+            .build()
+            .write_to_file(&format!("quantize_{}.png", levels));
+    }
+
+    // Ease the transitions between the levels instead of snapping to them.
+    let quantize = Quantize::new(&fbm, 8).unwrap().set_smoothing(0.5);
+
+    PlaneMapBuilder::new(&quantize)
+        .build()
+        .write_to_file("quantize_smooth.png");
+
+    // Round to the centers of the bins instead, so the darkest and brightest
+    // levels stay clear of the ends of the range.
+    let bin_center = Quantize::with_mode(&fbm, 4, QuantizeMode::BinCenter).unwrap();
+
+    PlaneMapBuilder::new(&bin_center)
+        .build()
+        .write_to_file("quantize_bin_center_4.png");
+}
@@ -0,0 +1,12 @@
+use noice::{utils::*, Perlin, Remap};
+
+fn main() {
+    let perlin = Perlin::new();
+    let remap = Remap::new(&perlin, [-0.5, 0.5], [-1.0, 1.0])
+        .unwrap()
+        .set_clamp(true);
+
+    PlaneMapBuilder::new(&remap)
+        .build()
+        .write_to_file("remap.png");
+}
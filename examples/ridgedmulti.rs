@@ -7,4 +7,17 @@ fn main() {
     PlaneMapBuilder::new(&ridged_multi)
         .build()
         .write_to_file("ridged_multi.png");
+
+    // Low sharpness gives rounded hills, high sharpness thin, pointed ridges.
+    PlaneMapBuilder::new(&RidgedMulti::new().set_sharpness(0.5))
+        .build()
+        .write_to_file("ridged_multi_sharpness=0.5.png");
+    PlaneMapBuilder::new(&RidgedMulti::new().set_sharpness(6.0).set_gain(2.0))
+        .build()
+        .write_to_file("ridged_multi_sharpness=6_gain=2.png");
+
+    // An offset below 1.0 leaves flat valleys between isolated ridges.
+    PlaneMapBuilder::new(&RidgedMulti::new().set_offset(0.7).set_attenuation(4.0))
+        .build()
+        .write_to_file("ridged_multi_offset=0.7_attenuation=4.png");
 }
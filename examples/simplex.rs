@@ -0,0 +1,16 @@
+//! An example of using simplex noise
+use noice::{utils::*, Seedable, Simplex};
+
+fn main() {
+    let simplex = Simplex::new();
+
+    PlaneMapBuilder::new(&simplex)
+        .build()
+        .write_to_file("simplex.png");
+
+    let simplex = simplex.set_seed(1);
+
+    PlaneMapBuilder::new(&simplex)
+        .build()
+        .write_to_file("simplex_seed=1.png");
+}
@@ -0,0 +1,16 @@
+use noice::{utils::*, Cylinders, Perlin, SmoothMax, SmoothMin};
+
+fn main() {
+    let cyl = Cylinders::new();
+    let perlin = Perlin::new();
+
+    let smooth_min = SmoothMin::new(&cyl, &perlin).set_k(0.5);
+    PlaneMapBuilder::new(&smooth_min)
+        .build()
+        .write_to_file("smooth_min.png");
+
+    let smooth_max = SmoothMax::new(&cyl, &perlin).set_k(0.5);
+    PlaneMapBuilder::new(&smooth_max)
+        .build()
+        .write_to_file("smooth_max.png");
+}
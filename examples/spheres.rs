@@ -0,0 +1,10 @@
+use noice::{utils::*, Spheres};
+
+fn main() {
+    PlaneMapBuilder::new(&Spheres::new())
+        .build()
+        .write_to_file("spheres.png");
+    PlaneMapBuilder::new(&Spheres::new().set_frequency(5.0))
+        .build()
+        .write_to_file("spheres-f5.png");
+}
@@ -0,0 +1,12 @@
+use noice::{utils::*, Perlin, TileableNoise3D};
+
+fn main() {
+    let perlin = Perlin::new();
+    let tileable = TileableNoise3D::new(&perlin).set_tile_size([2.0, 2.0, 2.0]);
+
+    PlaneMapBuilder::new(&tileable)
+        .set_x_bounds(-2.0, 2.0)
+        .set_y_bounds(-2.0, 2.0)
+        .build()
+        .write_to_file("tileable_noise_3d.png");
+}
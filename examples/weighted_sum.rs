@@ -0,0 +1,23 @@
+use noice::{utils::*, Billow, Checkerboard, Cylinders, NoiseFn, Perlin, WeightedSum, Worley};
+
+fn main() {
+    let perlin = Perlin::new();
+    let billow = Billow::new();
+    let worley = Worley::new();
+    let cylinders = Cylinders::new();
+    let checkerboard = Checkerboard::default();
+
+    let sum = WeightedSum::new(vec![
+        (&perlin as &dyn NoiseFn<[f64; 3]>, 1.0),
+        (&billow, 0.5),
+        (&worley, 0.5),
+        (&cylinders, 0.25),
+        (&checkerboard, 0.125),
+    ])
+    .unwrap()
+    .set_normalize(true);
+
+    PlaneMapBuilder::new(&sum)
+        .build()
+        .write_to_file("weighted_sum.png");
+}
@@ -0,0 +1,13 @@
+//! An example of using white noise
+use noice::{utils::*, WhiteNoise};
+
+fn main() {
+    PlaneMapBuilder::new(&WhiteNoise::new())
+        .build()
+        .write_to_file("white_noise.png");
+
+    // One cell per pixel of the default 100x100 map spanning [-1, 1].
+    PlaneMapBuilder::new(&WhiteNoise::with_frequency(50.0))
+        .build()
+        .write_to_file("white_noise_per_pixel.png");
+}
@@ -0,0 +1,19 @@
+//! Contour bands over fBm, produced by wrapping its output.
+use noice::{utils::*, Fbm, ScaleBias, Wrap, WrapMode};
+
+fn main() {
+    let fbm = Fbm::new();
+    let scaled = ScaleBias::new(&fbm).set_scale(4.0);
+
+    let sawtooth = Wrap::new(&scaled);
+
+    PlaneMapBuilder::new(&sawtooth)
+        .build()
+        .write_to_file("wrap_sawtooth.png");
+
+    let triangle = Wrap::new(&scaled).set_mode(WrapMode::Triangle);
+
+    PlaneMapBuilder::new(&triangle)
+        .build()
+        .write_to_file("wrap_triangle.png");
+}
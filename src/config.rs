@@ -0,0 +1,518 @@
+//! Serializable descriptions of noise function graphs.
+//!
+//! A `NoiseCfg` mirrors the tree of noise functions it describes: generators
+//! at the leaves, with modifiers, combiners and transformers owning the
+//! configurations of their sources. `NoiseCfg::build_2d` and
+//! `NoiseCfg::build_3d` turn a configuration into a noise function, and
+//! reject parameters that the noise functions would choke on.
+//!
+//! Configurations can be read from and written to RON with the `ron` feature,
+//! and TOML with the `toml` feature. In RON, the graph reads much like the
+//! code that would build it:
+//!
+//! ```text
+//! Modifier(ScaleBias(
+//!     source: Fractal(Fbm(seed: 7, octaves: 4)),
+//!     scale: 0.5,
+//!     bias: 0.25,
+//! ))
+//! ```
+//!
+//! Parameters of generators and fractals that are left out take the default
+//! values of the noise function.
+
+use crate::{
+    noise_fns::{
+        Abs, Add, BasicMulti, Billow, Clamp, Constant, Exponent, Fbm, HybridMulti, Max, Min,
+        MultiFractal, Multiply, Negate, NoiseFn, OpenSimplex, Perlin, RidgedMulti, ScaleBias,
+        ScalePoint, Seedable, Simplex, SuperSimplex, TranslatePoint, Value, Worley,
+    },
+    Error,
+};
+use serde::{Deserialize, Serialize};
+
+/// Configuration of a noise function and, recursively, its sources.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum NoiseCfg {
+    Generator(GeneratorCfg),
+    Fractal(FractalCfg),
+    Modifier(ModifierCfg),
+    Combiner(CombinerCfg),
+    Transformer(TransformerCfg),
+}
+
+/// Configuration of a generator, which has no sources.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum GeneratorCfg {
+    Constant {
+        value: f64,
+    },
+    Perlin {
+        #[serde(default)]
+        seed: u32,
+    },
+    OpenSimplex {
+        #[serde(default)]
+        seed: u32,
+    },
+    Simplex {
+        #[serde(default)]
+        seed: u32,
+    },
+    SuperSimplex {
+        #[serde(default)]
+        seed: u32,
+    },
+    Value {
+        #[serde(default)]
+        seed: u32,
+    },
+    Worley {
+        #[serde(default)]
+        seed: u32,
+    },
+}
+
+/// Configuration of a fractal. The octaves of each fractal are always Perlin
+/// noise.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum FractalCfg {
+    Fbm(FractalParams),
+    Billow(FractalParams),
+    BasicMulti(FractalParams),
+    HybridMulti(FractalParams),
+    RidgedMulti(FractalParams),
+}
+
+/// Parameters shared by all fractals. Parameters that are `None` keep the
+/// default value of the fractal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FractalParams {
+    pub seed: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub octaves: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lacunarity: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persistence: Option<f64>,
+}
+
+/// Configuration of a modifier, which maps the output value of one source.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ModifierCfg {
+    Abs {
+        source: Box<NoiseCfg>,
+    },
+    Negate {
+        source: Box<NoiseCfg>,
+    },
+    Clamp {
+        source: Box<NoiseCfg>,
+        lower_bound: f64,
+        upper_bound: f64,
+    },
+    ScaleBias {
+        source: Box<NoiseCfg>,
+        scale: f64,
+        bias: f64,
+    },
+    Exponent {
+        source: Box<NoiseCfg>,
+        exponent: f64,
+    },
+}
+
+/// Configuration of a combiner, which merges the output values of two
+/// sources.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CombinerCfg {
+    Add {
+        source1: Box<NoiseCfg>,
+        source2: Box<NoiseCfg>,
+    },
+    Multiply {
+        source1: Box<NoiseCfg>,
+        source2: Box<NoiseCfg>,
+    },
+    Min {
+        source1: Box<NoiseCfg>,
+        source2: Box<NoiseCfg>,
+    },
+    Max {
+        source1: Box<NoiseCfg>,
+        source2: Box<NoiseCfg>,
+    },
+}
+
+/// Configuration of a transformer, which moves the input point before passing
+/// it to its source. The _z_ components are ignored by 2-dimensional noise.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum TransformerCfg {
+    ScalePoint {
+        source: Box<NoiseCfg>,
+        #[serde(default = "one")]
+        x_scale: f64,
+        #[serde(default = "one")]
+        y_scale: f64,
+        #[serde(default = "one")]
+        z_scale: f64,
+    },
+    TranslatePoint {
+        source: Box<NoiseCfg>,
+        #[serde(default)]
+        x_translation: f64,
+        #[serde(default)]
+        y_translation: f64,
+        #[serde(default)]
+        z_translation: f64,
+    },
+}
+
+fn one() -> f64 {
+    1.0
+}
+
+impl NoiseCfg {
+    /// Builds the 2-dimensional noise function described by the
+    /// configuration.
+    ///
+    /// Returns `Error::InvalidRange` if the bounds of a `Clamp` are inverted
+    /// or NaN, and `Error::InvalidParameter` if any other parameter is
+    /// infinite or NaN.
+    pub fn build_2d(&self) -> Result<Box<dyn NoiseFn<[f64; 2]>>, Error> {
+        self.build()
+    }
+
+    /// Builds the 3-dimensional noise function described by the
+    /// configuration, for use with the noise map builders.
+    ///
+    /// Returns the same errors as `build_2d`.
+    pub fn build_3d(&self) -> Result<Box<dyn NoiseFn<[f64; 3]>>, Error> {
+        self.build()
+    }
+
+    /// Reads a configuration from RON. Optional fields may be written without
+    /// `Some`.
+    #[cfg(feature = "ron")]
+    pub fn from_ron(s: &str) -> Result<Self, ron::error::SpannedError> {
+        ron_options().from_str(s)
+    }
+
+    /// Writes the configuration as pretty-printed RON, which `from_ron` reads
+    /// back.
+    #[cfg(feature = "ron")]
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron_options().to_string_pretty(self, ron::ser::PrettyConfig::new())
+    }
+
+    /// Reads a configuration from TOML.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+
+    /// Writes the configuration as TOML, which `from_toml` reads back.
+    #[cfg(feature = "toml")]
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string(self)
+    }
+
+    fn build<T>(&self) -> Result<Box<dyn NoiseFn<T>>, Error>
+    where
+        T: Copy + 'static,
+        Perlin: NoiseFn<T>,
+        OpenSimplex: NoiseFn<T>,
+        Simplex: NoiseFn<T>,
+        SuperSimplex: NoiseFn<T>,
+        Value: NoiseFn<T>,
+        Worley: NoiseFn<T>,
+        Fbm: NoiseFn<T>,
+        Billow: NoiseFn<T>,
+        BasicMulti: NoiseFn<T>,
+        HybridMulti: NoiseFn<T>,
+        RidgedMulti: NoiseFn<T>,
+        ScalePoint<Box<dyn NoiseFn<T>>>: NoiseFn<T>,
+        TranslatePoint<Box<dyn NoiseFn<T>>>: NoiseFn<T>,
+    {
+        Ok(match self {
+            Self::Generator(generator) => match *generator {
+                GeneratorCfg::Constant { value } => Box::new(Constant::new(finite(value)?)),
+                GeneratorCfg::Perlin { seed } => Box::new(Perlin::new().set_seed(seed)),
+                GeneratorCfg::OpenSimplex { seed } => Box::new(OpenSimplex::new().set_seed(seed)),
+                GeneratorCfg::Simplex { seed } => Box::new(Simplex::new().set_seed(seed)),
+                GeneratorCfg::SuperSimplex { seed } => Box::new(SuperSimplex::new().set_seed(seed)),
+                GeneratorCfg::Value { seed } => Box::new(Value::new().set_seed(seed)),
+                GeneratorCfg::Worley { seed } => Box::new(Worley::new().set_seed(seed)),
+            },
+            Self::Fractal(fractal) => match fractal {
+                FractalCfg::Fbm(params) => Box::new(params.apply(Fbm::new())?),
+                FractalCfg::Billow(params) => Box::new(params.apply(Billow::new())?),
+                FractalCfg::BasicMulti(params) => Box::new(params.apply(BasicMulti::new())?),
+                FractalCfg::HybridMulti(params) => Box::new(params.apply(HybridMulti::new())?),
+                FractalCfg::RidgedMulti(params) => Box::new(params.apply(RidgedMulti::new())?),
+            },
+            Self::Modifier(modifier) => match modifier {
+                ModifierCfg::Abs { source } => {
+                    let source = source.build()?;
+                    Box::new(Node(move |point| Abs::new(&*source).get(point)))
+                }
+                ModifierCfg::Negate { source } => {
+                    let source = source.build()?;
+                    Box::new(Node(move |point| Negate::new(&*source).get(point)))
+                }
+                ModifierCfg::Clamp {
+                    source,
+                    lower_bound,
+                    upper_bound,
+                } => {
+                    let source = source.build()?;
+                    let (lower_bound, upper_bound) = (*lower_bound, *upper_bound);
+                    // The bounds are checked once here, so the node can set
+                    // them unchecked on every call.
+                    Clamp::new(&*source).try_set_bounds(lower_bound, upper_bound)?;
+                    Box::new(Node(move |point| {
+                        Clamp::new(&*source)
+                            .set_bounds(lower_bound, upper_bound)
+                            .get(point)
+                    }))
+                }
+                ModifierCfg::ScaleBias {
+                    source,
+                    scale,
+                    bias,
+                } => {
+                    let source = source.build()?;
+                    let (scale, bias) = (finite(*scale)?, finite(*bias)?);
+                    Box::new(Node(move |point| {
+                        ScaleBias::new(&*source)
+                            .set_scale(scale)
+                            .set_bias(bias)
+                            .get(point)
+                    }))
+                }
+                ModifierCfg::Exponent { source, exponent } => {
+                    let source = source.build()?;
+                    let exponent = finite(*exponent)?;
+                    Box::new(Node(move |point| {
+                        Exponent::new(&*source).set_exponent(exponent).get(point)
+                    }))
+                }
+            },
+            Self::Combiner(combiner) => match combiner {
+                CombinerCfg::Add { source1, source2 } => {
+                    let (source1, source2) = (source1.build()?, source2.build()?);
+                    Box::new(Node(move |point| Add::new(&*source1, &*source2).get(point)))
+                }
+                CombinerCfg::Multiply { source1, source2 } => {
+                    let (source1, source2) = (source1.build()?, source2.build()?);
+                    Box::new(Node(move |point| {
+                        Multiply::new(&*source1, &*source2).get(point)
+                    }))
+                }
+                CombinerCfg::Min { source1, source2 } => {
+                    let (source1, source2) = (source1.build()?, source2.build()?);
+                    Box::new(Node(move |point| Min::new(&*source1, &*source2).get(point)))
+                }
+                CombinerCfg::Max { source1, source2 } => {
+                    let (source1, source2) = (source1.build()?, source2.build()?);
+                    Box::new(Node(move |point| Max::new(&*source1, &*source2).get(point)))
+                }
+            },
+            Self::Transformer(transformer) => match transformer {
+                TransformerCfg::ScalePoint {
+                    source,
+                    x_scale,
+                    y_scale,
+                    z_scale,
+                } => Box::new(
+                    ScalePoint::new(source.build()?)
+                        .set_x_scale(finite(*x_scale)?)
+                        .set_y_scale(finite(*y_scale)?)
+                        .set_z_scale(finite(*z_scale)?),
+                ),
+                TransformerCfg::TranslatePoint {
+                    source,
+                    x_translation,
+                    y_translation,
+                    z_translation,
+                } => Box::new(
+                    TranslatePoint::new(source.build()?)
+                        .set_x_translation(finite(*x_translation)?)
+                        .set_y_translation(finite(*y_translation)?)
+                        .set_z_translation(finite(*z_translation)?),
+                ),
+            },
+        })
+    }
+}
+
+impl FractalParams {
+    fn apply<F: MultiFractal + Seedable>(&self, fractal: F) -> Result<F, Error> {
+        let mut fractal = fractal.set_seed(self.seed);
+
+        if let Some(octaves) = self.octaves {
+            fractal = fractal.set_octaves(octaves);
+        }
+        if let Some(frequency) = self.frequency {
+            fractal = fractal.set_frequency(finite(frequency)?);
+        }
+        if let Some(lacunarity) = self.lacunarity {
+            fractal = fractal.set_lacunarity(finite(lacunarity)?);
+        }
+        if let Some(persistence) = self.persistence {
+            fractal = fractal.set_persistence(finite(persistence)?);
+        }
+
+        Ok(fractal)
+    }
+}
+
+fn finite(value: f64) -> Result<f64, Error> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(Error::InvalidParameter)
+    }
+}
+
+/// Noise function evaluated by a closure, which owns the sources of noise
+/// functions that only borrow theirs and builds them on each call.
+struct Node<F>(F);
+
+impl<T, F> NoiseFn<T> for Node<F>
+where
+    F: Fn(T) -> f64,
+{
+    fn get(&self, point: T) -> f64 {
+        (self.0)(point)
+    }
+}
+
+#[cfg(feature = "ron")]
+fn ron_options() -> ron::Options {
+    use ron::extensions::Extensions;
+
+    ron::Options::default()
+        .with_default_extension(Extensions::IMPLICIT_SOME | Extensions::UNWRAP_VARIANT_NEWTYPES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_cfg() -> NoiseCfg {
+        let fbm = NoiseCfg::Fractal(FractalCfg::Fbm(FractalParams {
+            seed: 7,
+            octaves: Some(4),
+            ..FractalParams::default()
+        }));
+        let worley = NoiseCfg::Transformer(TransformerCfg::ScalePoint {
+            source: Box::new(NoiseCfg::Generator(GeneratorCfg::Worley { seed: 3 })),
+            x_scale: 2.0,
+            y_scale: 2.0,
+            z_scale: 1.0,
+        });
+
+        NoiseCfg::Modifier(ModifierCfg::ScaleBias {
+            source: Box::new(NoiseCfg::Combiner(CombinerCfg::Max {
+                source1: Box::new(fbm),
+                source2: Box::new(worley),
+            })),
+            scale: 0.5,
+            bias: 0.25,
+        })
+    }
+
+    #[test]
+    fn built_graph_matches_hand_built_graph() {
+        let fbm = Fbm::new().set_seed(7).set_octaves(4);
+        let worley = ScalePoint::new(Worley::new().set_seed(3))
+            .set_x_scale(2.0)
+            .set_y_scale(2.0);
+        let (max_2d, max_3d) = (Max::new(&fbm, &worley), Max::new(&fbm, &worley));
+        let expected_2d = ScaleBias::new(&max_2d).set_scale(0.5).set_bias(0.25);
+        let expected_3d = ScaleBias::new(&max_3d).set_scale(0.5).set_bias(0.25);
+
+        let cfg = sample_cfg();
+        let (noise_2d, noise_3d) = (cfg.build_2d().unwrap(), cfg.build_3d().unwrap());
+
+        for i in 0..50 {
+            let [x, y, z] = [i as f64 * 0.37, i as f64 * -0.21, 0.5];
+            assert_eq!(noise_2d.get([x, y]), expected_2d.get([x, y]));
+            assert_eq!(noise_3d.get([x, y, z]), expected_3d.get([x, y, z]));
+        }
+    }
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        let clamp = |lower_bound, upper_bound| {
+            NoiseCfg::Modifier(ModifierCfg::Clamp {
+                source: Box::new(NoiseCfg::Generator(GeneratorCfg::Perlin { seed: 0 })),
+                lower_bound,
+                upper_bound,
+            })
+        };
+        assert!(clamp(-0.5, 0.5).build_2d().is_ok());
+        assert_eq!(clamp(0.5, -0.5).build_2d().err(), Some(Error::InvalidRange));
+        assert_eq!(
+            clamp(f64::NAN, 0.5).build_3d().err(),
+            Some(Error::InvalidRange)
+        );
+
+        let fbm = NoiseCfg::Fractal(FractalCfg::Fbm(FractalParams {
+            frequency: Some(f64::INFINITY),
+            ..FractalParams::default()
+        }));
+        assert_eq!(fbm.build_3d().err(), Some(Error::InvalidParameter));
+
+        // Errors in nested sources are passed up.
+        let scaled = NoiseCfg::Modifier(ModifierCfg::ScaleBias {
+            source: Box::new(clamp(1.0, 0.0)),
+            scale: 1.0,
+            bias: 0.0,
+        });
+        assert_eq!(scaled.build_2d().err(), Some(Error::InvalidRange));
+
+        let translated = NoiseCfg::Transformer(TransformerCfg::TranslatePoint {
+            source: Box::new(NoiseCfg::Generator(GeneratorCfg::Constant { value: 0.0 })),
+            x_translation: f64::NAN,
+            y_translation: 0.0,
+            z_translation: 0.0,
+        });
+        assert_eq!(translated.build_2d().err(), Some(Error::InvalidParameter));
+    }
+
+    #[test]
+    #[cfg(feature = "ron")]
+    fn ron_round_trip() {
+        let cfg = sample_cfg();
+        assert_eq!(NoiseCfg::from_ron(&cfg.to_ron().unwrap()).unwrap(), cfg);
+
+        let cfg = NoiseCfg::from_ron("Fractal(RidgedMulti(seed: 2, lacunarity: 2.5))").unwrap();
+        assert_eq!(
+            cfg,
+            NoiseCfg::Fractal(FractalCfg::RidgedMulti(FractalParams {
+                seed: 2,
+                lacunarity: Some(2.5),
+                ..FractalParams::default()
+            }))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn toml_round_trip() {
+        let cfg = sample_cfg();
+        assert_eq!(NoiseCfg::from_toml(&cfg.to_toml().unwrap()).unwrap(), cfg);
+
+        let cfg = NoiseCfg::from_toml("[Generator.Simplex]").unwrap();
+        assert_eq!(cfg, NoiseCfg::Generator(GeneratorCfg::Simplex { seed: 0 }));
+    }
+}
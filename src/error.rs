@@ -0,0 +1,84 @@
+use core::fmt;
+
+/// Error returned when a noise function is built with invalid parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// No source functions, or layers, were given.
+    NoSources,
+
+    /// The number of source functions doesn't match the other parameters.
+    SourceCountMismatch {
+        /// Number of source functions needed.
+        expected: usize,
+
+        /// Number of source functions given.
+        found: usize,
+    },
+
+    /// The ends of a range are in the wrong order, equal where the range must
+    /// not be empty, or not finite.
+    InvalidRange,
+
+    /// A parameter is outside the values the noise function accepts.
+    InvalidParameter,
+
+    /// There are fewer control points than the noise function needs.
+    TooFewControlPoints {
+        /// Number of control points needed.
+        required: usize,
+
+        /// Number of control points given.
+        found: usize,
+    },
+
+    /// Two control points have the same input value.
+    DuplicateControlPoints,
+
+    /// A control point is infinite or NaN.
+    NonFiniteValue,
+
+    /// The thresholds are not finite and strictly increasing.
+    ThresholdsNotIncreasing,
+
+    /// There are fewer levels than the noise function needs.
+    TooFewLevels {
+        /// Number of levels needed.
+        required: usize,
+
+        /// Number of levels given.
+        found: usize,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoSources => write!(f, "at least one source function is needed"),
+            Self::SourceCountMismatch { expected, found } => write!(
+                f,
+                "{} source functions are needed, but {} were given",
+                expected, found
+            ),
+            Self::InvalidRange => write!(f, "the range is empty, inverted or not finite"),
+            Self::InvalidParameter => write!(f, "a parameter is out of range"),
+            Self::TooFewControlPoints { required, found } => write!(
+                f,
+                "at least {} control points are needed, but {} were given",
+                required, found
+            ),
+            Self::DuplicateControlPoints => write!(f, "the control points must be distinct"),
+            Self::NonFiniteValue => write!(f, "the control points must be finite"),
+            Self::ThresholdsNotIncreasing => {
+                write!(f, "the thresholds must be finite and strictly increasing")
+            }
+            Self::TooFewLevels { required, found } => write!(
+                f,
+                "at least {} levels are needed, but {} were given",
+                required, found
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
@@ -1,9 +1,191 @@
+//! Unit-length gradient vectors used by the gradient noise functions.
+//!
+//! Each function maps an index, usually the hash of a lattice point, onto one
+//! of a fixed set of gradients. Indices wrap around the size of the set.
+//!
+//! The gradient noise functions that take their gradients from this module
+//! (`OpenSimplex`, `PerlinSurflet` and `SuperSimplex`) look them up through
+//! the `GradientTable` trait, so a different set of gradients can be given
+//! to them with `with_gradients`. `DefaultGradients` provides the sets
+//! below. `Simplex` looks its gradients up the same way, but defaults to the
+//! sets of `SimplexGradients`.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// A set of gradients for each dimension, looked up by the hash of a lattice
+/// point.
+///
+/// Implementations must accept any index, wrapping it around the size of
+/// their sets.
+pub trait GradientTable {
+    fn get2(&self, index: usize) -> [f64; 2];
+
+    fn get3(&self, index: usize) -> [f64; 3];
+
+    fn get4(&self, index: usize) -> [f64; 4];
+}
+
+/// The gradients returned by `get2`, `get3` and `get4` of this module.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultGradients;
+
+impl GradientTable for DefaultGradients {
+    #[inline(always)]
+    fn get2(&self, index: usize) -> [f64; 2] {
+        get2(index)
+    }
+
+    #[inline(always)]
+    fn get3(&self, index: usize) -> [f64; 3] {
+        get3(index)
+    }
+
+    #[inline(always)]
+    fn get4(&self, index: usize) -> [f64; 4] {
+        get4(index)
+    }
+}
+
+/// The gradients `Simplex` uses by default.
+///
+/// Unlike the sets of `DefaultGradients`, these don't have unit length: their
+/// components are all -1, 0 or 1, so that the dot products with them are
+/// exact. The 2-dimensional set holds the 4 axis-aligned and the 4 diagonal
+/// vectors, the 3-dimensional set the 12 vectors towards the edges of a cube,
+/// and the 4-dimensional set the 32 vectors towards the edges of a
+/// tesseract.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SimplexGradients;
+
+impl GradientTable for SimplexGradients {
+    #[inline(always)]
+    #[rustfmt::skip]
+    fn get2(&self, index: usize) -> [f64; 2] {
+        match index & 0b111 {
+            0 => [ 1.0,  1.0],
+            1 => [-1.0,  1.0],
+            2 => [ 1.0, -1.0],
+            3 => [-1.0, -1.0],
+            4 => [ 1.0,  0.0],
+            5 => [-1.0,  0.0],
+            6 => [ 0.0,  1.0],
+            7 => [ 0.0, -1.0],
+            _ => unreachable!(),
+        }
+    }
+
+    #[inline(always)]
+    #[rustfmt::skip]
+    fn get3(&self, index: usize) -> [f64; 3] {
+        match index % 12 {
+            0  => [ 1.0,  1.0,  0.0],
+            1  => [-1.0,  1.0,  0.0],
+            2  => [ 1.0, -1.0,  0.0],
+            3  => [-1.0, -1.0,  0.0],
+            4  => [ 1.0,  0.0,  1.0],
+            5  => [-1.0,  0.0,  1.0],
+            6  => [ 1.0,  0.0, -1.0],
+            7  => [-1.0,  0.0, -1.0],
+            8  => [ 0.0,  1.0,  1.0],
+            9  => [ 0.0, -1.0,  1.0],
+            10 => [ 0.0,  1.0, -1.0],
+            11 => [ 0.0, -1.0, -1.0],
+            _  => unreachable!(),
+        }
+    }
+
+    #[inline(always)]
+    fn get4(&self, index: usize) -> [f64; 4] {
+        // The top two bits select the component that is zero, and the bottom
+        // three bits the signs of the other three.
+        let index = index & 0b11111;
+        let sign = |bit: usize| if index & bit == 0 { 1.0 } else { -1.0 };
+        let (a, b, c) = (sign(0b100), sign(0b010), sign(0b001));
+
+        match index >> 3 {
+            0 => [0.0, a, b, c],
+            1 => [a, 0.0, b, c],
+            2 => [a, b, 0.0, c],
+            3 => [a, b, c, 0.0],
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// A user-supplied set of gradients for each dimension.
+///
+/// The gradients should have unit length; longer gradients raise the output
+/// of the noise functions beyond the [-1,1] range.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct CustomGradients {
+    gradients2: Vec<[f64; 2]>,
+    gradients3: Vec<[f64; 3]>,
+    gradients4: Vec<[f64; 4]>,
+}
+
+#[cfg(feature = "alloc")]
+impl CustomGradients {
+    /// Creates a table from the gradients for each dimension.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the sets is empty.
+    pub fn new(
+        gradients2: Vec<[f64; 2]>,
+        gradients3: Vec<[f64; 3]>,
+        gradients4: Vec<[f64; 4]>,
+    ) -> Self {
+        assert!(
+            !gradients2.is_empty() && !gradients3.is_empty() && !gradients4.is_empty(),
+            "every set of gradients must hold at least one gradient"
+        );
+
+        Self {
+            gradients2,
+            gradients3,
+            gradients4,
+        }
+    }
+
+    /// Creates a table holding the same gradients as `DefaultGradients`, as
+    /// a starting point for modifying them.
+    pub fn from_defaults() -> Self {
+        Self::new(
+            (0..8).map(get2).collect(),
+            (0..32).map(get3).collect(),
+            (0..64).map(get4).collect(),
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl GradientTable for CustomGradients {
+    #[inline]
+    fn get2(&self, index: usize) -> [f64; 2] {
+        self.gradients2[index % self.gradients2.len()]
+    }
+
+    #[inline]
+    fn get3(&self, index: usize) -> [f64; 3] {
+        self.gradients3[index % self.gradients3.len()]
+    }
+
+    #[inline]
+    fn get4(&self, index: usize) -> [f64; 4] {
+        self.gradients4[index % self.gradients4.len()]
+    }
+}
+
+/// Returns one of 8 two-dimensional gradients: the 4 axis-aligned and the 4
+/// diagonal unit vectors.
 #[inline(always)]
 #[rustfmt::skip]
-pub(crate) fn get2(index: usize) -> [f64; 2] {
+pub fn get2(index: usize) -> [f64; 2] {
     // Vectors are combinations of -1, 0, and 1
     // Precompute the normalized element
-    const DIAG : f64 = std::f64::consts::FRAC_1_SQRT_2;
+    const DIAG : f64 = core::f64::consts::FRAC_1_SQRT_2;
 
     match index % 8 {
         0 => [  1.0,   0.0],
@@ -18,12 +200,15 @@ pub(crate) fn get2(index: usize) -> [f64; 2] {
     }
 }
 
+/// Returns one of 32 three-dimensional gradients: the 12 unit vectors towards
+/// the edges of a cube, which are twice as likely, and the 8 towards its
+/// corners.
 #[inline(always)]
 #[rustfmt::skip]
-pub(crate) fn get3(index: usize) -> [f64; 3] {
+pub fn get3(index: usize) -> [f64; 3] {
     // Vectors are combinations of -1, 0, and 1
     // Precompute the normalized elements
-    const DIAG : f64 = std::f64::consts::FRAC_1_SQRT_2;
+    const DIAG : f64 = core::f64::consts::FRAC_1_SQRT_2;
     const DIAG2 : f64 = 0.577_350_269_189_625_8;
 
     match index % 32 {
@@ -52,9 +237,12 @@ pub(crate) fn get3(index: usize) -> [f64; 3] {
     }
 }
 
+/// Returns one of 64 four-dimensional gradients: the 32 unit vectors towards
+/// the edges of a tesseract and the 16 towards its corners, which are twice
+/// as likely.
 #[inline(always)]
 #[rustfmt::skip]
-pub(crate) fn get4(index: usize) -> [f64; 4] {
+pub fn get4(index: usize) -> [f64; 4] {
     // Vectors are combinations of -1, 0, and 1
     // Precompute the normalized elements
     const DIAG : f64 = 0.577_350_269_189_625_8;
@@ -113,3 +301,74 @@ pub(crate) fn get4(index: usize) -> [f64; 4] {
         _       => panic!("Attempt to access gradient {} of 64", index % 64),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NoiseFn, OpenSimplex, PerlinSurflet, Seedable, SuperSimplex};
+
+    #[test]
+    fn gradients_have_unit_length() {
+        for index in 0..64 {
+            let length2: f64 = get2(index).iter().map(|v| v * v).sum();
+            let length3: f64 = get3(index).iter().map(|v| v * v).sum();
+            let length4: f64 = get4(index).iter().map(|v| v * v).sum();
+
+            assert!((length2 - 1.0).abs() < 1e-12);
+            assert!((length3 - 1.0).abs() < 1e-12);
+            assert!((length4 - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn explicit_default_gradients_match() {
+        let surflet = PerlinSurflet::new().set_seed(7);
+        let open_simplex = OpenSimplex::new().set_seed(7);
+        let super_simplex = SuperSimplex::new().set_seed(7);
+
+        let custom_surflet =
+            PerlinSurflet::with_gradients(CustomGradients::from_defaults()).set_seed(7);
+        let custom_open_simplex =
+            OpenSimplex::with_gradients(CustomGradients::from_defaults()).set_seed(7);
+        let custom_super_simplex =
+            SuperSimplex::with_gradients(CustomGradients::from_defaults()).set_seed(7);
+
+        for i in 0..200 {
+            let point = [
+                i as f64 * 0.173 - 9.0,
+                i as f64 * -0.291 + 4.0,
+                i as f64 * 0.05,
+            ];
+            let point2 = [point[0], point[1]];
+            let point4 = [point[0], point[1], point[2], point[0] * 0.5];
+
+            assert_eq!(custom_surflet.get(point2), surflet.get(point2));
+            assert_eq!(custom_surflet.get(point), surflet.get(point));
+            assert_eq!(custom_surflet.get(point4), surflet.get(point4));
+            assert_eq!(custom_open_simplex.get(point2), open_simplex.get(point2));
+            assert_eq!(custom_open_simplex.get(point), open_simplex.get(point));
+            assert_eq!(custom_open_simplex.get(point4), open_simplex.get(point4));
+            assert_eq!(custom_super_simplex.get(point2), super_simplex.get(point2));
+            assert_eq!(custom_super_simplex.get(point), super_simplex.get(point));
+        }
+    }
+
+    #[test]
+    fn custom_gradients_are_used() {
+        // Every lattice point has the same gradient along the x axis, so the
+        // noise rises through each lattice point along that axis.
+        let gradients = CustomGradients::new(
+            vec![[1.0, 0.0]],
+            vec![[1.0, 0.0, 0.0]],
+            vec![[1.0, 0.0, 0.0, 0.0]],
+        );
+        let surflet = PerlinSurflet::with_gradients(gradients);
+
+        for i in 1..10 {
+            let offset = i as f64 * 0.05;
+            let (a, b) = (surflet.get([offset, 0.0]), surflet.get([-offset, 0.0]));
+            assert!(a > 0.0);
+            assert!((a + b).abs() < 1e-12, "{} != {}", a, -b);
+        }
+    }
+}
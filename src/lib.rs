@@ -8,13 +8,53 @@
 //! let perlin = Perlin::new();
 //! let val = perlin.get([42.4, 37.7, 2.8]);
 //! ```
+//!
+//! # `no_std`
+//!
+//! The crate can be built without the standard library by disabling the
+//! default features. The `NoiseFn` trait and the generators that don't
+//! allocate, such as `Simplex`, `Perlin`, `Value` and `Worley`, are always
+//! available, with the floating point functions they need provided by the
+//! `libm` feature. The fractals need the `alloc` feature, and the rest of the
+//! crate, including the modifiers, combiners and `utils`, requires the `std`
+//! feature.
+//!
+//! ```toml
+//! [dependencies]
+//! noice = { version = "0.7", default-features = false, features = ["libm", "alloc"] }
+//! ```
 
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 #![deny(missing_copy_implementations)]
 
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either the `std` or the `libm` feature must be enabled");
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub use crate::error::Error;
 pub use crate::noise_fns::*;
+#[cfg(feature = "std")]
+pub use crate::pipeline::NoisePipeline;
+pub use crate::seed::seed_from_str;
+#[cfg(feature = "wasm")]
+pub use crate::wasm::WasmNoise;
 
-mod gradient;
+#[cfg(feature = "serde")]
+pub mod config;
+mod error;
+pub mod gradient;
 mod math;
 mod noise_fns;
 mod permutationtable;
+#[cfg(feature = "std")]
+mod pipeline;
+mod seed;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "std")]
 pub mod utils;
+#[cfg(feature = "wasm")]
+pub mod wasm;
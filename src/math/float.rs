@@ -0,0 +1,191 @@
+//! Floating point functions that are not available in `core`.
+//!
+//! With the `std` feature these forward to the intrinsics of the standard
+//! library. Without it they are provided by `libm`, which must then be
+//! enabled through the `libm` feature. Both give bitwise identical results,
+//! so a noise function outputs the same values whichever is used.
+//!
+//! Apart from `powf` and `ln`, every function here is correctly rounded or
+//! exact, so the results are also identical across platforms. Functions whose
+//! precision is left unspecified by the standard library, such as `f64::powi`,
+//! are implemented here instead. `powf` and `ln` are only used to derive
+//! parameters such as octave amplitudes, and their last bit may differ
+//! between `std` and `libm`.
+
+/// Rounds `x` down to the nearest integer.
+#[inline(always)]
+pub(crate) fn floor(x: f64) -> f64 {
+    #[cfg(feature = "std")]
+    {
+        x.floor()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        libm::floor(x)
+    }
+}
+
+/// Rounds `x` up to the nearest integer.
+#[inline(always)]
+pub(crate) fn ceil(x: f64) -> f64 {
+    #[cfg(feature = "std")]
+    {
+        x.ceil()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        libm::ceil(x)
+    }
+}
+
+/// Returns the square root of `x`.
+#[inline(always)]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    #[cfg(feature = "std")]
+    {
+        x.sqrt()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        libm::sqrt(x)
+    }
+}
+
+/// Calculates `x * a + b` with a single rounding.
+#[inline(always)]
+pub(crate) fn mul_add(x: f64, a: f64, b: f64) -> f64 {
+    #[cfg(feature = "std")]
+    {
+        x.mul_add(a, b)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        libm::fma(x, a, b)
+    }
+}
+
+/// Raises `x` to the power `n`.
+#[inline(always)]
+pub(crate) fn powf(x: f64, n: f64) -> f64 {
+    #[cfg(feature = "std")]
+    {
+        x.powf(n)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        libm::pow(x, n)
+    }
+}
+
+/// Returns the natural logarithm of `x`.
+#[inline(always)]
+pub(crate) fn ln(x: f64) -> f64 {
+    #[cfg(feature = "std")]
+    {
+        x.ln()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        libm::log(x)
+    }
+}
+
+/// Raises `x` to the integer power `n` by repeated squaring.
+///
+/// Unlike `f64::powi`, which may be evaluated with a different precision
+/// depending on the platform and optimization level, the multiplications are
+/// always performed in the same order.
+#[inline]
+pub(crate) fn powi(x: f64, n: i32) -> f64 {
+    let mut base = x;
+    let mut exponent = n.unsigned_abs();
+    let mut result = 1.0;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result *= base;
+        }
+        base *= base;
+        exponent >>= 1;
+    }
+
+    if n < 0 {
+        1.0 / result
+    } else {
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Values around the integers, the signed zeros and the extremes, where
+    // rounding differences would show up first.
+    static SPECIAL: [f64; 11] = [
+        0.0,
+        -0.0,
+        0.5,
+        -0.5,
+        1.0 - f64::EPSILON,
+        -1.0 + f64::EPSILON,
+        f64::MIN_POSITIVE,
+        f64::MAX,
+        f64::MIN,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+    ];
+
+    fn samples() -> impl Iterator<Item = f64> {
+        SPECIAL
+            .iter()
+            .copied()
+            .chain((-2000..2000).map(|i| i as f64 * 0.173_205_080_756_887_7))
+    }
+
+    #[test]
+    fn powi_multiplies_in_order() {
+        assert_eq!(powi(0.5, 0), 1.0);
+        assert_eq!(powi(0.5, 3), 0.125);
+        assert_eq!(powi(2.0, -2), 0.25);
+
+        let x = 0.173_205_080_756_887_7;
+        assert_eq!(powi(x, 4).to_bits(), ((x * x) * (x * x)).to_bits());
+    }
+
+    #[test]
+    fn floor_matches_libm() {
+        for x in samples() {
+            assert_eq!(floor(x).to_bits(), libm::floor(x).to_bits(), "floor({})", x);
+        }
+    }
+
+    #[test]
+    fn ceil_matches_libm() {
+        for x in samples() {
+            assert_eq!(ceil(x).to_bits(), libm::ceil(x).to_bits(), "ceil({})", x);
+        }
+    }
+
+    #[test]
+    fn sqrt_matches_libm() {
+        for x in samples().filter(|x| x.is_sign_positive()) {
+            assert_eq!(sqrt(x).to_bits(), libm::sqrt(x).to_bits(), "sqrt({})", x);
+        }
+    }
+
+    #[test]
+    fn mul_add_matches_libm() {
+        for x in samples().filter(|x| x.is_finite()) {
+            let (a, b) = (x * 0.7, -x / 3.0);
+            assert_eq!(
+                mul_add(x, a, b).to_bits(),
+                libm::fma(x, a, b).to_bits(),
+                "mul_add({}, {}, {})",
+                x,
+                a,
+                b
+            );
+        }
+    }
+}
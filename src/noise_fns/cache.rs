@@ -1,5 +1,5 @@
 use crate::noise_fns::NoiseFn;
-use std::cell::{Cell, RefCell};
+use std::sync::{Mutex, TryLockError};
 
 /// Noise function that caches the last output value generated by the source
 /// function.
@@ -14,22 +14,91 @@ use std::cell::{Cell, RefCell};
 /// multiple noise functions. If a source function is not cached, the source
 /// function will redundantly calculate the same output value once for each
 /// noise function in which it is included.
-#[derive(Clone, Debug)]
+///
+/// The cached entry is guarded by a lock, so a `Cache` can be shared between
+/// threads whenever its source can. A thread that finds the entry locked by
+/// another thread evaluates the source directly instead of waiting, and the
+/// value returned for a point always comes from that point.
+#[derive(Debug)]
 pub struct Cache<Source> {
     /// Outputs the value to be cached.
     pub source: Source,
 
-    value: Cell<Option<f64>>,
+    entry: Mutex<Option<Entry>>,
+}
+
+/// The last point evaluated by a `Cache`, with the value of the source there.
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    point: [f64; 4],
+    dimensions: usize,
+    value: f64,
+}
+
+impl Entry {
+    fn new(point: &[f64], value: f64) -> Self {
+        let mut padded = [0.0; 4];
+        padded[..point.len()].copy_from_slice(point);
+
+        Self {
+            point: padded,
+            dimensions: point.len(),
+            value,
+        }
+    }
 
-    point: RefCell<Vec<f64>>,
+    fn point(&self) -> &[f64] {
+        &self.point[..self.dimensions]
+    }
 }
 
 impl<Source> Cache<Source> {
     pub fn new(source: Source) -> Self {
         Cache {
             source,
-            value: Cell::new(None),
-            point: RefCell::new(Vec::new()),
+            entry: Mutex::new(None),
+        }
+    }
+
+    /// Returns the cached value if `point` is the cached point, or evaluates
+    /// `get` and caches its value otherwise.
+    fn get_cached(&self, point: &[f64], get: impl FnOnce() -> f64) -> f64 {
+        if let Some(entry) = self.try_entry() {
+            if entry.point() == point {
+                return entry.value;
+            }
+        }
+
+        // The lock is not held while the source is evaluated, so other
+        // threads can keep reading the old entry in the meantime.
+        let value = get();
+        self.try_store(Entry::new(point, value));
+
+        value
+    }
+
+    fn try_entry(&self) -> Option<Entry> {
+        match self.entry.try_lock() {
+            Ok(entry) => *entry,
+            Err(TryLockError::Poisoned(entry)) => *entry.into_inner(),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+
+    fn try_store(&self, new_entry: Entry) {
+        match self.entry.try_lock() {
+            Ok(mut entry) => *entry = Some(new_entry),
+            Err(TryLockError::Poisoned(entry)) => *entry.into_inner() = Some(new_entry),
+            Err(TryLockError::WouldBlock) => {}
+        }
+    }
+}
+
+impl<Source: Clone> Clone for Cache<Source> {
+    fn clone(&self) -> Self {
+        Cache {
+            source: self.source.clone(),
+            entry: Mutex::new(self.try_entry()),
         }
     }
 }
@@ -39,19 +108,7 @@ where
     Source: NoiseFn<[f64; 2]>,
 {
     fn get(&self, point: [f64; 2]) -> f64 {
-        match self.value.get() {
-            Some(value) if quick_eq(&*self.point.borrow(), &point) => value,
-            Some(_) | None => {
-                let value = self.source.get(point);
-                self.value.set(Some(value));
-
-                let mut cached_point = self.point.borrow_mut();
-                cached_point.clear();
-                cached_point.extend_from_slice(&point);
-
-                value
-            }
-        }
+        self.get_cached(&point, || self.source.get(point))
     }
 }
 
@@ -60,19 +117,7 @@ where
     Source: NoiseFn<[f64; 3]>,
 {
     fn get(&self, point: [f64; 3]) -> f64 {
-        match self.value.get() {
-            Some(value) if quick_eq(&*self.point.borrow(), &point) => value,
-            Some(_) | None => {
-                let value = self.source.get(point);
-                self.value.set(Some(value));
-
-                let mut cached_point = self.point.borrow_mut();
-                cached_point.clear();
-                cached_point.extend_from_slice(&point);
-
-                value
-            }
-        }
+        self.get_cached(&point, || self.source.get(point))
     }
 }
 
@@ -81,24 +126,67 @@ where
     Source: NoiseFn<[f64; 4]>,
 {
     fn get(&self, point: [f64; 4]) -> f64 {
-        match self.value.get() {
-            Some(value) if quick_eq(&*self.point.borrow(), &point) => value,
-            Some(_) | None => {
-                let value = self.source.get(point);
-                self.value.set(Some(value));
+        self.get_cached(&point, || self.source.get(point))
+    }
+}
 
-                let mut cached_point = self.point.borrow_mut();
-                cached_point.clear();
-                cached_point.extend_from_slice(&point);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Perlin;
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        thread,
+    };
 
-                value
-            }
+    // Sums the coordinates of a point, counting how often it is evaluated.
+    #[derive(Default)]
+    struct Counting {
+        calls: AtomicUsize,
+    }
+
+    impl<const N: usize> NoiseFn<[f64; N]> for Counting {
+        fn get(&self, point: [f64; N]) -> f64 {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            point.iter().sum()
         }
     }
-}
 
-fn quick_eq(a: &[f64], b: &[f64]) -> bool {
-    assert_eq!(a.len(), b.len());
+    #[test]
+    fn repeated_points_are_cached() {
+        let cache = Cache::new(Counting::default());
+
+        assert_eq!(cache.get([1.0, 2.0]), 3.0);
+        assert_eq!(cache.get([1.0, 2.0]), 3.0);
+        assert_eq!(cache.source.calls.load(Ordering::Relaxed), 1);
 
-    a.iter().eq(b)
+        // Points of another dimension never match, even when padded with
+        // zeros.
+        assert_eq!(cache.get([1.0, 2.0, 0.0]), 3.0);
+        assert_eq!(cache.get([1.0, 2.0]), 3.0);
+        assert_eq!(cache.source.calls.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn concurrent_access_returns_values_of_own_point() {
+        const THREADS: usize = 16;
+        const ITERATIONS: usize = 20_000;
+
+        let perlin = Perlin::new();
+        let cache = Cache::new(&perlin);
+        let points = [[0.3, -1.7, 2.9], [1.1, 0.4, -0.6], [-2.5, 3.3, 0.2]];
+        let expected = points.map(|point| perlin.get(point));
+
+        thread::scope(|scope| {
+            for thread in 0..THREADS {
+                let cache = &cache;
+                scope.spawn(move || {
+                    for i in 0..ITERATIONS {
+                        let index = (i + thread) % points.len();
+                        assert_eq!(cache.get(points[index]), expected[index]);
+                    }
+                });
+            }
+        });
+    }
 }
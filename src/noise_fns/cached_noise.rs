@@ -0,0 +1,256 @@
+use crate::{noise_fns::NoiseFn, Error};
+use lru::LruCache;
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, MutexGuard, TryLockError,
+    },
+};
+
+/// Noise function that remembers the output values of recently queried cells
+/// of the source function in a least-recently-used cache.
+///
+/// Each input coordinate is snapped down to a grid with the cache's
+/// resolution, and the source function is evaluated at that grid point. Any
+/// later query falling into the same grid cell returns the cached value, for
+/// as long as the cell stays among the most recently used ones. A resolution
+/// of zero disables the snapping: points are then matched by the exact bits
+/// of their coordinates, and the source is evaluated at the point itself.
+///
+/// Unlike `Cache`, which only remembers the last point, this suits workloads
+/// that revisit many points in no particular order, such as a pathfinder
+/// repeatedly sampling terrain height, or a subgraph reached through several
+/// paths that query it at a few different points in turn.
+///
+/// As with `Cache`, the entries are guarded by a lock, and a thread that finds
+/// them locked evaluates the source directly instead of waiting.
+pub struct CachedNoise<Source, const N: usize> {
+    /// Outputs the values to be cached.
+    pub source: Source,
+
+    /// Size of a grid cell along each axis, or zero to match exact points.
+    resolution: f64,
+
+    cache: Mutex<LruCache<[i64; N], f64>>,
+
+    hits: AtomicU64,
+
+    misses: AtomicU64,
+}
+
+impl<Source, const N: usize> CachedNoise<Source, N> {
+    pub const DEFAULT_CACHE_SIZE: usize = 1024;
+    pub const DEFAULT_RESOLUTION: f64 = 1.0 / 1024.0;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            resolution: Self::DEFAULT_RESOLUTION,
+            cache: Mutex::new(LruCache::new(Self::capacity(Self::DEFAULT_CACHE_SIZE))),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Sets how many cells the cache remembers. A size of zero is treated as
+    /// one. Resizing keeps the most recently used cells that still fit.
+    pub fn set_cache_size(mut self, entries: usize) -> Self {
+        self.cache_mut().resize(Self::capacity(entries));
+        self
+    }
+
+    /// Sets the size of a grid cell along each axis, and empties the cache. A
+    /// resolution of zero matches points by the exact bits of their
+    /// coordinates instead.
+    ///
+    /// Returns an error unless `resolution` is finite and non-negative.
+    pub fn set_resolution(self, resolution: f64) -> Result<Self, Error> {
+        if !resolution.is_finite() || resolution < 0.0 {
+            return Err(Error::InvalidParameter);
+        }
+
+        self.clear_cache();
+        Ok(Self { resolution, ..self })
+    }
+
+    pub fn cache_size(&self) -> usize {
+        self.lock().cap().get()
+    }
+
+    pub fn resolution(&self) -> f64 {
+        self.resolution
+    }
+
+    /// Fraction of queries answered from the cache since it was last cleared,
+    /// or 0.0 if there have been none.
+    pub fn cache_hit_rate(&self) -> f64 {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let total = hits + self.misses.load(Ordering::Relaxed);
+
+        if total == 0 {
+            0.0
+        } else {
+            hits as f64 / total as f64
+        }
+    }
+
+    /// Forgets every cached value, and resets the hit rate.
+    pub fn clear_cache(&self) {
+        self.lock().clear();
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+
+    fn capacity(entries: usize) -> NonZeroUsize {
+        NonZeroUsize::new(entries.max(1)).unwrap()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LruCache<[i64; N], f64>> {
+        self.cache.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    fn cache_mut(&mut self) -> &mut LruCache<[i64; N], f64> {
+        self.cache
+            .get_mut()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+
+    fn try_lock(&self) -> Option<MutexGuard<'_, LruCache<[i64; N], f64>>> {
+        match self.cache.try_lock() {
+            Ok(cache) => Some(cache),
+            Err(TryLockError::Poisoned(cache)) => Some(cache.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+}
+
+impl<Source, const N: usize> NoiseFn<[f64; N]> for CachedNoise<Source, N>
+where
+    Source: NoiseFn<[f64; N]>,
+{
+    fn get(&self, point: [f64; N]) -> f64 {
+        let exact = self.resolution == 0.0;
+
+        let mut cell = [0; N];
+        for (index, coord) in cell.iter_mut().zip(point.iter()) {
+            *index = if exact {
+                coord.to_bits() as i64
+            } else {
+                (coord / self.resolution).floor() as i64
+            };
+        }
+
+        if let Some(mut cache) = self.try_lock() {
+            if let Some(&value) = cache.get(&cell) {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return value;
+            }
+        }
+
+        let mut snapped = point;
+        if !exact {
+            for (coord, index) in snapped.iter_mut().zip(cell.iter()) {
+                *coord = *index as f64 * self.resolution;
+            }
+        }
+
+        // The lock is not held while the source is evaluated, so other
+        // threads can keep using the cache in the meantime.
+        let value = self.source.get(snapped);
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        if let Some(mut cache) = self.try_lock() {
+            cache.put(cell, value);
+        }
+
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Fbm, Perlin};
+
+    #[test]
+    fn matches_source_on_grid_points() {
+        let fbm = Fbm::new();
+        let cached = CachedNoise::new(&fbm).set_resolution(0.25).unwrap();
+
+        for pass in 0..2 {
+            for i in 0..40 {
+                let point = [i as f64 * 0.25 - 5.0, i as f64 * -0.5];
+                assert_eq!(cached.get(point), fbm.get(point), "pass {}", pass);
+            }
+        }
+
+        assert_eq!(cached.cache_hit_rate(), 0.5);
+
+        // Points within the same cell share a value.
+        assert_eq!(cached.get([0.3, 0.1]), fbm.get([0.25, 0.0]));
+        assert_eq!(cached.get([-0.1, 0.0]), fbm.get([-0.25, 0.0]));
+    }
+
+    #[test]
+    fn evicts_least_recently_used_cells() {
+        let perlin = Perlin::new();
+        let cached = CachedNoise::new(&perlin)
+            .set_resolution(1.0)
+            .unwrap()
+            .set_cache_size(2);
+        assert_eq!(cached.cache_size(), 2);
+
+        cached.get([0.0, 0.0, 0.0]);
+        cached.get([1.0, 0.0, 0.0]);
+        cached.get([0.0, 0.0, 0.0]);
+        cached.get([2.0, 0.0, 0.0]);
+        assert_eq!(cached.cache_hit_rate(), 0.25);
+
+        // [1, 0, 0] was evicted; [0, 0, 0] was used more recently.
+        cached.get([0.0, 0.0, 0.0]);
+        cached.get([1.0, 0.0, 0.0]);
+        assert_eq!(cached.cache_hit_rate(), 2.0 / 6.0);
+
+        cached.clear_cache();
+        assert_eq!(cached.cache_hit_rate(), 0.0);
+    }
+
+    #[test]
+    fn zero_resolution_matches_exact_points() {
+        let perlin = Perlin::new();
+        let cached = CachedNoise::new(&perlin)
+            .set_resolution(0.0)
+            .unwrap()
+            .set_cache_size(2);
+        let (a, b, c) = ([0.1, 0.2], [0.3, 0.4], [0.5, 0.6]);
+
+        assert_eq!(cached.get(a), perlin.get(a));
+        assert_eq!(cached.get(b), perlin.get(b));
+        assert_eq!(cached.get(a), perlin.get(a));
+        assert_eq!(cached.cache_hit_rate(), 1.0 / 3.0);
+
+        // `b` is now the least recently used, so `c` evicts it.
+        cached.get(c);
+        cached.get(a);
+        cached.get(b);
+        assert_eq!(cached.cache_hit_rate(), 2.0 / 6.0);
+
+        // Points are matched by their bits, so -0.0 and 0.0 differ.
+        cached.clear_cache();
+        cached.get([1.5, 0.0]);
+        cached.get([1.5, -0.0]);
+        assert_eq!(cached.cache_hit_rate(), 0.0);
+    }
+
+    #[test]
+    fn invalid_resolution_is_rejected() {
+        for &resolution in &[-1.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                CachedNoise::<Perlin, 2>::new(Perlin::new())
+                    .set_resolution(resolution)
+                    .err(),
+                Some(Error::InvalidParameter)
+            );
+        }
+    }
+}
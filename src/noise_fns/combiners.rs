@@ -1,11 +1,25 @@
 pub use self::add::*;
+pub use self::combine::*;
+pub use self::divide::*;
+pub use self::lerp::*;
 pub use self::max::*;
 pub use self::min::*;
+pub use self::multi_min_max::*;
 pub use self::multiply::*;
 pub use self::power::*;
+pub use self::smooth_max::*;
+pub use self::smooth_min::*;
+pub use self::weighted_sum::*;
 
 mod add;
+mod combine;
+mod divide;
+mod lerp;
 mod max;
 mod min;
+mod multi_min_max;
 mod multiply;
 mod power;
+mod smooth_max;
+mod smooth_min;
+mod weighted_sum;
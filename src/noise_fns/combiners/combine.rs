@@ -0,0 +1,90 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that combines the output values from two source functions
+/// with a closure.
+///
+/// This is useful for one-off binary operations, such as `f64::atan2` or a
+/// custom soft maximum, that don't warrant a noise function of their own. The
+/// closure receives the output value from the first source function followed
+/// by the output value from the second.
+///
+/// The closure must be pure: its result may only depend on its two
+/// arguments. Caching noise functions such as `Cache` assume that a point
+/// always gives the same value, which a closure with side effects or hidden
+/// state would break.
+///
+/// `Combine` is `Clone`, `Send` and `Sync` whenever both source functions and
+/// the closure are.
+#[derive(Clone)]
+pub struct Combine<Source1, Source2, F> {
+    /// Outputs the first argument of the closure.
+    pub source1: Source1,
+
+    /// Outputs the second argument of the closure.
+    pub source2: Source2,
+
+    /// Combines the output values from the source functions.
+    pub function: F,
+}
+
+impl<Source1, Source2, F> Combine<Source1, Source2, F>
+where
+    F: Fn(f64, f64) -> f64,
+{
+    pub fn new(source1: Source1, source2: Source2, function: F) -> Self {
+        Self {
+            source1,
+            source2,
+            function,
+        }
+    }
+}
+
+impl<T, Source1, Source2, F> NoiseFn<T> for Combine<Source1, Source2, F>
+where
+    T: Copy,
+    Source1: NoiseFn<T>,
+    Source2: NoiseFn<T>,
+    F: Fn(f64, f64) -> f64,
+{
+    fn get(&self, point: T) -> f64 {
+        (self.function)(self.source1.get(point), self.source2.get(point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Add, Constant, Min, Perlin, Power, Seedable, Simplex};
+
+    #[test]
+    fn closures_reproduce_combiners() {
+        let perlin = Perlin::new().set_seed(1);
+        let simplex = Simplex::new().set_seed(2);
+
+        let add = Combine::new(&perlin, &simplex, |a, b| a + b);
+        let min = Combine::new(&perlin, &simplex, f64::min);
+        let power = Combine::new(&perlin, &simplex, |base: f64, exponent: f64| {
+            if base < 0.0 && exponent.fract() != 0.0 {
+                base.abs().powf(exponent).copysign(base)
+            } else {
+                base.powf(exponent)
+            }
+        });
+
+        for i in 0..64 {
+            let point = [i as f64 * 0.31 - 7.0, i as f64 * 0.17, 0.5];
+            assert_eq!(add.get(point), Add::new(&perlin, &simplex).get(point));
+            assert_eq!(min.get(point), Min::new(&perlin, &simplex).get(point));
+            assert_eq!(power.get(point), Power::new(&perlin, &simplex).get(point));
+        }
+    }
+
+    #[test]
+    fn closure_sees_sources_in_order() {
+        let atan2 = Combine::new(Constant::new(1.0), Constant::new(-1.0), f64::atan2);
+
+        assert_eq!(atan2.get([0.0, 0.0]), 1.0_f64.atan2(-1.0));
+        assert_eq!(atan2.get([0.0, 0.0, 0.0, 0.0]), 1.0_f64.atan2(-1.0));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that outputs the output value from the first source function
+/// divided by the output value from the second source function.
+///
+/// Where the magnitude of the divisor is smaller than `epsilon`, the fallback
+/// value is output instead, so the output is never infinite or NaN.
+pub struct Divide<'a, T> {
+    /// Outputs the dividend.
+    pub source1: &'a dyn NoiseFn<T>,
+
+    /// Outputs the divisor.
+    pub source2: &'a dyn NoiseFn<T>,
+
+    /// Smallest magnitude of the divisor that the dividend is divided by. The
+    /// default epsilon is 1e-6.
+    pub epsilon: f64,
+
+    /// Value to output where the magnitude of the divisor is below the
+    /// epsilon. The default fallback is 0.0.
+    pub fallback: f64,
+}
+
+impl<'a, T> Divide<'a, T> {
+    pub const DEFAULT_EPSILON: f64 = 1e-6;
+    pub const DEFAULT_FALLBACK: f64 = 0.0;
+
+    pub fn new(source1: &'a dyn NoiseFn<T>, source2: &'a dyn NoiseFn<T>) -> Self {
+        Self {
+            source1,
+            source2,
+            epsilon: Self::DEFAULT_EPSILON,
+            fallback: Self::DEFAULT_FALLBACK,
+        }
+    }
+
+    pub fn set_epsilon(self, epsilon: f64) -> Self {
+        Self { epsilon, ..self }
+    }
+
+    pub fn set_fallback(self, fallback: f64) -> Self {
+        Self { fallback, ..self }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for Divide<'a, T>
+where
+    T: Copy,
+{
+    fn get(&self, point: T) -> f64 {
+        let divisor = self.source2.get(point);
+
+        if divisor.abs() < self.epsilon || divisor.is_nan() {
+            return self.fallback;
+        }
+
+        self.source1.get(point) / divisor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Constant, Perlin};
+
+    #[test]
+    fn divides_outputs() {
+        let dividend = Constant::new(3.0);
+        let divisor = Constant::new(-0.5);
+
+        assert_eq!(Divide::new(&dividend, &divisor).get([0.5, 1.5]), -6.0);
+        assert_eq!(Divide::new(&dividend, &divisor).get([0.5, 1.5, 2.5]), -6.0);
+        assert_eq!(
+            Divide::new(&dividend, &divisor).get([0.5, 1.5, 2.5, 3.5]),
+            -6.0
+        );
+    }
+
+    #[test]
+    fn near_zero_divisor_outputs_fallback() {
+        let dividend = Constant::new(1.0);
+
+        for &value in &[0.0, -0.0, 1e-7, -1e-7, f64::NAN] {
+            let divisor = Constant::new(value);
+            let divide = Divide::new(&dividend, &divisor).set_fallback(0.25);
+
+            assert_eq!(divide.get([0.0, 0.0]), 0.25);
+        }
+
+        // Perlin noise is zero at every lattice point.
+        let perlin = Perlin::new();
+        let divide = Divide::new(&dividend, &perlin).set_epsilon(0.01);
+        for x in -5..5 {
+            let value = divide.get([x as f64, 2.0, -1.0]);
+            assert!(value.is_finite());
+            assert_eq!(value, 0.0);
+        }
+    }
+}
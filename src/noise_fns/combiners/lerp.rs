@@ -0,0 +1,97 @@
+use crate::{
+    math::{self, interpolate},
+    noise_fns::NoiseFn,
+};
+
+/// Noise function that linearly interpolates between the output values from
+/// two source functions by a constant factor.
+///
+/// This is equivalent to a `Blend` controlled by a `Constant`, without
+/// evaluating the control function at every point.
+pub struct Lerp<'a, T> {
+    /// Outputs the value at a factor of 0.0.
+    pub source1: &'a dyn NoiseFn<T>,
+
+    /// Outputs the value at a factor of 1.0.
+    pub source2: &'a dyn NoiseFn<T>,
+
+    /// Interpolation factor, within [0,1]. The default factor is 0.5.
+    pub t: f64,
+}
+
+impl<'a, T> Lerp<'a, T> {
+    pub const DEFAULT_T: f64 = 0.5;
+
+    pub fn new(source1: &'a dyn NoiseFn<T>, source2: &'a dyn NoiseFn<T>) -> Self {
+        Self {
+            source1,
+            source2,
+            t: Self::DEFAULT_T,
+        }
+    }
+
+    /// Sets the interpolation factor. The factor is clamped to [0,1].
+    pub fn set_t(self, t: f64) -> Self {
+        Self {
+            t: math::clamp(t, 0.0, 1.0),
+            ..self
+        }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for Lerp<'a, T>
+where
+    T: Copy,
+{
+    fn get(&self, point: T) -> f64 {
+        interpolate::linear(self.source1.get(point), self.source2.get(point), self.t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Blend, Constant, Perlin, Seedable, Simplex};
+
+    fn assert_matches_blend<T: Copy>(
+        source1: &dyn NoiseFn<T>,
+        source2: &dyn NoiseFn<T>,
+        points: impl Iterator<Item = T>,
+    ) {
+        let points: Vec<T> = points.collect();
+
+        for &t in &[0.0, 0.3, 0.5, 0.85, 1.0] {
+            let lerp = Lerp::new(source1, source2).set_t(t);
+            let control = Constant::new(t);
+            let blend = Blend::new(source1, source2, &control);
+
+            for &point in &points {
+                assert_eq!(lerp.get(point), blend.get(point));
+            }
+        }
+    }
+
+    #[test]
+    fn matches_blend_with_constant_control() {
+        let perlin = Perlin::new();
+        let simplex = Simplex::new().set_seed(5);
+        let coord = |i: usize| i as f64 * 0.173 - 4.0;
+
+        assert_matches_blend::<[f64; 2]>(&perlin, &simplex, (0..50).map(|i| [coord(i), 0.4]));
+        assert_matches_blend::<[f64; 3]>(&perlin, &simplex, (0..50).map(|i| [coord(i), 0.4, -0.6]));
+        assert_matches_blend::<[f64; 4]>(
+            &perlin,
+            &simplex,
+            (0..50).map(|i| [coord(i), 0.4, -0.6, 1.1]),
+        );
+    }
+
+    #[test]
+    fn factor_is_clamped() {
+        let a = Constant::new(-1.0);
+        let b = Constant::new(3.0);
+
+        assert_eq!(Lerp::<[f64; 2]>::new(&a, &b).set_t(2.0).t, 1.0);
+        assert_eq!(Lerp::new(&a, &b).set_t(-0.5).get([0.0, 0.0]), -1.0);
+    }
+}
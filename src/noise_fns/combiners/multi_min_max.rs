@@ -0,0 +1,132 @@
+use crate::{noise_fns::NoiseFn, Error};
+
+/// Noise function that outputs the smallest of the output values from any
+/// number of source functions.
+pub struct MultiMin<'a, T> {
+    sources: Vec<Box<dyn NoiseFn<T> + 'a>>,
+}
+
+/// Noise function that outputs the largest of the output values from any
+/// number of source functions.
+pub struct MultiMax<'a, T> {
+    sources: Vec<Box<dyn NoiseFn<T> + 'a>>,
+}
+
+impl<'a, T> MultiMin<'a, T> {
+    /// Creates the minimum of the given source functions.
+    ///
+    /// Returns an error if `sources` is empty.
+    pub fn new(sources: Vec<Box<dyn NoiseFn<T> + 'a>>) -> Result<Self, Error> {
+        if sources.is_empty() {
+            return Err(Error::NoSources);
+        }
+
+        Ok(Self { sources })
+    }
+
+    pub fn sources(&self) -> &[Box<dyn NoiseFn<T> + 'a>] {
+        &self.sources
+    }
+}
+
+impl<'a, T> MultiMax<'a, T> {
+    /// Creates the maximum of the given source functions.
+    ///
+    /// Returns an error if `sources` is empty.
+    pub fn new(sources: Vec<Box<dyn NoiseFn<T> + 'a>>) -> Result<Self, Error> {
+        if sources.is_empty() {
+            return Err(Error::NoSources);
+        }
+
+        Ok(Self { sources })
+    }
+
+    pub fn sources(&self) -> &[Box<dyn NoiseFn<T> + 'a>] {
+        &self.sources
+    }
+}
+
+impl<'a, T> NoiseFn<T> for MultiMin<'a, T>
+where
+    T: Copy,
+{
+    fn get(&self, point: T) -> f64 {
+        self.sources
+            .iter()
+            .map(|source| source.get(point))
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+impl<'a, T> NoiseFn<T> for MultiMax<'a, T>
+where
+    T: Copy,
+{
+    fn get(&self, point: T) -> f64 {
+        self.sources
+            .iter()
+            .map(|source| source.get(point))
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Constant, Max, Min, Perlin, Seedable};
+
+    fn constants<'a>(values: &[f64]) -> Vec<Box<dyn NoiseFn<[f64; 2]> + 'a>> {
+        values
+            .iter()
+            .map(|&value| Box::new(Constant::new(value)) as Box<dyn NoiseFn<[f64; 2]>>)
+            .collect()
+    }
+
+    #[test]
+    fn outputs_extremum_of_constants() {
+        let values = [0.25, -0.75, 0.5];
+
+        let min = MultiMin::new(constants(&values)).unwrap();
+        let max = MultiMax::new(constants(&values)).unwrap();
+
+        assert_eq!(min.get([0.0, 0.0]), -0.75);
+        assert_eq!(max.get([0.0, 0.0]), 0.5);
+        assert_eq!(min.sources().len(), 3);
+    }
+
+    #[test]
+    fn matches_nested_binary_combiners() {
+        let perlins: Vec<Perlin> = (0..3).map(|seed| Perlin::new().set_seed(seed)).collect();
+        let boxed = || {
+            perlins
+                .iter()
+                .map(|perlin| Box::new(perlin) as Box<dyn NoiseFn<[f64; 2]>>)
+                .collect()
+        };
+
+        let min = MultiMin::new(boxed()).unwrap();
+        let max = MultiMax::new(boxed()).unwrap();
+        let inner_min = Min::new(&perlins[0], &perlins[1]);
+        let nested_min = Min::new(&inner_min, &perlins[2]);
+        let inner_max = Max::new(&perlins[0], &perlins[1]);
+        let nested_max = Max::new(&inner_max, &perlins[2]);
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173, i as f64 * -0.291];
+            assert_eq!(min.get(point), nested_min.get(point));
+            assert_eq!(max.get(point), nested_max.get(point));
+        }
+    }
+
+    #[test]
+    fn empty_source_list_is_rejected() {
+        assert_eq!(
+            MultiMin::<[f64; 2]>::new(Vec::new()).err(),
+            Some(Error::NoSources)
+        );
+        assert_eq!(
+            MultiMax::<[f64; 2]>::new(Vec::new()).err(),
+            Some(Error::NoSources)
+        );
+    }
+}
@@ -2,11 +2,20 @@ use crate::noise_fns::NoiseFn;
 
 /// Noise function that raises the output value from the first source function
 /// to the power of the output value of the second source function.
+///
+/// Most noise functions output negative values, which `f64::powf` turns into
+/// NaN for any exponent that is not an integer. For those, the magnitude of
+/// the base is raised to the power and the sign of the base is restored, so
+/// `-0.25` to the power of `0.5` is `-0.5`. Every other base and exponent
+/// gives the same result as `f64::powf`, so `-2.0` to the power of `2.0` is
+/// still `4.0`.
+///
+/// For a fixed exponent, `PowerConst` avoids the second noise function.
 pub struct Power<'a, T> {
-    /// Outputs a value.
+    /// Outputs the base.
     pub source1: &'a dyn NoiseFn<T>,
 
-    /// Outputs a value.
+    /// Outputs the exponent.
     pub source2: &'a dyn NoiseFn<T>,
 }
 
@@ -21,6 +30,44 @@ where
     T: Copy,
 {
     fn get(&self, point: T) -> f64 {
-        (self.source1.get(point)).powf(self.source2.get(point))
+        power(self.source1.get(point), self.source2.get(point))
+    }
+}
+
+/// Raises `base` to the power of `exponent`, keeping the sign of negative
+/// bases where `f64::powf` would return NaN.
+pub(crate) fn power(base: f64, exponent: f64) -> f64 {
+    if base < 0.0 && exponent.is_finite() && exponent.fract() != 0.0 {
+        base.abs().powf(exponent).copysign(base)
+    } else {
+        base.powf(exponent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    fn power(base: f64, exponent: f64) -> f64 {
+        Power::new(&Constant::new(base), &Constant::new(exponent)).get([0.5, 1.5, 2.5])
+    }
+
+    #[test]
+    fn raises_to_power() {
+        assert_eq!(power(2.0, 3.0), 8.0);
+        assert_eq!(power(0.25, 0.5), 0.5);
+        assert_eq!(power(4.0, -1.0), 0.25);
+    }
+
+    #[test]
+    fn negative_bases_keep_their_sign() {
+        // Integer exponents are left to `f64::powf`.
+        assert_eq!(power(-2.0, 3.0), -8.0);
+        assert_eq!(power(-2.0, 2.0), 4.0);
+
+        // Fractional exponents would give NaN.
+        assert_eq!(power(-0.25, 0.5), -0.5);
+        assert_eq!(power(-8.0, 1.0 / 3.0), -2.0);
     }
 }
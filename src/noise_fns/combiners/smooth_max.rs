@@ -0,0 +1,47 @@
+use crate::noise_fns::NoiseFn;
+
+use super::smooth_min::smooth_min;
+
+/// Noise function that outputs the larger of the two output values from two
+/// source functions, blending smoothly between them where they are close.
+///
+/// This is the counterpart of `SmoothMin`. Near the crossing of the two
+/// sources the output rises slightly above both output values, by at most
+/// `k / 4`.
+pub struct SmoothMax<'a, T> {
+    /// Outputs a value.
+    pub source1: &'a dyn NoiseFn<T>,
+
+    /// Outputs a value.
+    pub source2: &'a dyn NoiseFn<T>,
+
+    /// Width of the blend between the two output values. The default of 0.1
+    /// rounds off the crease slightly; a value of 0.0 outputs exactly the
+    /// same as `Max`.
+    pub k: f64,
+}
+
+impl<'a, T> SmoothMax<'a, T> {
+    pub const DEFAULT_K: f64 = 0.1;
+
+    pub fn new(source1: &'a dyn NoiseFn<T>, source2: &'a dyn NoiseFn<T>) -> Self {
+        Self {
+            source1,
+            source2,
+            k: Self::DEFAULT_K,
+        }
+    }
+
+    pub fn set_k(self, k: f64) -> Self {
+        Self { k, ..self }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for SmoothMax<'a, T>
+where
+    T: Copy,
+{
+    fn get(&self, point: T) -> f64 {
+        -smooth_min(-self.source1.get(point), -self.source2.get(point), self.k)
+    }
+}
@@ -0,0 +1,99 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that outputs the smaller of the two output values from two
+/// source functions, blending smoothly between them where they are close.
+///
+/// Where the two output values are within `k` of each other, the output is
+/// rounded off with the polynomial smooth minimum, which removes the crease a
+/// hard minimum leaves where the two sources cross. Near the crossing the
+/// output dips slightly below both output values, by at most `k / 4`.
+pub struct SmoothMin<'a, T> {
+    /// Outputs a value.
+    pub source1: &'a dyn NoiseFn<T>,
+
+    /// Outputs a value.
+    pub source2: &'a dyn NoiseFn<T>,
+
+    /// Width of the blend between the two output values. The default of 0.1
+    /// rounds off the crease slightly; a value of 0.0 outputs exactly the
+    /// same as `Min`.
+    pub k: f64,
+}
+
+impl<'a, T> SmoothMin<'a, T> {
+    pub const DEFAULT_K: f64 = 0.1;
+
+    pub fn new(source1: &'a dyn NoiseFn<T>, source2: &'a dyn NoiseFn<T>) -> Self {
+        Self {
+            source1,
+            source2,
+            k: Self::DEFAULT_K,
+        }
+    }
+
+    pub fn set_k(self, k: f64) -> Self {
+        Self { k, ..self }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for SmoothMin<'a, T>
+where
+    T: Copy,
+{
+    fn get(&self, point: T) -> f64 {
+        smooth_min(self.source1.get(point), self.source2.get(point), self.k)
+    }
+}
+
+/// Polynomial smooth minimum of `a` and `b`, blending over a width of `k`.
+///
+/// Widths of 0.0 or less return the hard minimum.
+#[inline]
+pub(crate) fn smooth_min(a: f64, b: f64, k: f64) -> f64 {
+    if k <= 0.0 {
+        return a.min(b);
+    }
+
+    let h = (k - (a - b).abs()).max(0.0) / k;
+    a.min(b) - h * h * k * 0.25
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Max, Min, Perlin, Seedable, SmoothMax};
+
+    #[test]
+    fn zero_k_matches_hard_combiners() {
+        let a = Perlin::new();
+        let b = Perlin::new().set_seed(1);
+
+        let min = Min::new(&a, &b);
+        let max = Max::new(&a, &b);
+        let smooth_min = SmoothMin::new(&a, &b).set_k(0.0);
+        let smooth_max = SmoothMax::new(&a, &b).set_k(0.0);
+
+        for i in 0..200 {
+            let point = [i as f64 * 0.113 - 7.0, i as f64 * -0.071 + 3.0];
+            assert_eq!(smooth_min.get(point), min.get(point));
+            assert_eq!(smooth_max.get(point), max.get(point));
+        }
+    }
+
+    #[test]
+    fn blend_stays_near_hard_combiners() {
+        let k = 0.2;
+
+        for i in -20..=20 {
+            let a = i as f64 * 0.05;
+            let b = 0.25;
+
+            let value = smooth_min(a, b, k);
+            assert!(value <= a.min(b));
+            assert!(value >= a.min(b) - k / 4.0);
+            if (a - b).abs() >= k {
+                assert_eq!(value, a.min(b));
+            }
+        }
+    }
+}
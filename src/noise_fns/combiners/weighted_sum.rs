@@ -0,0 +1,109 @@
+use crate::{noise_fns::NoiseFn, Error};
+
+/// Noise function that outputs the sum of the output values from any number of
+/// source functions, each multiplied by its own weight.
+///
+/// If `normalize` is set, the sum is divided by the sum of the absolute
+/// weights, so the output stays within [-1,1] when every source function
+/// outputs values within [-1,1].
+pub struct WeightedSum<'a, T> {
+    sources: Vec<(&'a dyn NoiseFn<T>, f64)>,
+
+    /// Whether the output is divided by the sum of the absolute weights. The
+    /// default is false.
+    pub normalize: bool,
+}
+
+impl<'a, T> WeightedSum<'a, T> {
+    pub const DEFAULT_NORMALIZE: bool = false;
+
+    /// Creates a sum of the given source functions and their weights.
+    ///
+    /// Returns an error if `sources` is empty.
+    pub fn new(sources: Vec<(&'a dyn NoiseFn<T>, f64)>) -> Result<Self, Error> {
+        if sources.is_empty() {
+            return Err(Error::NoSources);
+        }
+
+        Ok(Self {
+            sources,
+            normalize: Self::DEFAULT_NORMALIZE,
+        })
+    }
+
+    pub fn set_normalize(self, normalize: bool) -> Self {
+        Self { normalize, ..self }
+    }
+
+    pub fn sources(&self) -> &[(&'a dyn NoiseFn<T>, f64)] {
+        &self.sources
+    }
+}
+
+impl<'a, T> NoiseFn<T> for WeightedSum<'a, T>
+where
+    T: Copy,
+{
+    fn get(&self, point: T) -> f64 {
+        let sum: f64 = self
+            .sources
+            .iter()
+            .map(|(source, weight)| source.get(point) * weight)
+            .sum();
+
+        if self.normalize {
+            let total: f64 = self.sources.iter().map(|(_, weight)| weight.abs()).sum();
+            if total > 0.0 {
+                return sum / total;
+            }
+        }
+
+        sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Add, Perlin, ScaleBias, Seedable, Simplex, Worley};
+
+    #[test]
+    fn empty_source_list_is_rejected() {
+        assert_eq!(
+            WeightedSum::<[f64; 2]>::new(Vec::new()).err(),
+            Some(Error::NoSources)
+        );
+    }
+
+    #[test]
+    fn matches_nested_add_and_scale_bias() {
+        let perlin = Perlin::new();
+        let simplex = Simplex::new().set_seed(1);
+        let worley = Worley::new().set_seed(2);
+
+        let sum = WeightedSum::new(vec![
+            (&perlin as &dyn NoiseFn<[f64; 3]>, 0.5),
+            (&simplex, -1.5),
+            (&worley, 0.25),
+        ])
+        .unwrap();
+
+        let scaled_perlin = ScaleBias::new(&perlin).set_scale(0.5);
+        let scaled_simplex = ScaleBias::new(&simplex).set_scale(-1.5);
+        let scaled_worley = ScaleBias::new(&worley).set_scale(0.25);
+        let first = Add::new(&scaled_perlin, &scaled_simplex);
+        let nested = Add::new(&first, &scaled_worley);
+
+        let normalized = WeightedSum::new(sum.sources().to_vec())
+            .unwrap()
+            .set_normalize(true);
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173 - 8.0, i as f64 * 0.091, 1.3];
+            let expected = nested.get(point);
+
+            assert!((sum.get(point) - expected).abs() < 1e-12);
+            assert!((normalized.get(point) - expected / 2.25).abs() < 1e-12);
+        }
+    }
+}
@@ -1,21 +1,128 @@
 pub use self::checkerboard::*;
 pub use self::constant::*;
+pub use self::curl_noise::*;
 pub use self::cylinders::*;
+#[cfg(feature = "alloc")]
 pub use self::fractals::*;
 pub use self::open_simplex::*;
 pub use self::perlin::*;
 pub use self::perlin_surflet::*;
+pub use self::simplex::*;
+pub use self::simplex_vec2::*;
+pub use self::spheres::*;
 pub use self::super_simplex::*;
 pub use self::value::*;
+pub use self::white_noise::*;
 pub use self::worley::*;
 
 mod checkerboard;
 mod constant;
+mod curl_noise;
 mod cylinders;
+#[cfg(feature = "alloc")]
 mod fractals;
 mod open_simplex;
 mod perlin;
 mod perlin_surflet;
+mod simplex;
+mod simplex_vec2;
+mod spheres;
 mod super_simplex;
 mod value;
+mod white_noise;
 mod worley;
+
+/// Checks that `doubled` is `base` with its frequency doubled: the input point
+/// is scaled exactly, and a line crosses about twice as many features, counted
+/// as the local extrema of the output along it.
+#[cfg(test)]
+fn assert_doubles_feature_count(
+    base: &dyn crate::NoiseFn<[f64; 2]>,
+    doubled: &dyn crate::NoiseFn<[f64; 2]>,
+) {
+    fn extrema(noise: &dyn crate::NoiseFn<[f64; 2]>) -> usize {
+        let values: Vec<f64> = (0..4000)
+            .map(|i| noise.get([i as f64 * 0.01, 0.37]))
+            .collect();
+
+        values
+            .windows(3)
+            .filter(|w| (w[1] - w[0]) * (w[2] - w[1]) < 0.0)
+            .count()
+    }
+
+    for i in 0..32 {
+        let point = [i as f64 * 0.29 - 4.0, i as f64 * -0.13];
+        assert_eq!(
+            doubled.get(point),
+            base.get([point[0] * 2.0, point[1] * 2.0])
+        );
+    }
+
+    let ratio = extrema(doubled) as f64 / extrema(base) as f64;
+    assert!(
+        (1.7..2.3).contains(&ratio),
+        "feature count ratio is {}",
+        ratio
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{NoiseBounds, NoiseFn};
+
+    // Lattice coordinates beyond the range of `isize` wrap instead of
+    // overflowing, so the output stays finite and within bounds.
+    const LARGE_COORDINATES: [f64; 5] = [1e19, -1e19, 1e30, f64::MAX, f64::MIN];
+
+    fn assert_in_range(value: f64, (min, max): (f64, f64), point: &[f64]) {
+        assert!(
+            value.is_finite() && min <= value && value <= max,
+            "{} is outside [{}, {}] at {:?}",
+            value,
+            min,
+            max,
+            point
+        );
+    }
+
+    fn assert_large_coordinates_in_range_3d<N>(noise: &N, range: (f64, f64))
+    where
+        N: NoiseFn<[f64; 2]> + NoiseFn<[f64; 3]>,
+    {
+        for &coord in &LARGE_COORDINATES {
+            let point = [coord, -coord];
+            assert_in_range(noise.get(point), range, &point);
+
+            let point = [coord, 0.5, -coord];
+            assert_in_range(noise.get(point), range, &point);
+        }
+    }
+
+    fn assert_large_coordinates_in_range<N>(noise: &N, range: (f64, f64))
+    where
+        N: NoiseFn<[f64; 2]> + NoiseFn<[f64; 3]> + NoiseFn<[f64; 4]>,
+    {
+        assert_large_coordinates_in_range_3d(noise, range);
+
+        for &coord in &LARGE_COORDINATES {
+            let point = [coord, -coord, coord, 0.5];
+            assert_in_range(noise.get(point), range, &point);
+        }
+    }
+
+    #[test]
+    fn large_coordinates_stay_within_bounds() {
+        assert_large_coordinates_in_range(&Perlin::new(), <Perlin>::range());
+        assert_large_coordinates_in_range(&PerlinSurflet::new(), PerlinSurflet::range());
+        assert_large_coordinates_in_range(&OpenSimplex::new(), OpenSimplex::range());
+        assert_large_coordinates_in_range_3d(&SuperSimplex::new(), SuperSimplex::range());
+        assert_large_coordinates_in_range(&Value::new(), <Value>::range());
+        assert_large_coordinates_in_range(&WhiteNoise::new(), <WhiteNoise>::range());
+
+        // With the default displacement and `enable_range` unset, Worley
+        // outputs the cell value, which lies in [-1, 1].
+        assert_large_coordinates_in_range(&Worley::new(), (-1.0, 1.0));
+    }
+}
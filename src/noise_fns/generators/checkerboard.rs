@@ -1,4 +1,7 @@
-use crate::noise_fns::NoiseFn;
+use crate::{
+    math::float,
+    noise_fns::{NoiseBounds, NoiseFn},
+};
 
 /// Noise function that outputs a checkerboard pattern.
 ///
@@ -15,29 +18,34 @@ pub struct Checkerboard {
 }
 
 impl Checkerboard {
-    const DEFAULT_SIZE: usize = 0;
+    pub const DEFAULT_SIZE: usize = 0;
 
     pub fn new(size: usize) -> Self {
-        Self { size: 1 << size }
+        Self { size }
     }
 
     pub fn set_size(self, size: usize) -> Self {
-        Self { size: 1 << size }
+        Self { size }
     }
 
+    /// Returns the length of the sides of the blocks, 2<sup>size</sup>.
     pub fn size(self) -> usize {
-        self.size
+        1 << self.size
     }
 }
 
 impl Default for Checkerboard {
     fn default() -> Self {
-        Self {
-            size: 1 << Checkerboard::DEFAULT_SIZE,
-        }
+        Self::new(Self::DEFAULT_SIZE)
     }
 }
 
+/// The output is always either -1.0 or 1.0.
+impl NoiseBounds for Checkerboard {
+    const MIN: f64 = -1.0;
+    const MAX: f64 = 1.0;
+}
+
 // These impl's should be made generic over Point, but there is no higher Point
 // type. Keep the code the same anyway.
 impl NoiseFn<[f64; 2]> for Checkerboard {
@@ -59,10 +67,12 @@ impl NoiseFn<[f64; 4]> for Checkerboard {
 }
 
 fn calculate_checkerboard(point: &[f64], size: usize) -> f64 {
+    // Floor to a signed integer first, so that negative coordinates land in
+    // their own blocks instead of all saturating to zero.
     let result = point
         .iter()
-        .map(|&a| a.floor() as usize)
-        .fold(0, |a, b| (a & size) ^ (b & size));
+        .map(|&a| (float::floor(a) as isize) >> size)
+        .fold(0, |a, b| a ^ (b & 1));
 
     if result > 0 {
         -1.0
@@ -70,3 +80,54 @@ fn calculate_checkerboard(point: &[f64], size: usize) -> f64 {
         1.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negative_coordinates_alternate() {
+        let checkerboard = Checkerboard::default();
+
+        for x in -8..8 {
+            let value = checkerboard.get([x as f64 + 0.5, -0.5]);
+            assert_eq!(checkerboard.get([x as f64 + 1.5, -0.5]), -value);
+        }
+    }
+
+    #[test]
+    fn default_has_unit_blocks() {
+        let checkerboard = Checkerboard::default();
+
+        assert_eq!(checkerboard.get([0.5, 0.5]), 1.0);
+        assert_eq!(checkerboard.get([1.5, 0.5]), -1.0);
+        assert_eq!(checkerboard.get([-0.5, 0.5]), -1.0);
+        assert_eq!(checkerboard.get([-0.5, -0.5]), 1.0);
+    }
+
+    #[test]
+    fn size_is_the_block_length() {
+        assert_eq!(Checkerboard::default().size(), 1);
+        assert_eq!(Checkerboard::new(3).size(), 8);
+    }
+
+    #[test]
+    fn larger_sizes_have_larger_blocks() {
+        for size in 0..4 {
+            let checkerboard = Checkerboard::new(size);
+            let block = (1 << size) as f64;
+
+            for origin in -4..4 {
+                let start = origin as f64 * block;
+                let value = checkerboard.get([start, 0.0]);
+
+                // Constant across the block, and flipped in the next one.
+                for step in 0..8 {
+                    let x = start + step as f64 * block / 8.0;
+                    assert_eq!(checkerboard.get([x, 0.0]), value);
+                }
+                assert_eq!(checkerboard.get([start + block, 0.0]), -value);
+            }
+        }
+    }
+}
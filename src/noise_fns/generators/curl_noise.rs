@@ -0,0 +1,118 @@
+use super::simplex_vec2::curl_2d;
+use crate::noise_fns::{Seedable, Simplex};
+
+/// Divergence-free vector field built from the curl of simplex noise, as
+/// described in Bridson et al., "Curl-Noise for Procedural Fluid Flow".
+///
+/// Particles advected through the field swirl around without bunching up or
+/// spreading out, as if carried by an incompressible fluid. The derivatives
+/// are calculated analytically, so each vector costs about as much as
+/// sampling the noise once per component.
+///
+/// The three potential functions are simplex noise seeded with `seed`,
+/// `seed.wrapping_add(1)` and `seed.wrapping_add(2)`. The 2-dimensional field
+/// only uses the first of them, and matches `SimplexVec2::get_curl` with the
+/// same seed.
+#[derive(Clone, Copy, Debug)]
+pub struct CurlNoise {
+    seed: u32,
+    sources: [Simplex; 3],
+}
+
+impl CurlNoise {
+    pub const DEFAULT_SEED: u32 = 0;
+
+    pub fn new() -> Self {
+        Self {
+            seed: Self::DEFAULT_SEED,
+            sources: build_sources(Self::DEFAULT_SEED),
+        }
+    }
+
+    /// Returns the curl of the potential `F` at `point`, `(∂F/∂y, -∂F/∂x)`.
+    pub fn get_2d(&self, point: [f64; 2]) -> [f64; 2] {
+        curl_2d(&self.sources[0], point)
+    }
+
+    /// Returns the curl of the potential `(Fx, Fy, Fz)` at `point`,
+    /// `(∂Fz/∂y - ∂Fy/∂z, ∂Fx/∂z - ∂Fz/∂x, ∂Fy/∂x - ∂Fx/∂y)`.
+    pub fn get_3d(&self, point: [f64; 3]) -> [f64; 3] {
+        let [_, dx_dy, dx_dz] = self.sources[0].get_derivative_3d(point);
+        let [dy_dx, _, dy_dz] = self.sources[1].get_derivative_3d(point);
+        let [dz_dx, dz_dy, _] = self.sources[2].get_derivative_3d(point);
+
+        [dz_dy - dy_dz, dx_dz - dz_dx, dy_dx - dx_dy]
+    }
+}
+
+fn build_sources(seed: u32) -> [Simplex; 3] {
+    [
+        Simplex::new().set_seed(seed),
+        Simplex::new().set_seed(seed.wrapping_add(1)),
+        Simplex::new().set_seed(seed.wrapping_add(2)),
+    ]
+}
+
+impl Default for CurlNoise {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Seedable for CurlNoise {
+    fn set_seed(self, seed: u32) -> Self {
+        if self.seed == seed {
+            return self;
+        }
+
+        Self {
+            seed,
+            sources: build_sources(seed),
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEP: f64 = 1e-5;
+
+    #[test]
+    fn curl_3d_is_divergence_free() {
+        let curl = CurlNoise::new().set_seed(u32::MAX);
+        let mut largest = 0.0_f64;
+
+        for i in 0..200 {
+            let point = [
+                i as f64 * 0.0731 - 4.0,
+                i as f64 * -0.0419 + 2.0,
+                i as f64 * 0.0253,
+            ];
+
+            let mut divergence = 0.0;
+            for axis in 0..3 {
+                let mut ahead = point;
+                let mut behind = point;
+                ahead[axis] += STEP;
+                behind[axis] -= STEP;
+                divergence += (curl.get_3d(ahead)[axis] - curl.get_3d(behind)[axis]) / (2.0 * STEP);
+            }
+
+            assert!(
+                divergence.abs() < 1e-3,
+                "divergence {} at {:?}",
+                divergence,
+                point
+            );
+            largest = largest.max(curl.get_3d(point).iter().fold(0.0, |a, b| a.max(b.abs())));
+        }
+
+        // The field isn't trivially zero.
+        assert!(largest > 0.5, "largest component is {}", largest);
+    }
+}
@@ -1,10 +1,18 @@
-use crate::noise_fns::NoiseFn;
+use crate::{
+    math::float,
+    noise_fns::{NoiseBounds, NoiseFn},
+};
 
 /// Noise function that outputs concentric cylinders.
 ///
 /// This noise function outputs concentric cylinders centered on the origin. The
 /// cylinders are oriented along the z axis similar to the concentric rings of
 /// a tree. Each cylinder extends infinitely along the z axis.
+///
+/// The output only depends on the distance from the z axis, measured in the
+/// xy-plane, so 2-dimensional input gives a cross-section of the cylinders,
+/// and the z and w coordinates are ignored. To orient the cylinders along
+/// another axis, swap the coordinates before passing them in.
 #[derive(Clone, Copy, Debug)]
 pub struct Cylinders {
     /// Frequency of the concentric objects.
@@ -20,6 +28,11 @@ impl Cylinders {
         }
     }
 
+    /// Creates a noise function with the given frequency.
+    pub fn with_frequency(frequency: f64) -> Self {
+        Self::new().set_frequency(frequency)
+    }
+
     pub fn set_frequency(self, frequency: f64) -> Self {
         Self { frequency }
     }
@@ -31,6 +44,11 @@ impl Default for Cylinders {
     }
 }
 
+impl NoiseBounds for Cylinders {
+    const MIN: f64 = -1.0;
+    const MAX: f64 = 1.0;
+}
+
 impl NoiseFn<[f64; 2]> for Cylinders {
     fn get(&self, point: [f64; 2]) -> f64 {
         calculate_cylinders(&point, self.frequency)
@@ -55,12 +73,61 @@ fn calculate_cylinders(point: &[f64], frequency: f64) -> f64 {
     let y = point[1] * frequency;
 
     // Calculate the distance of the point from the origin.
-    let dist_from_center = (x.powi(2) + y.powi(2)).sqrt();
+    let dist_from_center = float::sqrt(float::powi(x, 2) + float::powi(y, 2));
 
-    let dist_from_smaller_sphere = dist_from_center - dist_from_center.floor();
+    let dist_from_smaller_sphere = dist_from_center - float::floor(dist_from_center);
     let dist_from_larger_sphere = 1.0 - dist_from_smaller_sphere;
     let nearest_dist = dist_from_smaller_sphere.min(dist_from_larger_sphere);
 
     // Shift the result to be in the -1.0 to +1.0 range.
     1.0 - (nearest_dist * 4.0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    // Rings are centered on the z axis, so the radius is measured in the
+    // xy-plane.
+    #[test]
+    fn output_is_radially_symmetric() {
+        let cylinders = Cylinders::new().set_frequency(1.7);
+
+        for i in 0..20 {
+            let radius = i as f64 * 0.23;
+            let expected = cylinders.get([radius, 0.0]);
+
+            for j in 0..16 {
+                let angle = j as f64 * PI / 8.0;
+                let (sin, cos) = angle.sin_cos();
+                let value = cylinders.get([radius * cos, radius * sin, j as f64]);
+
+                assert!((value - expected).abs() < 1e-9);
+                assert!((-1.0..=1.0).contains(&value));
+            }
+        }
+    }
+
+    #[test]
+    fn cylinders_run_along_z_axis() {
+        let cylinders = Cylinders::new().set_frequency(1.7);
+
+        for i in 0..20 {
+            let [x, y] = [i as f64 * 0.23, i as f64 * -0.11];
+            let expected = cylinders.get([x, y]);
+
+            for j in 0..10 {
+                let z = j as f64 * 3.1 - 15.0;
+                assert_eq!(cylinders.get([x, y, z]), expected);
+                assert_eq!(cylinders.get([x, y, z, -z]), expected);
+            }
+        }
+
+        // Moving away from the z axis along y changes the output.
+        assert_ne!(
+            cylinders.get([0.0, 0.0, 0.0]),
+            cylinders.get([0.0, 0.3, 0.0])
+        );
+    }
+}
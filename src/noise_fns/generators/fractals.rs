@@ -2,17 +2,29 @@ pub use self::basicmulti::*;
 pub use self::billow::*;
 pub use self::fbm::*;
 pub use self::hybridmulti::*;
+pub use self::layered::*;
+pub use self::lod_noise::*;
 pub use self::ridgedmulti::*;
 
 mod basicmulti;
 mod billow;
 mod fbm;
 mod hybridmulti;
+mod layered;
+mod lod_noise;
 mod ridgedmulti;
 
-use crate::noise_fns::{Perlin, Seedable};
+use crate::{
+    math::{self, float, interpolate},
+    noise_fns::{NoiseFn, Perlin, Seedable},
+};
+use alloc::vec::Vec;
 
 /// Trait for `MultiFractal` functions
+///
+/// Setting the seed of a fractal reseeds each of its octaves with a seed
+/// derived from the base seed, `seed.wrapping_add(octave)`, so the octaves
+/// are uncorrelated with each other.
 pub trait MultiFractal {
     fn set_octaves(self, octaves: usize) -> Self;
 
@@ -21,12 +33,814 @@ pub trait MultiFractal {
     fn set_lacunarity(self, lacunarity: f64) -> Self;
 
     fn set_persistence(self, persistence: f64) -> Self;
+
+    /// Enables or disables rescaling the output by the largest value the
+    /// octaves can theoretically accumulate, which keeps the output strictly
+    /// within [-1,1] for any combination of parameters.
+    ///
+    /// Fractals whose output is always scaled this way ignore this setting.
+    fn set_normalized(self, normalized: bool) -> Self;
 }
 
+/// In-place counterparts of the `MultiFractal` setters, for fractals that
+/// are only reachable through a mutable reference.
+///
+/// Each method has the same effect as the `MultiFractal` setter of the same
+/// name. The fields are updated directly, and the octave sources are only
+/// regenerated when the number of octaves changes.
+pub trait MultiFractalMut {
+    fn update_octaves(&mut self, octaves: usize);
+
+    fn update_frequency(&mut self, frequency: f64);
+
+    fn update_lacunarity(&mut self, lacunarity: f64);
+
+    fn update_persistence(&mut self, persistence: f64);
+
+    fn update_normalized(&mut self, normalized: bool);
+}
+
+/// Trait for fractals that can be evaluated with only some of their octaves.
+///
+/// Evaluating fewer octaves reuses the same per-octave sources, so raising
+/// the octave count only adds finer detail on top of the coarse shape. This
+/// makes it suitable for level-of-detail schemes, where distant samples need
+/// fewer octaves than close ones.
+pub trait OctaveNoiseFn<T>: NoiseFn<T> {
+    /// Returns the number of octaves the fractal was built with.
+    fn octaves(&self) -> usize;
+
+    /// Returns the output of the fractal using only its first `octaves`
+    /// octaves, as if it had been built with that many octaves.
+    ///
+    /// The octave count is clamped to the range between 1 and the number of
+    /// octaves the fractal was built with.
+    fn get_with_octaves(&self, point: T, octaves: usize) -> f64 {
+        self.get_with_fractional_octaves(point, octaves as f64)
+    }
+
+    /// Returns the output of the fractal using a fractional number of
+    /// octaves. The fractional part fades the last octave in, so that the
+    /// output changes continuously as `octaves` increases.
+    ///
+    /// The octave count is clamped to the range between 1 and the number of
+    /// octaves the fractal was built with.
+    fn get_with_fractional_octaves(&self, point: T, octaves: f64) -> f64;
+}
+
+impl<T, M: OctaveNoiseFn<T> + ?Sized> OctaveNoiseFn<T> for &M {
+    #[inline]
+    fn octaves(&self) -> usize {
+        M::octaves(*self)
+    }
+
+    #[inline]
+    fn get_with_fractional_octaves(&self, point: T, octaves: f64) -> f64 {
+        M::get_with_fractional_octaves(*self, point, octaves)
+    }
+}
+
+/// Builds one source per octave. Each octave is seeded with
+/// `seed.wrapping_add(octave)`, so that no two octaves share a permutation
+/// table and their features don't line up.
 fn build_sources(seed: u32, octaves: usize) -> Vec<Perlin> {
+    build_sources_from(&Perlin::new(), seed, octaves)
+}
+
+/// Builds one source per octave from copies of `base`, seeded like
+/// `build_sources`.
+fn build_sources_from<T: Seedable + Clone>(base: &T, seed: u32, octaves: usize) -> Vec<T> {
     let mut sources = Vec::with_capacity(octaves);
     for x in 0..octaves {
-        sources.push(Perlin::new().set_seed(seed + x as u32));
+        sources.push(base.clone().set_seed(seed.wrapping_add(x as u32)));
     }
     sources
 }
+
+/// Splits a fractional octave count into the number of octaves to evaluate
+/// and the fade applied to the last of them, after clamping it between 1 and
+/// `max`.
+fn octave_count(octaves: f64, max: usize) -> (usize, f64) {
+    let octaves = math::clamp(octaves, 1.0, max as f64);
+    let count = float::ceil(octaves) as usize;
+
+    (count, octaves - (count - 1) as f64)
+}
+
+/// Calculates the factor by which the amplitude of the octave following
+/// `signal` is scaled, given the fractal's weighted strength.
+///
+/// The signal is saturated to the [0,1] range before blending, so a weighted
+/// strength of 0.0 always returns 1.0 and leaves the amplitudes untouched.
+#[inline]
+fn octave_weight(signal: f64, weighted_strength: f64) -> f64 {
+    interpolate::linear(1.0, math::clamp(signal, 0.0, 1.0), weighted_strength)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::noise_fns::{
+        BasicMulti, Billow, Fbm, HybridMulti, Layer, LayeredFractal, MultiFractal, MultiFractalMut,
+        NoiseFn, OctaveNoiseFn, Perlin, RidgedMulti, Seedable, Simplex, Worley,
+    };
+    use rand::{Rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn ridged_multi_4d_attenuation_damps_later_octaves() {
+        // With a huge attenuation every octave after the first has no weight,
+        // so changing the frequency of the later octaves changes nothing.
+        let ridged = RidgedMulti::new().set_attenuation(1e9);
+        let spread = ridged.clone().set_lacunarity(3.0);
+
+        for i in 0..32 {
+            let point = [
+                i as f64 * 0.37,
+                i as f64 * -0.53,
+                i as f64 * 0.71,
+                i as f64 * -0.29,
+            ];
+            assert!((ridged.get(point) - spread.get(point)).abs() < 1e-6);
+        }
+    }
+
+    // Sum of the squared differences between neighbouring samples; a flatter
+    // function has less variation from one sample to the next.
+    fn roughness(source: &dyn NoiseFn<[f64; 2]>) -> f64 {
+        let mut total = 0.0;
+        for y in 0..64 {
+            let mut previous = source.get([0.0, y as f64 * 0.05]);
+            for x in 1..64 {
+                let value = source.get([x as f64 * 0.05, y as f64 * 0.05]);
+                total += (value - previous).powi(2);
+                previous = value;
+            }
+        }
+        total
+    }
+
+    #[test]
+    fn weighted_strength_flattens_monotonically() {
+        let strengths = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+        let fbm: Vec<f64> = strengths
+            .iter()
+            .map(|&s| roughness(&Fbm::new().set_weighted_strength(s)))
+            .collect();
+        let billow: Vec<f64> = strengths
+            .iter()
+            .map(|&s| roughness(&Billow::new().set_weighted_strength(s)))
+            .collect();
+
+        for pair in fbm.windows(2).chain(billow.windows(2)) {
+            assert!(pair[1] < pair[0], "{:?} is not decreasing", pair);
+        }
+    }
+
+    // Largest magnitude sampled over a grid spanning several lattice cells.
+    fn sampled_max(source: &dyn NoiseFn<[f64; 3]>) -> f64 {
+        let mut max = 0.0_f64;
+        for y in 0..48 {
+            for x in 0..48 {
+                let value = source.get([x as f64 * 0.137, y as f64 * 0.137, 0.5]);
+                max = max.max(value.abs());
+            }
+        }
+        max
+    }
+
+    #[test]
+    fn octaves_stay_within_bounds() {
+        for &octaves in &[1, 6, 32] {
+            for &persistence in &[0.5, 0.9, 1.0] {
+                let fbm = Fbm::new().set_octaves(octaves).set_persistence(persistence);
+                let ridged = RidgedMulti::new()
+                    .set_octaves(octaves)
+                    .set_persistence(persistence);
+
+                assert!(sampled_max(&fbm) <= 1.0);
+                assert!(sampled_max(&ridged) <= 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn octaves_are_not_capped() {
+        assert_eq!(48, Fbm::new().set_octaves(48).octaves);
+        assert_eq!(48, RidgedMulti::new().set_octaves(48).octaves);
+    }
+
+    // Applies the same random parameters to a fractal, with normalization on.
+    fn randomize<F: MultiFractal + Seedable>(fractal: F, rng: &mut XorShiftRng) -> F {
+        fractal
+            .set_seed(rng.gen())
+            .set_octaves(rng.gen_range(1, 12))
+            .set_frequency(rng.gen_range(0.1, 8.0))
+            .set_lacunarity(rng.gen_range(1.1, 4.0))
+            .set_persistence(rng.gen_range(0.0, 1.5))
+            .set_normalized(true)
+    }
+
+    #[test]
+    fn normalized_output_is_bounded() {
+        const PARAMETER_SETS: usize = 50;
+        const POINTS_PER_SET: usize = 4_000;
+
+        let mut rng = XorShiftRng::seed_from_u64(1);
+
+        for _ in 0..PARAMETER_SETS {
+            let fractals: [Box<dyn NoiseFn<[f64; 3]>>; 5] = [
+                Box::new(randomize(Fbm::new(), &mut rng)),
+                Box::new(randomize(Billow::new(), &mut rng)),
+                Box::new(randomize(BasicMulti::new(), &mut rng)),
+                Box::new(randomize(HybridMulti::new(), &mut rng)),
+                Box::new(randomize(RidgedMulti::new(), &mut rng)),
+            ];
+
+            for fractal in fractals.iter() {
+                for _ in 0..POINTS_PER_SET {
+                    let point = [
+                        rng.gen_range(-100.0, 100.0),
+                        rng.gen_range(-100.0, 100.0),
+                        rng.gen_range(-100.0, 100.0),
+                    ];
+                    let value = fractal.get(point);
+                    assert!(
+                        (-1.0..=1.0).contains(&value),
+                        "{} at {:?} is out of range",
+                        value,
+                        point
+                    );
+                }
+            }
+        }
+    }
+
+    // Checks that evaluating the first few octaves of the fractal built by
+    // `build` with 8 octaves matches the fractal built with that many
+    // octaves.
+    fn assert_fewer_octaves_match<F>(build: impl Fn(usize) -> F)
+    where
+        F: NoiseFn<[f64; 3]> + OctaveNoiseFn<[f64; 3]>,
+    {
+        let full = build(8);
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173, i as f64 * -0.291, 0.5];
+            assert_eq!(full.get(point), full.get_with_octaves(point, 8));
+        }
+
+        for octaves in 1..8 {
+            let coarse = build(octaves);
+
+            for i in 0..100 {
+                let point = [i as f64 * 0.173, i as f64 * -0.291, 0.5];
+                let value = full.get_with_octaves(point, octaves);
+                assert!((coarse.get(point) - value).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn fewer_octaves_match_separate_fractal() {
+        assert_fewer_octaves_match(|octaves| Fbm::new().set_seed(7).set_octaves(octaves));
+        assert_fewer_octaves_match(|octaves| Billow::new().set_seed(7).set_octaves(octaves));
+        assert_fewer_octaves_match(|octaves| {
+            Billow::with_base(Worley::new())
+                .set_octaves(octaves)
+                .set_normalized(true)
+        });
+        assert_fewer_octaves_match(|octaves| {
+            BasicMulti::new()
+                .set_seed(7)
+                .set_octaves(octaves)
+                .set_normalized(true)
+        });
+        assert_fewer_octaves_match(|octaves| {
+            HybridMulti::new()
+                .set_seed(7)
+                .set_octaves(octaves)
+                .set_normalized(true)
+        });
+        assert_fewer_octaves_match(|octaves| RidgedMulti::new().set_seed(7).set_octaves(octaves));
+    }
+
+    fn assert_fractional_octaves_are_continuous(fractal: &dyn OctaveNoiseFn<[f64; 3]>) {
+        let point = [1.37, -2.91, 0.5];
+
+        assert_eq!(
+            fractal.get_with_octaves(point, 3),
+            fractal.get_with_fractional_octaves(point, 3.0)
+        );
+
+        let below = fractal.get_with_fractional_octaves(point, 4.0 - 1e-9);
+        let above = fractal.get_with_fractional_octaves(point, 4.0);
+        assert!((below - above).abs() < 1e-6);
+    }
+
+    #[test]
+    fn fractional_octaves_are_continuous() {
+        assert_fractional_octaves_are_continuous(&Fbm::new().set_octaves(8));
+        assert_fractional_octaves_are_continuous(&Billow::new().set_octaves(8));
+        assert_fractional_octaves_are_continuous(&BasicMulti::new().set_octaves(8));
+        assert_fractional_octaves_are_continuous(&HybridMulti::new().set_octaves(8));
+        assert_fractional_octaves_are_continuous(&RidgedMulti::new().set_octaves(8));
+    }
+
+    #[test]
+    fn spectral_weights_follow_settings() {
+        let ridged = RidgedMulti::new().set_lacunarity(2.0).set_h(1.0);
+        assert_eq!(
+            ridged.spectral_weights(),
+            &[1.0, 0.5, 0.25, 0.125, 0.0625, 0.03125]
+        );
+
+        let ridged = ridged.set_spectral_weights(vec![1.0, 0.75, 0.5]);
+        assert_eq!(ridged.octaves, 3);
+        assert_eq!(ridged.spectral_weights(), &[1.0, 0.75, 0.5]);
+
+        let ridged = ridged.set_octaves(4);
+        assert_eq!(ridged.spectral_weights(), &[1.0, 0.75, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn single_octave_hybrid_multi_is_its_basis() {
+        let perlin = Perlin::new().set_seed(5);
+        let hybrid = HybridMulti::new()
+            .set_seed(5)
+            .set_octaves(1)
+            .set_frequency(1.0);
+        let normalized = hybrid.clone().set_normalized(true);
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173, i as f64 * -0.291, 0.5];
+            assert_eq!(hybrid.get(point), perlin.get(point) * 0.75);
+            assert_eq!(normalized.get(point), perlin.get(point));
+        }
+    }
+
+    #[test]
+    fn zero_gain_stops_after_second_octave() {
+        let gated = HybridMulti::new()
+            .set_octaves(6)
+            .set_offset(0.0)
+            .set_gain(0.0);
+        let two_octaves = HybridMulti::new().set_octaves(2).set_offset(0.0);
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173, i as f64 * -0.291, 0.5];
+            assert_eq!(gated.get(point), two_octaves.get(point));
+        }
+    }
+
+    #[test]
+    fn negative_octaves_suppress_detail() {
+        let perlin = Perlin::new().set_seed(5);
+        let hybrid = HybridMulti::new()
+            .set_seed(5)
+            .set_frequency(1.0)
+            .set_offset(0.0);
+
+        // Where the first octave is negative the weight is clamped to zero,
+        // so none of the following octaves are added, inverted or not.
+        for i in 0..200 {
+            let point = [i as f64 * 0.173, i as f64 * -0.291, 0.5];
+            let basis = perlin.get(point);
+            if basis < 0.0 {
+                assert_eq!(hybrid.get(point), basis * 0.75);
+            }
+        }
+    }
+
+    #[test]
+    fn hybrid_multi_golden_values() {
+        let hybrid = HybridMulti::new().set_seed(42);
+
+        assert_eq!(hybrid.get([0.3, -1.7]), -0.8191896083421057);
+        assert_eq!(hybrid.get([0.3, -1.7, 2.9]), 0.007443140682189033);
+
+        let hybrid = hybrid.set_offset(0.7);
+
+        assert_eq!(hybrid.get([0.3, -1.7]), -0.09050220687981167);
+        assert_eq!(hybrid.get([0.3, -1.7, 2.9]), 0.5248941582380754);
+    }
+
+    #[test]
+    fn basic_and_hybrid_multi_default_golden_values() {
+        // Output of the defaults from before their parameters were exposed.
+        // The 4D values include the fix to the 4D Perlin interpolation terms,
+        // which changed them from 0.07334394254177709 and
+        // -0.6381414151129238 respectively.
+        let basic = BasicMulti::new();
+        assert_eq!(basic.get([0.3, -1.7]), 0.18178798731692392);
+        assert_eq!(basic.get([0.3, -1.7, 2.9]), 0.026790658877412714);
+        assert_eq!(basic.get([0.3, -1.7, 2.9, -4.1]), -0.23631302470875076);
+
+        let hybrid = HybridMulti::new();
+        assert_eq!(hybrid.get([0.3, -1.7]), 0.06721949170691695);
+        assert_eq!(hybrid.get([0.3, -1.7, 2.9]), 0.026235980581807605);
+        assert_eq!(hybrid.get([0.3, -1.7, 2.9, -4.1]), -1.0319113049230613);
+
+        // Sums over a grid, so a change anywhere on it is caught.
+        let grid = |i: usize| {
+            [
+                (i % 16) as f64 * 0.173 - 1.0,
+                (i / 16) as f64 * -0.291 + 2.0,
+            ]
+        };
+        let sum_2d = |fractal: &dyn NoiseFn<[f64; 2]>| -> f64 {
+            (0..256).map(|i| fractal.get(grid(i))).sum()
+        };
+        let sum_3d = |fractal: &dyn NoiseFn<[f64; 3]>| -> f64 {
+            (0..256)
+                .map(|i| {
+                    let [x, y] = grid(i);
+                    fractal.get([x, y, 0.5])
+                })
+                .sum()
+        };
+
+        assert_eq!(sum_2d(&basic), 1.0189992618774575);
+        assert_eq!(sum_3d(&basic), -1.600944767006852);
+        assert_eq!(sum_2d(&hybrid), -2.3185372393265964);
+        assert_eq!(sum_3d(&hybrid), -0.21093787694828786);
+    }
+
+    // Asserts that every setter of `MultiFractal` changes the output of the
+    // fractal somewhere on a grid.
+    fn assert_setters_change_output<F>(fractal: F)
+    where
+        F: MultiFractal + NoiseFn<[f64; 2]> + Clone,
+    {
+        let map = |fractal: &F| -> Vec<f64> {
+            (0..256)
+                .map(|i| fractal.get([(i % 16) as f64 * 0.173, (i / 16) as f64 * 0.173]))
+                .collect()
+        };
+        let baseline = map(&fractal);
+
+        let variants = [
+            fractal.clone().set_octaves(2),
+            fractal.clone().set_frequency(0.7),
+            fractal.clone().set_lacunarity(2.5),
+            fractal.clone().set_persistence(0.9),
+            fractal.clone().set_normalized(true),
+        ];
+
+        for (index, variant) in variants.iter().enumerate() {
+            assert_ne!(map(variant), baseline, "setter {} has no effect", index);
+        }
+    }
+
+    #[test]
+    fn basic_and_hybrid_multi_setters_change_output() {
+        assert_setters_change_output(BasicMulti::new());
+        assert_setters_change_output(HybridMulti::new());
+    }
+
+    struct Terrain {
+        fbm: Fbm,
+        ridged: RidgedMulti,
+    }
+
+    impl Terrain {
+        fn set_detail(&mut self, octaves: usize) {
+            self.fbm.update_octaves(octaves);
+            self.ridged.update_octaves(octaves);
+        }
+    }
+
+    #[test]
+    fn in_place_updates_match_setters() {
+        let mut terrain = Terrain {
+            fbm: Fbm::new().set_seed(3),
+            ridged: RidgedMulti::new().set_seed(3),
+        };
+        let point = [1.37, -2.91, 0.5];
+        let before = terrain.fbm.get(point);
+
+        terrain.set_detail(2);
+        terrain.fbm.update_frequency(1.5);
+
+        let fbm = Fbm::new().set_seed(3).set_octaves(2).set_frequency(1.5);
+        let ridged = RidgedMulti::new().set_seed(3).set_octaves(2);
+
+        assert_ne!(terrain.fbm.get(point), before);
+        assert_eq!(terrain.fbm.get(point), fbm.get(point));
+        assert_eq!(terrain.ridged.get(point), ridged.get(point));
+        assert_eq!(terrain.ridged.spectral_weights().len(), 2);
+
+        terrain.ridged.update_persistence(0.7);
+        let ridged = ridged.set_persistence(0.7);
+        assert_eq!(terrain.ridged.spectral_weights(), ridged.spectral_weights());
+        assert_eq!(terrain.ridged.get(point), ridged.get(point));
+
+        // Fractals over a base without a default can be updated as well.
+        let mut billow = Billow::with_base(Worley::new());
+        billow.update_octaves(3);
+        billow.update_lacunarity(2.5);
+        let expected = Billow::with_base(Worley::new())
+            .set_octaves(3)
+            .set_lacunarity(2.5);
+        assert_eq!(billow.get(point), expected.get(point));
+    }
+
+    #[test]
+    fn layered_fractal_rejects_no_layers() {
+        let layered = LayeredFractal::<[f64; 3]>::new(Vec::new());
+        assert_eq!(layered.unwrap_err(), crate::Error::NoSources);
+    }
+
+    #[test]
+    fn layered_fractal_seeds_each_layer() {
+        let layered = LayeredFractal::new(vec![
+            Layer::new(Worley::new()).set_amplitude(2.0),
+            Layer::new(Simplex::new()).set_frequency(4.0),
+        ])
+        .unwrap()
+        .set_seed(10);
+
+        let worley = Worley::new().set_seed(10);
+        let simplex = Simplex::new().set_seed(11);
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173, i as f64 * -0.291, 0.5];
+            let expected =
+                (worley.get(point) * 2.0 + simplex.get(super::math::mul3(point, 4.0))) / 3.0;
+            assert_eq!(layered.get(point), expected);
+        }
+    }
+
+    #[test]
+    fn fbm_golden_values() {
+        let fbm = Fbm::new().set_seed(42).set_octaves(6);
+
+        assert_eq!(fbm.get([0.3, -1.7]), -0.25560117048774006);
+        assert_eq!(fbm.get([0.3, -1.7, 2.9]), -0.23988332192197687);
+    }
+
+    // Mean squared difference between samples `distance` apart along a line.
+    fn structure_function(source: &dyn NoiseFn<[f64; 2]>, distance: f64) -> f64 {
+        let mut total = 0.0;
+        let count = 4000;
+        for i in 0..count {
+            let x = i as f64 * 0.731;
+            let y = i as f64 * 0.317;
+            total += (source.get([x + distance, y]) - source.get([x, y])).powi(2);
+        }
+        total / count as f64
+    }
+
+    #[test]
+    fn half_hurst_exponent_is_brownian() {
+        let fbm = Fbm::new()
+            .set_lacunarity(2.0)
+            .set_octaves(14)
+            .set_hurst_exponent(0.5);
+        assert!((fbm.persistence - 0.5_f64.sqrt()).abs() < 1e-12);
+        assert!((fbm.hurst_exponent() - 0.5).abs() < 1e-12);
+
+        // A 1/f^2 power spectrum means the mean squared difference between
+        // two samples grows linearly with the distance between them, so
+        // quadrupling the distance quadruples the difference.
+        let near = structure_function(&fbm, 1.0 / 256.0);
+        let far = structure_function(&fbm, 1.0 / 64.0);
+        let measured = (far / near).log(4.0) / 2.0;
+        assert!((measured - 0.5).abs() < 0.05, "H = {}", measured);
+    }
+
+    #[test]
+    fn seeds_decorrelate_fractals() {
+        fn correlation(a: &Fbm, b: &Fbm) -> f64 {
+            let points: Vec<[f64; 2]> = (0..4096)
+                .map(|i| [(i % 64) as f64 * 0.737, (i / 64) as f64 * 0.737])
+                .collect();
+            let a: Vec<f64> = points.iter().map(|&point| a.get(point)).collect();
+            let b: Vec<f64> = points.iter().map(|&point| b.get(point)).collect();
+
+            let n = points.len() as f64;
+            let mean_a = a.iter().sum::<f64>() / n;
+            let mean_b = b.iter().sum::<f64>() / n;
+            let (mut covariance, mut variance_a, mut variance_b) = (0.0, 0.0, 0.0);
+            for (a, b) in a.iter().zip(&b) {
+                covariance += (a - mean_a) * (b - mean_b);
+                variance_a += (a - mean_a) * (a - mean_a);
+                variance_b += (b - mean_b) * (b - mean_b);
+            }
+            covariance / (variance_a * variance_b).sqrt()
+        }
+
+        let fbm = Fbm::new().set_seed(1);
+
+        let same = correlation(&fbm, &Fbm::new().set_seed(1));
+        assert_eq!(same, 1.0);
+
+        for &seed in &[2, 1000, u32::MAX] {
+            let other = correlation(&fbm, &Fbm::new().set_seed(seed));
+            assert!(other.abs() < 0.1, "seed {} correlates by {}", seed, other);
+        }
+
+        // Octaves wrap around to the smallest seeds.
+        let wrapped = Fbm::new().set_seed(u32::MAX).set_octaves(2);
+        let point = [0.37, -1.91];
+        let expected = Perlin::new().set_seed(u32::MAX).get(point)
+            + Perlin::new()
+                .set_seed(0)
+                .get([point[0] * wrapped.lacunarity, point[1] * wrapped.lacunarity])
+                * wrapped.persistence;
+        assert!((wrapped.get(point) - expected / (1.0 + wrapped.persistence)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn hurst_exponent_round_trips() {
+        for &h in &[0.1, 0.5, 0.9] {
+            let fbm = Fbm::new().set_hurst_exponent(h);
+            let basic_multi = BasicMulti::new().set_lacunarity(3.0).set_hurst_exponent(h);
+
+            assert!((fbm.hurst_exponent() - h).abs() < 1e-12);
+            assert!((basic_multi.hurst_exponent() - h).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn billow_sharpness_spans_fbm_to_billow() {
+        let fbm = Fbm::new().set_seed(5);
+        let billow = Billow::new().set_seed(5);
+        let flat = billow.clone().set_normalized(true).set_sharpness(0.0);
+
+        assert_eq!(billow.sharpness, Billow::DEFAULT_SHARPNESS);
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173 - 3.0, i as f64 * -0.091 + 1.0];
+            // Every octave fully folded, as Billow behaved before the
+            // sharpness was added.
+            let expected_billow = (0..billow.octaves)
+                .map(|x| {
+                    let signal = Perlin::new().set_seed(5 + x as u32).get([
+                        point[0] * billow.lacunarity.powi(x as i32),
+                        point[1] * billow.lacunarity.powi(x as i32),
+                    ]);
+                    (signal.abs() * 2.0 - 1.0) * billow.persistence.powi(x as i32)
+                })
+                .sum::<f64>()
+                * 0.5;
+
+            assert_eq!(flat.get(point), fbm.get(point));
+            assert!((billow.get(point) - expected_billow).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn ridged_sharpness_narrows_ridges() {
+        // Mean output over a grid; narrower ridges leave more of the plane
+        // near the bottom of the range.
+        let mean = |ridged: &RidgedMulti| {
+            let mut total = 0.0;
+            for y in 0..32 {
+                for x in 0..32 {
+                    total += ridged.get([x as f64 * 0.113, y as f64 * 0.113]);
+                }
+            }
+            total / 1024.0
+        };
+
+        let means: Vec<f64> = [0.5, 1.0, 2.0, 4.0]
+            .iter()
+            .map(|&sharpness| mean(&RidgedMulti::new().set_sharpness(sharpness)))
+            .collect();
+
+        for pair in means.windows(2) {
+            assert!(pair[1] < pair[0], "{:?} is not decreasing", pair);
+        }
+    }
+
+    #[test]
+    fn ridged_gain_is_reciprocal_attenuation() {
+        let ridged = RidgedMulti::new();
+        assert_eq!(ridged.gain(), 0.5);
+
+        let ridged = ridged.set_gain(4.0);
+        assert_eq!(ridged.attenuation, 0.25);
+        assert_eq!(
+            ridged.get([0.3, 1.2]),
+            RidgedMulti::new().set_attenuation(0.25).get([0.3, 1.2])
+        );
+    }
+
+    #[test]
+    fn ridged_attenuation_reduces_high_octaves() {
+        // Mean squared contribution of the octaves above the first, found as
+        // the difference from the first octave on its own.
+        let high_octaves = |attenuation: f64| {
+            let ridged = RidgedMulti::new().set_attenuation(attenuation);
+            let first = ridged.clone().set_octaves(1);
+
+            (0..64 * 64)
+                .map(|i| {
+                    let point = [(i % 64) as f64 * 0.071, (i / 64) as f64 * 0.071];
+                    (ridged.get(point) - first.get(point)).powi(2)
+                })
+                .sum::<f64>()
+                / (64.0 * 64.0)
+        };
+
+        let contributions: Vec<f64> = [1.0, 2.0, 4.0, 8.0]
+            .iter()
+            .map(|&attenuation| high_octaves(attenuation))
+            .collect();
+
+        for pair in contributions.windows(2) {
+            assert!(pair[1] < pair[0], "{:?} is not decreasing", pair);
+        }
+    }
+
+    #[test]
+    fn ridged_offset_shapes_ridges() {
+        let ridged = RidgedMulti::new();
+        assert_eq!(
+            ridged
+                .clone()
+                .set_offset(RidgedMulti::DEFAULT_OFFSET)
+                .get([0.3, 1.2]),
+            ridged.get([0.3, 1.2])
+        );
+
+        // Offsets below 1.0 flatten the valleys between the ridges.
+        let flat_points = |offset: f64| {
+            let ridged = RidgedMulti::new().set_offset(offset);
+            (0..1024)
+                .filter(|&i| ridged.get([(i % 32) as f64 * 0.113, (i / 32) as f64 * 0.113]) == -1.0)
+                .count()
+        };
+        assert!(flat_points(0.5) > flat_points(1.0) + 100);
+
+        // Without any ridges the output is flat, rather than NaN.
+        assert_eq!(RidgedMulti::new().set_offset(0.0).get([0.3, 1.2]), -1.0);
+    }
+
+    #[test]
+    fn four_dimensional_fractals_vary_smoothly_along_w() {
+        let fbm = Fbm::new();
+        let billow = Billow::new();
+        let basic = BasicMulti::new();
+        // The default scaling of HybridMulti can overshoot [-1,1].
+        let hybrid = HybridMulti::new().set_normalized(true);
+        let ridged = RidgedMulti::new();
+        let fractals: [&dyn NoiseFn<[f64; 4]>; 5] = [&fbm, &billow, &basic, &hybrid, &ridged];
+
+        for fractal in fractals.iter() {
+            let mut changed = false;
+
+            for i in 0..64 {
+                let point = [i as f64 * 0.173, i as f64 * -0.291, 0.5, 0.25];
+                let value = fractal.get(point);
+                assert!(value.is_finite() && value.abs() <= 1.0);
+
+                // A small step along w changes the output a little, but a
+                // large step gives unrelated values.
+                let nearby = fractal.get([point[0], point[1], point[2], point[3] + 1e-4]);
+                assert!((nearby - value).abs() < 0.01);
+                changed |= fractal.get([point[0], point[1], point[2], 3.7]) != value;
+            }
+
+            assert!(changed);
+        }
+
+        // With one octave, the fractal is its first Perlin source.
+        let single = Fbm::new().set_seed(5).set_octaves(1);
+        let point = [0.3, -1.2, 2.5, 0.8];
+        assert_eq!(single.get(point), Perlin::new().set_seed(5).get(point));
+    }
+
+    #[test]
+    fn billow_folds_octaves_of_any_base() {
+        // The default base is Perlin noise, seeded as before.
+        let perlin_billow = Billow::with_base(Perlin::new());
+        assert_eq!(perlin_billow.get([0.3, 1.2]), Billow::new().get([0.3, 1.2]));
+
+        let billow = |octaves| Billow::with_base(Worley::new().set_seed(9)).set_octaves(octaves);
+
+        for i in 0..16 {
+            let point = [i as f64 * 0.41 - 3.0, i as f64 * -0.23 + 1.0];
+            let mut octave_point = point;
+            let mut previous = 0.0;
+
+            for octave in 0..6 {
+                // Each octave adds its folded signal, scaled by its amplitude
+                // and the final 0.5, so the sum recovers the signal's
+                // absolute value before it was remapped to [-1,1].
+                let total = billow(octave + 1).get(point);
+                let amplitude = Billow::DEFAULT_PERSISTENCE.powi(octave as i32) * 0.5;
+                let folded = (total - previous) / amplitude;
+                let abs = (folded + 1.0) / 2.0;
+
+                let signal = Worley::new().set_seed(9 + octave as u32).get(octave_point);
+                assert!(abs >= -1e-9);
+                assert!((abs - signal.abs()).abs() < 1e-9);
+
+                previous = total;
+                octave_point = octave_point.map(|x| x * Billow::DEFAULT_LACUNARITY);
+            }
+        }
+    }
+}
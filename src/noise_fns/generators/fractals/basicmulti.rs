@@ -1,6 +1,7 @@
-use crate::math;
+use crate::math::{self, float};
 
-use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
+use crate::noise_fns::{MultiFractal, MultiFractalMut, NoiseFn, OctaveNoiseFn, Perlin, Seedable};
+use alloc::vec::Vec;
 
 /// Noise function that outputs heterogenous Multifractal noise.
 ///
@@ -18,10 +19,11 @@ pub struct BasicMulti {
     ///
     /// The number of octaves control the _amount of detail_ in the noise
     /// function. Adding more octaves increases the detail, with the drawback
-    /// of increasing the calculation time.
+    /// of increasing the calculation time. The default is 6 octaves.
     pub octaves: usize,
 
     /// The number of cycles per unit length that the noise function outputs.
+    /// The default frequency is 2.0.
     pub frequency: f64,
 
     /// A multiplier that determines how quickly the frequency increases for
@@ -31,7 +33,8 @@ pub struct BasicMulti {
     /// previous octave's frequency and the lacunarity value.
     ///
     /// A lacunarity of 2.0 results in the frequency doubling every octave. For
-    /// almost all cases, 2.0 is a good value to use.
+    /// almost all cases, 2.0 is a good value to use. The default lacunarity
+    /// is 2π/3, which keeps the lattices of the octaves from lining up.
     pub lacunarity: f64,
 
     /// A multiplier that determines how quickly the amplitudes diminish for
@@ -39,9 +42,16 @@ pub struct BasicMulti {
     ///
     /// The amplitude of each successive octave is equal to the product of the
     /// previous octave's amplitude and the persistence value. Increasing the
-    /// persistence produces "rougher" noise.
+    /// persistence produces "rougher" noise. The default persistence is 0.5.
     pub persistence: f64,
 
+    /// Determines if the output is rescaled by the largest value the octaves
+    /// can theoretically accumulate. This guarantees the output stays within
+    /// [-1,1] for any parameters, at the cost of lower contrast. The default
+    /// is false, which scales the output by a constant tuned for the default
+    /// parameters.
+    pub normalized: bool,
+
     seed: u32,
     sources: Vec<Perlin>,
 }
@@ -50,7 +60,7 @@ impl BasicMulti {
     pub const DEFAULT_SEED: u32 = 0;
     pub const DEFAULT_OCTAVES: usize = 6;
     pub const DEFAULT_FREQUENCY: f64 = 2.0;
-    pub const DEFAULT_LACUNARITY: f64 = std::f64::consts::PI * 2.0 / 3.0;
+    pub const DEFAULT_LACUNARITY: f64 = core::f64::consts::PI * 2.0 / 3.0;
     pub const DEFAULT_PERSISTENCE: f64 = 0.5;
     pub const MAX_OCTAVES: usize = 32;
 
@@ -61,9 +71,51 @@ impl BasicMulti {
             frequency: Self::DEFAULT_FREQUENCY,
             lacunarity: Self::DEFAULT_LACUNARITY,
             persistence: Self::DEFAULT_PERSISTENCE,
+            normalized: false,
             sources: super::build_sources(Self::DEFAULT_SEED, Self::DEFAULT_OCTAVES),
         }
     }
+
+    /// Returns the largest magnitude the accumulated octaves can reach.
+    ///
+    /// Every octave scales the result by at most one plus its amplitude, so
+    /// the bound is the product of those factors.
+    ///
+    /// Only the first `count` octaves are included, and the amplitude of the
+    /// last one is multiplied by `fade`.
+    fn scale(&self, count: usize, fade: f64) -> f64 {
+        (1..count)
+            .map(|x| {
+                let mut amplitude = float::powi(self.persistence.abs(), x as i32);
+                if x == count - 1 {
+                    amplitude *= fade;
+                }
+
+                1.0 + amplitude
+            })
+            .product()
+    }
+
+    /// Sets the persistence from the Hurst exponent `h`, as
+    /// `lacunarity^(-h)`, using the current lacunarity.
+    ///
+    /// See `Fbm::set_hurst_exponent` for how the exponent relates to the
+    /// roughness of the noise. Because each octave of `BasicMulti` is also
+    /// scaled by the octaves before it, the exponent only describes the
+    /// spectrum of the noise on average.
+    pub fn set_hurst_exponent(self, h: f64) -> Self {
+        let persistence = float::powf(self.lacunarity, -h);
+        Self {
+            persistence,
+            ..self
+        }
+    }
+
+    /// Returns the Hurst exponent matching the current persistence and
+    /// lacunarity, `-ln(persistence) / ln(lacunarity)`.
+    pub fn hurst_exponent(&self) -> f64 {
+        -float::ln(self.persistence) / float::ln(self.lacunarity)
+    }
 }
 
 impl Default for BasicMulti {
@@ -100,6 +152,38 @@ impl MultiFractal for BasicMulti {
             ..self
         }
     }
+
+    fn set_normalized(self, normalized: bool) -> Self {
+        Self { normalized, ..self }
+    }
+}
+
+impl MultiFractalMut for BasicMulti {
+    fn update_octaves(&mut self, octaves: usize) {
+        let octaves = math::clamp(octaves, 1, Self::MAX_OCTAVES);
+        if self.octaves == octaves {
+            return;
+        }
+
+        self.octaves = octaves;
+        self.sources = super::build_sources(self.seed, octaves);
+    }
+
+    fn update_frequency(&mut self, frequency: f64) {
+        self.frequency = frequency;
+    }
+
+    fn update_lacunarity(&mut self, lacunarity: f64) {
+        self.lacunarity = lacunarity;
+    }
+
+    fn update_persistence(&mut self, persistence: f64) {
+        self.persistence = persistence;
+    }
+
+    fn update_normalized(&mut self, normalized: bool) {
+        self.normalized = normalized;
+    }
 }
 
 impl Seedable for BasicMulti {
@@ -122,13 +206,24 @@ impl Seedable for BasicMulti {
 
 /// 2-dimensional `BasicMulti` noise
 impl NoiseFn<[f64; 2]> for BasicMulti {
-    fn get(&self, mut point: [f64; 2]) -> f64 {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.get_with_octaves(point, self.octaves)
+    }
+}
+
+impl OctaveNoiseFn<[f64; 2]> for BasicMulti {
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn get_with_fractional_octaves(&self, mut point: [f64; 2], octaves: f64) -> f64 {
+        let (count, fade) = super::octave_count(octaves, self.octaves);
         // First unscaled octave of function; later octaves are scaled.
         point = math::mul2(point, self.frequency);
         let mut result = self.sources[0].get(point);
 
         // Spectral construction inner loop, where the fractal is built.
-        for x in 1..self.octaves {
+        for x in 1..count {
             // Raise the spatial frequency.
             point = math::mul2(point, self.lacunarity);
 
@@ -136,7 +231,12 @@ impl NoiseFn<[f64; 2]> for BasicMulti {
             let mut signal = self.sources[x].get(point);
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= float::powi(self.persistence, x as i32);
+
+            // Fade in the last octave when evaluating a fractional count.
+            if x == count - 1 {
+                signal *= fade;
+            }
 
             // Scale the signal by the current 'altitude' of the function.
             signal *= result;
@@ -146,19 +246,34 @@ impl NoiseFn<[f64; 2]> for BasicMulti {
         }
 
         // Scale the result to the [-1,1] range.
-        result * 0.5
+        if self.normalized {
+            result / self.scale(count, fade)
+        } else {
+            result * 0.5
+        }
     }
 }
 
 /// 3-dimensional `BasicMulti` noise
 impl NoiseFn<[f64; 3]> for BasicMulti {
-    fn get(&self, mut point: [f64; 3]) -> f64 {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.get_with_octaves(point, self.octaves)
+    }
+}
+
+impl OctaveNoiseFn<[f64; 3]> for BasicMulti {
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn get_with_fractional_octaves(&self, mut point: [f64; 3], octaves: f64) -> f64 {
+        let (count, fade) = super::octave_count(octaves, self.octaves);
         // First unscaled octave of function; later octaves are scaled.
         point = math::mul3(point, self.frequency);
         let mut result = self.sources[0].get(point);
 
         // Spectral construction inner loop, where the fractal is built.
-        for x in 1..self.octaves {
+        for x in 1..count {
             // Raise the spatial frequency.
             point = math::mul3(point, self.lacunarity);
 
@@ -166,7 +281,12 @@ impl NoiseFn<[f64; 3]> for BasicMulti {
             let mut signal = self.sources[x].get(point);
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= float::powi(self.persistence, x as i32);
+
+            // Fade in the last octave when evaluating a fractional count.
+            if x == count - 1 {
+                signal *= fade;
+            }
 
             // Scale the signal by the current 'altitude' of the function.
             signal *= result;
@@ -176,19 +296,34 @@ impl NoiseFn<[f64; 3]> for BasicMulti {
         }
 
         // Scale the result to the [-1,1] range.
-        result * 0.5
+        if self.normalized {
+            result / self.scale(count, fade)
+        } else {
+            result * 0.5
+        }
     }
 }
 
 /// 4-dimensional `BasicMulti` noise
 impl NoiseFn<[f64; 4]> for BasicMulti {
-    fn get(&self, mut point: [f64; 4]) -> f64 {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.get_with_octaves(point, self.octaves)
+    }
+}
+
+impl OctaveNoiseFn<[f64; 4]> for BasicMulti {
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn get_with_fractional_octaves(&self, mut point: [f64; 4], octaves: f64) -> f64 {
+        let (count, fade) = super::octave_count(octaves, self.octaves);
         // First unscaled octave of function; later octaves are scaled.
         point = math::mul4(point, self.frequency);
         let mut result = self.sources[0].get(point);
 
         // Spectral construction inner loop, where the fractal is built.
-        for x in 1..self.octaves {
+        for x in 1..count {
             // Raise the spatial frequency.
             point = math::mul4(point, self.lacunarity);
 
@@ -196,7 +331,12 @@ impl NoiseFn<[f64; 4]> for BasicMulti {
             let mut signal = self.sources[x].get(point);
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= float::powi(self.persistence, x as i32);
+
+            // Fade in the last octave when evaluating a fractional count.
+            if x == count - 1 {
+                signal *= fade;
+            }
 
             // Scale the signal by the current 'altitude' of the function.
             signal *= result;
@@ -206,6 +346,10 @@ impl NoiseFn<[f64; 4]> for BasicMulti {
         }
 
         // Scale the result to the [-1,1] range.
-        result * 0.5
+        if self.normalized {
+            result / self.scale(count, fade)
+        } else {
+            result * 0.5
+        }
     }
 }
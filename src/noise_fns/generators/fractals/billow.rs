@@ -1,5 +1,6 @@
-use crate::math::{self, scale_shift};
-use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
+use crate::math::{self, float, scale_shift};
+use crate::noise_fns::{MultiFractal, MultiFractalMut, NoiseFn, OctaveNoiseFn, Perlin, Seedable};
+use alloc::vec::Vec;
 
 /// Noise function that outputs "billowy" noise.
 ///
@@ -8,8 +9,15 @@ use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
 /// This noise function is nearly identical to fBm noise, except this noise
 /// function modifies each octave with an absolute-value function. See the
 /// documentation for fBm for more information.
+///
+/// The `sharpness` parameter blends each octave between the plain value used
+/// by fBm and the folded absolute value, so the look can be tuned anywhere
+/// between the two.
+///
+/// The octaves are `Perlin` noise by default. `Billow::with_base` builds the
+/// octaves from any other seedable noise function instead, such as `Worley`.
 #[derive(Clone, Debug)]
-pub struct Billow {
+pub struct Billow<T = Perlin> {
     /// Total number of frequency octaves to generate the noise with.
     ///
     /// The number of octaves control the _amount of detail_ in the noise
@@ -38,16 +46,41 @@ pub struct Billow {
     /// persistence produces "rougher" noise.
     pub persistence: f64,
 
+    /// Determines if the output is rescaled by the largest value the octaves
+    /// can theoretically accumulate. This guarantees the output stays within
+    /// [-1,1] for any parameters, at the cost of lower contrast. The default
+    /// is false, which scales the output by a constant tuned for the default
+    /// parameters.
+    pub normalized: bool,
+
+    /// How strongly each octave attenuates the octaves that follow it.
+    ///
+    /// After each octave, the amplitude of the next octave is multiplied by
+    /// `lerp(1.0, saturate(value), weighted_strength)`, where `value` is the
+    /// output of the octave just added. The default of 0.0 disables the
+    /// weighting, while values towards 1.0 progressively flatten the noise.
+    pub weighted_strength: f64,
+
+    /// How much each octave is folded by the absolute-value function.
+    ///
+    /// Each octave is interpolated between its plain value and its folded
+    /// value by this amount before being added to the result. A sharpness of
+    /// 0.0 outputs the same noise as `Fbm`, while the default of 1.0 folds
+    /// every octave completely.
+    pub sharpness: f64,
+
     seed: u32,
-    sources: Vec<Perlin>,
+    sources: Vec<T>,
 }
 
 impl Billow {
     pub const DEFAULT_SEED: u32 = 0;
     pub const DEFAULT_OCTAVE_COUNT: usize = 6;
     pub const DEFAULT_FREQUENCY: f64 = 1.0;
-    pub const DEFAULT_LACUNARITY: f64 = std::f64::consts::PI * 2.0 / 3.0;
+    pub const DEFAULT_LACUNARITY: f64 = core::f64::consts::PI * 2.0 / 3.0;
     pub const DEFAULT_PERSISTENCE: f64 = 0.5;
+    pub const DEFAULT_WEIGHTED_STRENGTH: f64 = 0.0;
+    pub const DEFAULT_SHARPNESS: f64 = 1.0;
     pub const MAX_OCTAVES: usize = 32;
 
     pub fn new() -> Self {
@@ -57,9 +90,67 @@ impl Billow {
             frequency: Self::DEFAULT_FREQUENCY,
             lacunarity: Self::DEFAULT_LACUNARITY,
             persistence: Self::DEFAULT_PERSISTENCE,
+            normalized: false,
+            weighted_strength: Self::DEFAULT_WEIGHTED_STRENGTH,
+            sharpness: Self::DEFAULT_SHARPNESS,
             sources: super::build_sources(Self::DEFAULT_SEED, Self::DEFAULT_OCTAVE_COUNT),
         }
     }
+
+    /// Creates billowy noise whose octaves are copies of `base`, each with
+    /// its own seed. The seed of `base` becomes the seed of the fractal.
+    pub fn with_base<T: Seedable + Clone>(base: T) -> Billow<T> {
+        let seed = base.seed();
+
+        Billow {
+            seed,
+            octaves: Self::DEFAULT_OCTAVE_COUNT,
+            frequency: Self::DEFAULT_FREQUENCY,
+            lacunarity: Self::DEFAULT_LACUNARITY,
+            persistence: Self::DEFAULT_PERSISTENCE,
+            normalized: false,
+            weighted_strength: Self::DEFAULT_WEIGHTED_STRENGTH,
+            sharpness: Self::DEFAULT_SHARPNESS,
+            sources: super::build_sources_from(&base, seed, Self::DEFAULT_OCTAVE_COUNT),
+        }
+    }
+}
+
+impl<T> Billow<T> {
+    pub fn set_weighted_strength(self, weighted_strength: f64) -> Self {
+        Self {
+            weighted_strength,
+            ..self
+        }
+    }
+
+    pub fn set_sharpness(self, sharpness: f64) -> Self {
+        Self { sharpness, ..self }
+    }
+
+    /// Folds `signal` by the absolute-value function, scaled and shifted back
+    /// to the [-1,1] range, to the extent given by the sharpness.
+    ///
+    /// Both ends of the blend are exact, so a sharpness of 0.0 or 1.0 returns
+    /// exactly the plain or the folded signal.
+    #[inline]
+    fn fold(&self, signal: f64) -> f64 {
+        (1.0 - self.sharpness) * signal + self.sharpness * scale_shift(signal, 2.0)
+    }
+
+    /// Returns the largest magnitude the accumulated octaves can reach, which
+    /// is the sum of the amplitudes of all octaves.
+    ///
+    /// Only the first `count` octaves are included, and the amplitude of the
+    /// last one is multiplied by `fade`.
+    fn scale(&self, count: usize, fade: f64) -> f64 {
+        let last = count - 1;
+        let full: f64 = (0..last)
+            .map(|x| float::powi(self.persistence.abs(), x as i32))
+            .sum();
+
+        full + float::powi(self.persistence.abs(), last as i32) * fade
+    }
 }
 
 impl Default for Billow {
@@ -68,16 +159,16 @@ impl Default for Billow {
     }
 }
 
-impl MultiFractal for Billow {
+impl<T: Seedable + Clone> MultiFractal for Billow<T> {
     fn set_octaves(self, mut octaves: usize) -> Self {
         if self.octaves == octaves {
             return self;
         }
 
-        octaves = math::clamp(octaves, 1, Self::MAX_OCTAVES);
+        octaves = math::clamp(octaves, 1, Billow::MAX_OCTAVES);
         Self {
             octaves,
-            sources: super::build_sources(self.seed, octaves),
+            sources: super::build_sources_from(&self.sources[0], self.seed, octaves),
             ..self
         }
     }
@@ -96,9 +187,41 @@ impl MultiFractal for Billow {
             ..self
         }
     }
+
+    fn set_normalized(self, normalized: bool) -> Self {
+        Self { normalized, ..self }
+    }
 }
 
-impl Seedable for Billow {
+impl<T: Seedable + Clone> MultiFractalMut for Billow<T> {
+    fn update_octaves(&mut self, octaves: usize) {
+        let octaves = math::clamp(octaves, 1, Billow::MAX_OCTAVES);
+        if self.octaves == octaves {
+            return;
+        }
+
+        self.octaves = octaves;
+        self.sources = super::build_sources_from(&self.sources[0], self.seed, octaves);
+    }
+
+    fn update_frequency(&mut self, frequency: f64) {
+        self.frequency = frequency;
+    }
+
+    fn update_lacunarity(&mut self, lacunarity: f64) {
+        self.lacunarity = lacunarity;
+    }
+
+    fn update_persistence(&mut self, persistence: f64) {
+        self.persistence = persistence;
+    }
+
+    fn update_normalized(&mut self, normalized: bool) {
+        self.normalized = normalized;
+    }
+}
+
+impl<T: Seedable + Clone> Seedable for Billow<T> {
     fn set_seed(self, seed: u32) -> Self {
         if self.seed == seed {
             return self;
@@ -106,7 +229,7 @@ impl Seedable for Billow {
 
         Self {
             seed,
-            sources: super::build_sources(seed, self.octaves),
+            sources: super::build_sources_from(&self.sources[0], seed, self.octaves),
             ..self
         }
     }
@@ -117,22 +240,45 @@ impl Seedable for Billow {
 }
 
 /// 2-dimensional Billow noise
-impl NoiseFn<[f64; 2]> for Billow {
-    fn get(&self, mut point: [f64; 2]) -> f64 {
+impl<T: NoiseFn<[f64; 2]>> NoiseFn<[f64; 2]> for Billow<T> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.get_with_octaves(point, self.octaves)
+    }
+}
+
+impl<T: NoiseFn<[f64; 2]>> OctaveNoiseFn<[f64; 2]> for Billow<T> {
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn get_with_fractional_octaves(&self, mut point: [f64; 2], octaves: f64) -> f64 {
+        let (count, fade) = super::octave_count(octaves, self.octaves);
         let mut result = 0.0;
+        let mut weight = 1.0;
 
         point = math::mul2(point, self.frequency);
 
-        for x in 0..self.octaves {
+        for x in 0..count {
             // Get the signal.
             let mut signal = self.sources[x].get(point);
 
             // Take the abs of the signal, then scale and shift back to
-            // the [-1,1] range.
-            signal = scale_shift(signal, 2.0);
+            // the [-1,1] range, blending by the sharpness.
+            signal = self.fold(signal);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Scale the amplitude appropriately for this frequency, and by
+            // the weighting from the previous octaves.
+            let mut amplitude = float::powi(self.persistence, x as i32) * weight;
+
+            // Fade in the last octave when evaluating a fractional count.
+            if x == count - 1 {
+                amplitude *= fade;
+            }
+
+            // Weight the next octave by the value of this one.
+            weight *= super::octave_weight(signal, self.weighted_strength);
+
+            signal *= amplitude;
 
             // Add the signal to the result.
             result += signal;
@@ -142,27 +288,54 @@ impl NoiseFn<[f64; 2]> for Billow {
         }
 
         // Scale the result to the [-1,1] range.
-        result * 0.5
+        if self.normalized {
+            result / self.scale(count, fade)
+        } else {
+            result * 0.5
+        }
     }
 }
 
 /// 3-dimensional Billow noise
-impl NoiseFn<[f64; 3]> for Billow {
-    fn get(&self, mut point: [f64; 3]) -> f64 {
+impl<T: NoiseFn<[f64; 3]>> NoiseFn<[f64; 3]> for Billow<T> {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.get_with_octaves(point, self.octaves)
+    }
+}
+
+impl<T: NoiseFn<[f64; 3]>> OctaveNoiseFn<[f64; 3]> for Billow<T> {
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn get_with_fractional_octaves(&self, mut point: [f64; 3], octaves: f64) -> f64 {
+        let (count, fade) = super::octave_count(octaves, self.octaves);
         let mut result = 0.0;
+        let mut weight = 1.0;
 
         point = math::mul3(point, self.frequency);
 
-        for x in 0..self.octaves {
+        for x in 0..count {
             // Get the signal.
             let mut signal = self.sources[x].get(point);
 
             // Take the abs of the signal, then scale and shift back to
-            // the [-1,1] range.
-            signal = scale_shift(signal, 2.0);
+            // the [-1,1] range, blending by the sharpness.
+            signal = self.fold(signal);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Scale the amplitude appropriately for this frequency, and by
+            // the weighting from the previous octaves.
+            let mut amplitude = float::powi(self.persistence, x as i32) * weight;
+
+            // Fade in the last octave when evaluating a fractional count.
+            if x == count - 1 {
+                amplitude *= fade;
+            }
+
+            // Weight the next octave by the value of this one.
+            weight *= super::octave_weight(signal, self.weighted_strength);
+
+            signal *= amplitude;
 
             // Add the signal to the result.
             result += signal;
@@ -172,27 +345,54 @@ impl NoiseFn<[f64; 3]> for Billow {
         }
 
         // Scale the result to the [-1,1] range.
-        result * 0.5
+        if self.normalized {
+            result / self.scale(count, fade)
+        } else {
+            result * 0.5
+        }
     }
 }
 
 /// 4-dimensional Billow noise
-impl NoiseFn<[f64; 4]> for Billow {
-    fn get(&self, mut point: [f64; 4]) -> f64 {
+impl<T: NoiseFn<[f64; 4]>> NoiseFn<[f64; 4]> for Billow<T> {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.get_with_octaves(point, self.octaves)
+    }
+}
+
+impl<T: NoiseFn<[f64; 4]>> OctaveNoiseFn<[f64; 4]> for Billow<T> {
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn get_with_fractional_octaves(&self, mut point: [f64; 4], octaves: f64) -> f64 {
+        let (count, fade) = super::octave_count(octaves, self.octaves);
         let mut result = 0.0;
+        let mut weight = 1.0;
 
         point = math::mul4(point, self.frequency);
 
-        for x in 0..self.octaves {
+        for x in 0..count {
             // Get the signal.
             let mut signal = self.sources[x].get(point);
 
             // Take the abs of the signal, then scale and shift back to
-            // the [-1,1] range.
-            signal = scale_shift(signal, 2.0);
+            // the [-1,1] range, blending by the sharpness.
+            signal = self.fold(signal);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Scale the amplitude appropriately for this frequency, and by
+            // the weighting from the previous octaves.
+            let mut amplitude = float::powi(self.persistence, x as i32) * weight;
+
+            // Fade in the last octave when evaluating a fractional count.
+            if x == count - 1 {
+                amplitude *= fade;
+            }
+
+            // Weight the next octave by the value of this one.
+            weight *= super::octave_weight(signal, self.weighted_strength);
+
+            signal *= amplitude;
 
             // Add the signal to the output value.
             result += signal;
@@ -202,6 +402,10 @@ impl NoiseFn<[f64; 4]> for Billow {
         }
 
         // Scale the result to the [-1,1] range.
-        result * 0.5
+        if self.normalized {
+            result / self.scale(count, fade)
+        } else {
+            result * 0.5
+        }
     }
 }
@@ -1,6 +1,9 @@
-use crate::math;
+use crate::math::{self, float};
 
-use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
+use crate::noise_fns::{
+    MultiFractal, MultiFractalMut, NoiseBounds, NoiseFn, OctaveNoiseFn, Perlin, Seedable,
+};
+use alloc::vec::Vec;
 
 /// Noise function that outputs fBm (fractal Brownian motion) noise.
 ///
@@ -24,7 +27,8 @@ pub struct Fbm {
     ///
     /// The number of octaves control the _amount of detail_ in the noise
     /// function. Adding more octaves increases the detail, with the drawback
-    /// of increasing the calculation time.
+    /// of increasing the calculation time. There is no upper limit on the
+    /// number of octaves; the output is rescaled to stay within [-1,1].
     pub octaves: usize,
 
     /// The number of cycles per unit length that the noise function outputs.
@@ -48,6 +52,14 @@ pub struct Fbm {
     /// persistence produces "rougher" noise.
     pub persistence: f64,
 
+    /// How strongly each octave attenuates the octaves that follow it.
+    ///
+    /// After each octave, the amplitude of the next octave is multiplied by
+    /// `lerp(1.0, saturate(value), weighted_strength)`, where `value` is the
+    /// output of the octave just added. The default of 0.0 disables the
+    /// weighting, while values towards 1.0 progressively flatten the noise.
+    pub weighted_strength: f64,
+
     seed: u32,
     sources: Vec<Perlin>,
 }
@@ -56,9 +68,9 @@ impl Fbm {
     pub const DEFAULT_SEED: u32 = 0;
     pub const DEFAULT_OCTAVE_COUNT: usize = 6;
     pub const DEFAULT_FREQUENCY: f64 = 1.0;
-    pub const DEFAULT_LACUNARITY: f64 = std::f64::consts::PI * 2.0 / 3.0;
+    pub const DEFAULT_LACUNARITY: f64 = core::f64::consts::PI * 2.0 / 3.0;
     pub const DEFAULT_PERSISTENCE: f64 = 0.5;
-    pub const MAX_OCTAVES: usize = 32;
+    pub const DEFAULT_WEIGHTED_STRENGTH: f64 = 0.0;
 
     pub fn new() -> Self {
         Self {
@@ -67,9 +79,58 @@ impl Fbm {
             frequency: Self::DEFAULT_FREQUENCY,
             lacunarity: Self::DEFAULT_LACUNARITY,
             persistence: Self::DEFAULT_PERSISTENCE,
+            weighted_strength: Self::DEFAULT_WEIGHTED_STRENGTH,
             sources: super::build_sources(Self::DEFAULT_SEED, Self::DEFAULT_OCTAVE_COUNT),
         }
     }
+
+    /// Returns the largest magnitude the accumulated octaves can reach, which
+    /// is the sum of the amplitudes of all octaves.
+    ///
+    /// Only the first `count` octaves are included, and the amplitude of the
+    /// last one is multiplied by `fade`.
+    fn scale(&self, count: usize, fade: f64) -> f64 {
+        let last = count - 1;
+        let full: f64 = (0..last)
+            .map(|x| float::powi(self.persistence.abs(), x as i32))
+            .sum();
+
+        full + float::powi(self.persistence.abs(), last as i32) * fade
+    }
+
+    /// Sets the persistence from the Hurst exponent `h` of the noise, using
+    /// the current lacunarity.
+    ///
+    /// The Hurst exponent is the usual parameter of fractal Brownian motion.
+    /// Each octave's amplitude falls off as `frequency^(-h)`, which makes
+    /// the persistence `lacunarity^(-h)`. The power spectrum of the noise
+    /// then falls off as `1/f^(2h + 1)` and, for 2-dimensional noise, its
+    /// fractal dimension is `3 - h`. A Hurst exponent of 0.5 gives ordinary
+    /// Brownian motion with a `1/f^2` spectrum, while values towards 1.0
+    /// give smoother noise and values towards 0.0 rougher noise.
+    ///
+    /// Only the persistence is stored, so the lacunarity should be set
+    /// first.
+    pub fn set_hurst_exponent(self, h: f64) -> Self {
+        let persistence = float::powf(self.lacunarity, -h);
+        Self {
+            persistence,
+            ..self
+        }
+    }
+
+    /// Returns the Hurst exponent matching the current persistence and
+    /// lacunarity, `-ln(persistence) / ln(lacunarity)`.
+    pub fn hurst_exponent(&self) -> f64 {
+        -float::ln(self.persistence) / float::ln(self.lacunarity)
+    }
+
+    pub fn set_weighted_strength(self, weighted_strength: f64) -> Self {
+        Self {
+            weighted_strength,
+            ..self
+        }
+    }
 }
 
 impl Default for Fbm {
@@ -78,13 +139,19 @@ impl Default for Fbm {
     }
 }
 
+/// The output is divided by the sum of the amplitudes of the octaves.
+impl NoiseBounds for Fbm {
+    const MIN: f64 = -1.0;
+    const MAX: f64 = 1.0;
+}
+
 impl MultiFractal for Fbm {
     fn set_octaves(self, mut octaves: usize) -> Self {
         if self.octaves == octaves {
             return self;
         }
 
-        octaves = math::clamp(octaves, 1, Self::MAX_OCTAVES);
+        octaves = octaves.max(1);
         Self {
             octaves,
             sources: super::build_sources(self.seed, octaves),
@@ -106,6 +173,38 @@ impl MultiFractal for Fbm {
             ..self
         }
     }
+
+    /// `Fbm` output is always normalized, so this has no effect.
+    fn set_normalized(self, _normalized: bool) -> Self {
+        self
+    }
+}
+
+impl MultiFractalMut for Fbm {
+    fn update_octaves(&mut self, octaves: usize) {
+        let octaves = octaves.max(1);
+        if self.octaves == octaves {
+            return;
+        }
+
+        self.octaves = octaves;
+        self.sources = super::build_sources(self.seed, octaves);
+    }
+
+    fn update_frequency(&mut self, frequency: f64) {
+        self.frequency = frequency;
+    }
+
+    fn update_lacunarity(&mut self, lacunarity: f64) {
+        self.lacunarity = lacunarity;
+    }
+
+    fn update_persistence(&mut self, persistence: f64) {
+        self.persistence = persistence;
+    }
+
+    /// `Fbm` output is always normalized, so this has no effect.
+    fn update_normalized(&mut self, _normalized: bool) {}
 }
 
 impl Seedable for Fbm {
@@ -128,17 +227,40 @@ impl Seedable for Fbm {
 
 /// 2-dimensional Fbm noise
 impl NoiseFn<[f64; 2]> for Fbm {
-    fn get(&self, mut point: [f64; 2]) -> f64 {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.get_with_octaves(point, self.octaves)
+    }
+}
+
+impl OctaveNoiseFn<[f64; 2]> for Fbm {
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn get_with_fractional_octaves(&self, mut point: [f64; 2], octaves: f64) -> f64 {
+        let (count, fade) = super::octave_count(octaves, self.octaves);
         let mut result = 0.0;
+        let mut weight = 1.0;
 
         point = math::mul2(point, self.frequency);
 
-        for x in 0..self.octaves {
+        for x in 0..count {
             // Get the signal.
             let mut signal = self.sources[x].get(point);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Scale the amplitude appropriately for this frequency, and by
+            // the weighting from the previous octaves.
+            let mut amplitude = float::powi(self.persistence, x as i32) * weight;
+
+            // Fade in the last octave when evaluating a fractional count.
+            if x == count - 1 {
+                amplitude *= fade;
+            }
+
+            // Weight the next octave by the value of this one.
+            weight *= super::octave_weight(signal, self.weighted_strength);
+
+            signal *= amplitude;
 
             // Add the signal to the result.
             result += signal;
@@ -147,25 +269,47 @@ impl NoiseFn<[f64; 2]> for Fbm {
             point = math::mul2(point, self.lacunarity);
         }
 
-        // Scale and shift the result into the [-1,1] range
-        let scale = 2.0 - self.persistence.powi(self.octaves as i32 - 1);
-        result / scale
+        // Scale the result into the [-1,1] range
+        result / self.scale(count, fade)
     }
 }
 
 /// 3-dimensional Fbm noise
 impl NoiseFn<[f64; 3]> for Fbm {
-    fn get(&self, mut point: [f64; 3]) -> f64 {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.get_with_octaves(point, self.octaves)
+    }
+}
+
+impl OctaveNoiseFn<[f64; 3]> for Fbm {
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn get_with_fractional_octaves(&self, mut point: [f64; 3], octaves: f64) -> f64 {
+        let (count, fade) = super::octave_count(octaves, self.octaves);
         let mut result = 0.0;
+        let mut weight = 1.0;
 
         point = math::mul3(point, self.frequency);
 
-        for x in 0..self.octaves {
+        for x in 0..count {
             // Get the signal.
             let mut signal = self.sources[x].get(point);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Scale the amplitude appropriately for this frequency, and by
+            // the weighting from the previous octaves.
+            let mut amplitude = float::powi(self.persistence, x as i32) * weight;
+
+            // Fade in the last octave when evaluating a fractional count.
+            if x == count - 1 {
+                amplitude *= fade;
+            }
+
+            // Weight the next octave by the value of this one.
+            weight *= super::octave_weight(signal, self.weighted_strength);
+
+            signal *= amplitude;
 
             // Add the signal to the result.
             result += signal;
@@ -174,25 +318,47 @@ impl NoiseFn<[f64; 3]> for Fbm {
             point = math::mul3(point, self.lacunarity);
         }
 
-        // Scale and shift the result into the [-1,1] range
-        let scale = 2.0 - self.persistence.powi(self.octaves as i32 - 1);
-        result / scale
+        // Scale the result into the [-1,1] range
+        result / self.scale(count, fade)
     }
 }
 
 /// 4-dimensional Fbm noise
 impl NoiseFn<[f64; 4]> for Fbm {
-    fn get(&self, mut point: [f64; 4]) -> f64 {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.get_with_octaves(point, self.octaves)
+    }
+}
+
+impl OctaveNoiseFn<[f64; 4]> for Fbm {
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn get_with_fractional_octaves(&self, mut point: [f64; 4], octaves: f64) -> f64 {
+        let (count, fade) = super::octave_count(octaves, self.octaves);
         let mut result = 0.0;
+        let mut weight = 1.0;
 
         point = math::mul4(point, self.frequency);
 
-        for x in 0..self.octaves {
+        for x in 0..count {
             // Get the signal.
             let mut signal = self.sources[x].get(point);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Scale the amplitude appropriately for this frequency, and by
+            // the weighting from the previous octaves.
+            let mut amplitude = float::powi(self.persistence, x as i32) * weight;
+
+            // Fade in the last octave when evaluating a fractional count.
+            if x == count - 1 {
+                amplitude *= fade;
+            }
+
+            // Weight the next octave by the value of this one.
+            weight *= super::octave_weight(signal, self.weighted_strength);
+
+            signal *= amplitude;
 
             // Add the signal to the result.
             result += signal;
@@ -201,8 +367,7 @@ impl NoiseFn<[f64; 4]> for Fbm {
             point = math::mul4(point, self.lacunarity);
         }
 
-        // Scale and shift the result into the [-1,1] range
-        let scale = 2.0 - self.persistence.powi(self.octaves as i32 - 1);
-        result / scale
+        // Scale the result into the [-1,1] range
+        result / self.scale(count, fade)
     }
 }
@@ -1,21 +1,28 @@
-use crate::math;
+use crate::math::{self, float};
 
-use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
+use crate::noise_fns::{MultiFractal, MultiFractalMut, NoiseFn, OctaveNoiseFn, Perlin, Seedable};
+use alloc::vec::Vec;
 
 /// Noise function that outputs hybrid Multifractal noise.
 ///
 /// The result of this multifractal noise is that valleys in the noise should
 /// have smooth bottoms at all altitudes.
+///
+/// By default each octave is weighted by at least 1.0. Calling `set_offset`
+/// or `set_gain` switches to the weighting of Musgrave's hybrid
+/// multifractal, where an offset is added to each octave and the weight
+/// passed on to the next one is clamped to [0,1].
 #[derive(Clone, Debug)]
 pub struct HybridMulti {
     /// Total number of frequency octaves to generate the noise with.
     ///
     /// The number of octaves control the _amount of detail_ in the noise
     /// function. Adding more octaves increases the detail, with the drawback
-    /// of increasing the calculation time.
+    /// of increasing the calculation time. The default is 6 octaves.
     pub octaves: usize,
 
     /// The number of cycles per unit length that the noise function outputs.
+    /// The default frequency is 2.0.
     pub frequency: f64,
 
     /// A multiplier that determines how quickly the frequency increases for
@@ -25,7 +32,8 @@ pub struct HybridMulti {
     /// previous octave's frequency and the lacunarity value.
     ///
     /// A lacunarity of 2.0 results in the frequency doubling every octave. For
-    /// almost all cases, 2.0 is a good value to use.
+    /// almost all cases, 2.0 is a good value to use. The default lacunarity
+    /// is 2π/3, which keeps the lattices of the octaves from lining up.
     pub lacunarity: f64,
 
     /// A multiplier that determines how quickly the amplitudes diminish for
@@ -33,19 +41,47 @@ pub struct HybridMulti {
     ///
     /// The amplitude of each successive octave is equal to the product of the
     /// previous octave's amplitude and the persistence value. Increasing the
-    /// persistence produces "rougher" noise.
+    /// persistence produces "rougher" noise. The default persistence is 0.25.
     pub persistence: f64,
 
+    /// Determines if the output is rescaled by the largest value the octaves
+    /// can theoretically accumulate. This guarantees the output stays within
+    /// [-1,1] for any parameters, at the cost of lower contrast. The default
+    /// is false, which scales the output by a constant tuned for the default
+    /// parameters.
+    pub normalized: bool,
+
+    /// Value added to the output of each octave before it is weighted.
+    ///
+    /// Raising the offset makes more of the signal positive, so that more of
+    /// the following octaves are let through. The offset is only applied
+    /// once `set_offset` or `set_gain` has been called. The default offset
+    /// is 1.0.
+    pub offset: f64,
+
+    /// Multiplier applied to the weight that each octave passes on to the
+    /// octaves that follow it.
+    ///
+    /// Low areas of the noise suppress the detail added on top of them, which
+    /// keeps valleys smooth while peaks get rough. A higher gain lets more
+    /// detail through the peaks. The gain is only applied once `set_offset`
+    /// or `set_gain` has been called. The default gain is 1.0, which matches
+    /// the weighting used otherwise.
+    pub gain: f64,
+
     seed: u32,
     sources: Vec<Perlin>,
+    musgrave: bool,
 }
 
 impl HybridMulti {
     pub const DEFAULT_SEED: u32 = 0;
     pub const DEFAULT_OCTAVES: usize = 6;
     pub const DEFAULT_FREQUENCY: f64 = 2.0;
-    pub const DEFAULT_LACUNARITY: f64 = std::f64::consts::PI * 2.0 / 3.0;
+    pub const DEFAULT_LACUNARITY: f64 = core::f64::consts::PI * 2.0 / 3.0;
     pub const DEFAULT_PERSISTENCE: f64 = 0.25;
+    pub const DEFAULT_OFFSET: f64 = 1.0;
+    pub const DEFAULT_GAIN: f64 = 1.0;
     pub const MAX_OCTAVES: usize = 32;
 
     pub fn new() -> Self {
@@ -55,7 +91,124 @@ impl HybridMulti {
             frequency: Self::DEFAULT_FREQUENCY,
             lacunarity: Self::DEFAULT_LACUNARITY,
             persistence: Self::DEFAULT_PERSISTENCE,
+            normalized: false,
+            offset: Self::DEFAULT_OFFSET,
+            gain: Self::DEFAULT_GAIN,
             sources: super::build_sources(Self::DEFAULT_SEED, Self::DEFAULT_OCTAVES),
+            musgrave: false,
+        }
+    }
+
+    /// Returns the largest magnitude the accumulated octaves can reach.
+    ///
+    /// This follows the same weighting as `get`, assuming every octave
+    /// produces its largest possible value.
+    ///
+    /// Only the first `count` octaves are included, and the last one is
+    /// multiplied by `fade`.
+    fn scale(&self, count: usize, fade: f64) -> f64 {
+        let offset = if self.musgrave {
+            self.offset.abs()
+        } else {
+            0.0
+        };
+        let mut result = (1.0 + offset) * self.amplitude(0).abs();
+        let mut weight = result;
+
+        for x in 1..count {
+            // Clamping the weight to [0,1] can only lower it, so only the
+            // lower limit of the original weighting needs to be followed.
+            if !self.musgrave {
+                weight = weight.max(1.0);
+            }
+
+            let mut signal = (1.0 + offset) * self.amplitude(x).abs();
+            if x == count - 1 {
+                signal *= fade;
+            }
+
+            result += weight * signal;
+            weight *= self.gain().abs() * signal;
+        }
+
+        result
+    }
+
+    /// Returns the amplitude of octave `x`.
+    ///
+    /// The original weighting scales the first octave by the persistence as
+    /// well as the second.
+    fn amplitude(&self, x: usize) -> f64 {
+        if self.musgrave {
+            float::powi(self.persistence, x as i32)
+        } else {
+            float::powi(self.persistence, x.max(1) as i32)
+        }
+    }
+
+    /// Returns the contribution of octave `x`, given the output of its
+    /// source.
+    fn signal(&self, value: f64, x: usize) -> f64 {
+        if self.musgrave {
+            (value + self.offset) * self.amplitude(x)
+        } else {
+            value * self.amplitude(x)
+        }
+    }
+
+    /// Returns the multiplier applied to the weight passed on to the next
+    /// octave.
+    fn gain(&self) -> f64 {
+        if self.musgrave {
+            self.gain
+        } else {
+            1.0
+        }
+    }
+
+    /// Limits the weight that the previous octaves pass on to the next one.
+    fn limit_weight(&self, weight: f64) -> f64 {
+        if self.musgrave {
+            // Stop negative weights from inverting the octave.
+            math::clamp(weight, 0.0, 1.0)
+        } else {
+            weight.max(1.0)
+        }
+    }
+
+    /// Scales the accumulated octaves to the [-1,1] range.
+    fn scale_result(&self, result: f64, count: usize, fade: f64) -> f64 {
+        if self.normalized {
+            result / self.scale(count, fade)
+        } else if self.musgrave {
+            result * 0.75
+        } else {
+            result * 3.0
+        }
+    }
+
+    /// Sets the offset added to each octave.
+    ///
+    /// This switches to weighting each octave by the previous ones clamped to
+    /// [0,1], rather than by at least 1.0, so low areas of the noise suppress
+    /// the detail added on top of them. The gain is applied from then on too.
+    pub fn set_offset(self, offset: f64) -> Self {
+        Self {
+            offset,
+            musgrave: true,
+            ..self
+        }
+    }
+
+    /// Sets the multiplier applied to the weight passed on to each octave.
+    ///
+    /// Like `set_offset`, this switches to weighting each octave by the
+    /// previous ones clamped to [0,1], and applies the offset from then on.
+    pub fn set_gain(self, gain: f64) -> Self {
+        Self {
+            gain,
+            musgrave: true,
+            ..self
         }
     }
 }
@@ -94,6 +247,38 @@ impl MultiFractal for HybridMulti {
             ..self
         }
     }
+
+    fn set_normalized(self, normalized: bool) -> Self {
+        Self { normalized, ..self }
+    }
+}
+
+impl MultiFractalMut for HybridMulti {
+    fn update_octaves(&mut self, octaves: usize) {
+        let octaves = math::clamp(octaves, 1, Self::MAX_OCTAVES);
+        if self.octaves == octaves {
+            return;
+        }
+
+        self.octaves = octaves;
+        self.sources = super::build_sources(self.seed, octaves);
+    }
+
+    fn update_frequency(&mut self, frequency: f64) {
+        self.frequency = frequency;
+    }
+
+    fn update_lacunarity(&mut self, lacunarity: f64) {
+        self.lacunarity = lacunarity;
+    }
+
+    fn update_persistence(&mut self, persistence: f64) {
+        self.persistence = persistence;
+    }
+
+    fn update_normalized(&mut self, normalized: bool) {
+        self.normalized = normalized;
+    }
 }
 
 impl Seedable for HybridMulti {
@@ -116,102 +301,141 @@ impl Seedable for HybridMulti {
 
 /// 2-dimensional `HybridMulti` noise
 impl NoiseFn<[f64; 2]> for HybridMulti {
-    fn get(&self, mut point: [f64; 2]) -> f64 {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.get_with_octaves(point, self.octaves)
+    }
+}
+
+impl OctaveNoiseFn<[f64; 2]> for HybridMulti {
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn get_with_fractional_octaves(&self, mut point: [f64; 2], octaves: f64) -> f64 {
+        let (count, fade) = super::octave_count(octaves, self.octaves);
         // First unscaled octave of function; later octaves are scaled.
         point = math::mul2(point, self.frequency);
-        let mut result = self.sources[0].get(point) * self.persistence;
+        let mut result = self.signal(self.sources[0].get(point), 0);
         let mut weight = result;
 
         // Spectral construction inner loop, where the fractal is built.
-        for x in 1..self.octaves {
+        for x in 1..count {
             // Prevent divergence.
-            weight = weight.max(1.0);
+            weight = self.limit_weight(weight);
 
             // Raise the spatial frequency.
             point = math::mul2(point, self.lacunarity);
 
-            // Get noise value.
-            let mut signal = self.sources[x].get(point);
+            // Get noise value, scaled appropriately for this frequency.
+            let mut signal = self.signal(self.sources[x].get(point), x);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Fade in the last octave when evaluating a fractional count.
+            if x == count - 1 {
+                signal *= fade;
+            }
 
             // Add it in, weighted by previous octave's noise value.
             result += weight * signal;
 
             // Update the weighting value.
-            weight *= signal;
+            weight *= self.gain() * signal;
         }
 
         // Scale the result to the [-1,1] range
-        result * 3.0
+        self.scale_result(result, count, fade)
     }
 }
 
 /// 3-dimensional `HybridMulti` noise
 impl NoiseFn<[f64; 3]> for HybridMulti {
-    fn get(&self, mut point: [f64; 3]) -> f64 {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.get_with_octaves(point, self.octaves)
+    }
+}
+
+impl OctaveNoiseFn<[f64; 3]> for HybridMulti {
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn get_with_fractional_octaves(&self, mut point: [f64; 3], octaves: f64) -> f64 {
+        let (count, fade) = super::octave_count(octaves, self.octaves);
         // First unscaled octave of function; later octaves are scaled.
         point = math::mul3(point, self.frequency);
-        let mut result = self.sources[0].get(point) * self.persistence;
+        let mut result = self.signal(self.sources[0].get(point), 0);
         let mut weight = result;
 
         // Spectral construction inner loop, where the fractal is built.
-        for x in 1..self.octaves {
+        for x in 1..count {
             // Prevent divergence.
-            weight = weight.max(1.0);
+            weight = self.limit_weight(weight);
 
             // Raise the spatial frequency.
             point = math::mul3(point, self.lacunarity);
 
-            // Get noise value.
-            let mut signal = self.sources[x].get(point);
+            // Get noise value, scaled appropriately for this frequency.
+            let mut signal = self.signal(self.sources[x].get(point), x);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Fade in the last octave when evaluating a fractional count.
+            if x == count - 1 {
+                signal *= fade;
+            }
 
             // Add it in, weighted by previous octave's noise value.
             result += weight * signal;
 
             // Update the weighting value.
-            weight *= signal;
+            weight *= self.gain() * signal;
         }
 
         // Scale the result to the [-1,1] range
-        result * 3.0
+        self.scale_result(result, count, fade)
     }
 }
 
 /// 4-dimensional `HybridMulti` noise
 impl NoiseFn<[f64; 4]> for HybridMulti {
-    fn get(&self, mut point: [f64; 4]) -> f64 {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.get_with_octaves(point, self.octaves)
+    }
+}
+
+impl OctaveNoiseFn<[f64; 4]> for HybridMulti {
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn get_with_fractional_octaves(&self, mut point: [f64; 4], octaves: f64) -> f64 {
+        let (count, fade) = super::octave_count(octaves, self.octaves);
         // First unscaled octave of function; later octaves are scaled.
         point = math::mul4(point, self.frequency);
-        let mut result = self.sources[0].get(point) * self.persistence;
+        let mut result = self.signal(self.sources[0].get(point), 0);
         let mut weight = result;
 
         // Spectral construction inner loop, where the fractal is built.
-        for x in 1..self.octaves {
+        for x in 1..count {
             // Prevent divergence.
-            weight = weight.max(1.0);
+            weight = self.limit_weight(weight);
 
             // Raise the spatial frequency.
             point = math::mul4(point, self.lacunarity);
 
-            // Get noise value.
-            let mut signal = self.sources[x].get(point);
+            // Get noise value, scaled appropriately for this frequency.
+            let mut signal = self.signal(self.sources[x].get(point), x);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Fade in the last octave when evaluating a fractional count.
+            if x == count - 1 {
+                signal *= fade;
+            }
 
             // Add it in, weighted by previous octave's noise value.
             result += weight * signal;
 
             // Update the weighting value.
-            weight *= signal;
+            weight *= self.gain() * signal;
         }
 
         // Scale the result to the [-1,1] range
-        result * 3.0
+        self.scale_result(result, count, fade)
     }
 }
@@ -0,0 +1,214 @@
+use crate::math;
+
+use crate::noise_fns::{NoiseFn, Seedable};
+use crate::Error;
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt;
+
+/// Noise function that sums octaves taken from a different source function
+/// for each layer.
+///
+/// Unlike the other fractals, which sample the same kind of noise at every
+/// octave, each layer of a `LayeredFractal` has its own source function,
+/// frequency and amplitude. This allows, for example, a few octaves of
+/// `Worley` noise to lay out the continents while octaves of `Simplex`
+/// noise add the detail.
+///
+/// When the fractal is seeded, each layer is seeded with the fractal's seed
+/// plus the index of the layer.
+pub struct LayeredFractal<T> {
+    layers: Vec<Layer<T>>,
+    seed: u32,
+}
+
+/// A single octave of a `LayeredFractal`.
+pub struct Layer<T> {
+    /// Source function that outputs the value of the octave.
+    source: Box<dyn LayerSource<T>>,
+
+    /// The number of cycles per unit length that the source function outputs.
+    pub frequency: f64,
+
+    /// The factor the output of the source function is multiplied by.
+    pub amplitude: f64,
+}
+
+/// Object-safe combination of `NoiseFn` and `Seedable`, so that layers with
+/// different source functions can be stored and reseeded together.
+trait LayerSource<T>: NoiseFn<T> {
+    fn with_seed(&self, seed: u32) -> Box<dyn LayerSource<T>>;
+
+    fn clone_box(&self) -> Box<dyn LayerSource<T>>;
+}
+
+impl<T, S> LayerSource<T> for S
+where
+    S: NoiseFn<T> + Seedable + Clone + 'static,
+{
+    fn with_seed(&self, seed: u32) -> Box<dyn LayerSource<T>> {
+        Box::new(self.clone().set_seed(seed))
+    }
+
+    fn clone_box(&self) -> Box<dyn LayerSource<T>> {
+        Box::new(self.clone())
+    }
+}
+
+impl<T> Layer<T> {
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_AMPLITUDE: f64 = 1.0;
+
+    pub fn new<S>(source: S) -> Self
+    where
+        S: NoiseFn<T> + Seedable + Clone + 'static,
+    {
+        Self {
+            source: Box::new(source),
+            frequency: Self::DEFAULT_FREQUENCY,
+            amplitude: Self::DEFAULT_AMPLITUDE,
+        }
+    }
+
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    pub fn set_amplitude(self, amplitude: f64) -> Self {
+        Self { amplitude, ..self }
+    }
+}
+
+impl<T> Clone for Layer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            source: self.source.clone_box(),
+            frequency: self.frequency,
+            amplitude: self.amplitude,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Layer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Layer")
+            .field("frequency", &self.frequency)
+            .field("amplitude", &self.amplitude)
+            .finish()
+    }
+}
+
+impl<T> LayeredFractal<T> {
+    pub const DEFAULT_SEED: u32 = 0;
+
+    /// Creates a fractal from the given layers, seeding each of them.
+    ///
+    /// Returns an error if `layers` is empty.
+    pub fn new(layers: Vec<Layer<T>>) -> Result<Self, Error> {
+        if layers.is_empty() {
+            return Err(Error::NoSources);
+        }
+
+        Ok(Self {
+            layers: seed_layers(&layers, Self::DEFAULT_SEED),
+            seed: Self::DEFAULT_SEED,
+        })
+    }
+
+    pub fn layers(&self) -> &[Layer<T>] {
+        &self.layers
+    }
+
+    /// Returns the sum of the amplitudes of all layers, which bounds the
+    /// output when every source function outputs values within [-1,1].
+    fn scale(&self) -> f64 {
+        self.layers.iter().map(|layer| layer.amplitude.abs()).sum()
+    }
+}
+
+fn seed_layers<T>(layers: &[Layer<T>], seed: u32) -> Vec<Layer<T>> {
+    layers
+        .iter()
+        .enumerate()
+        .map(|(x, layer)| Layer {
+            source: layer.source.with_seed(seed.wrapping_add(x as u32)),
+            ..*layer
+        })
+        .collect()
+}
+
+impl<T> Clone for LayeredFractal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            layers: self.layers.clone(),
+            seed: self.seed,
+        }
+    }
+}
+
+impl<T> fmt::Debug for LayeredFractal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LayeredFractal")
+            .field("layers", &self.layers)
+            .field("seed", &self.seed)
+            .finish()
+    }
+}
+
+impl<T> Seedable for LayeredFractal<T> {
+    fn set_seed(self, seed: u32) -> Self {
+        if self.seed == seed {
+            return self;
+        }
+
+        Self {
+            layers: seed_layers(&self.layers, seed),
+            seed,
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed
+    }
+}
+
+/// 2-dimensional `LayeredFractal` noise
+impl NoiseFn<[f64; 2]> for LayeredFractal<[f64; 2]> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let result: f64 = self
+            .layers
+            .iter()
+            .map(|layer| layer.source.get(math::mul2(point, layer.frequency)) * layer.amplitude)
+            .sum();
+
+        // Scale the result into the [-1,1] range
+        result / self.scale()
+    }
+}
+
+/// 3-dimensional `LayeredFractal` noise
+impl NoiseFn<[f64; 3]> for LayeredFractal<[f64; 3]> {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        let result: f64 = self
+            .layers
+            .iter()
+            .map(|layer| layer.source.get(math::mul3(point, layer.frequency)) * layer.amplitude)
+            .sum();
+
+        // Scale the result into the [-1,1] range
+        result / self.scale()
+    }
+}
+
+/// 4-dimensional `LayeredFractal` noise
+impl NoiseFn<[f64; 4]> for LayeredFractal<[f64; 4]> {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        let result: f64 = self
+            .layers
+            .iter()
+            .map(|layer| layer.source.get(math::mul4(point, layer.frequency)) * layer.amplitude)
+            .sum();
+
+        // Scale the result into the [-1,1] range
+        result / self.scale()
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{
+    math::{self, float},
+    noise_fns::{NoiseFn, OctaveNoiseFn},
+};
+
+/// Noise function that evaluates fewer octaves of a fractal the further a
+/// point is from the camera.
+///
+/// Distant terrain covers few pixels on screen, so its fine detail is mostly
+/// invisible, yet it costs as much to evaluate as the terrain right next to
+/// the camera. `LodNoise` trims octaves by the distance from the camera to
+/// the _x_ and _y_ coordinates of each point, relative to the view distance.
+///
+/// By default, the octave count falls linearly from the full count at the
+/// camera to a single octave at the view distance and beyond. The count is
+/// fractional, with the last octave faded in, so the output has no seams
+/// where the count changes.
+#[derive(Clone, Debug)]
+pub struct LodNoise<Source> {
+    /// Fractal to evaluate.
+    pub source: Source,
+
+    /// Position of the camera on the _xy_ plane. The default is the origin.
+    pub camera_pos: [f64; 2],
+
+    /// Distance at which the octave count reaches its lowest level. The
+    /// default is 16.0.
+    pub view_distance: f64,
+
+    /// Custom mapping from the relative distance to the camera to an octave
+    /// count. The default is `None`, which uses the linear falloff.
+    pub lod_curve: Option<fn(f64) -> usize>,
+}
+
+impl<Source> LodNoise<Source> {
+    pub const DEFAULT_CAMERA_POS: [f64; 2] = [0.0, 0.0];
+    pub const DEFAULT_VIEW_DISTANCE: f64 = 16.0;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            camera_pos: Self::DEFAULT_CAMERA_POS,
+            view_distance: Self::DEFAULT_VIEW_DISTANCE,
+            lod_curve: None,
+        }
+    }
+
+    pub fn set_camera_pos(self, camera_pos: [f64; 2]) -> Self {
+        Self { camera_pos, ..self }
+    }
+
+    pub fn set_view_distance(self, view_distance: f64) -> Self {
+        Self {
+            view_distance,
+            ..self
+        }
+    }
+
+    /// Sets a custom mapping from the relative distance to the camera to an
+    /// octave count.
+    ///
+    /// The curve is given the distance divided by the view distance, so 0.0
+    /// is at the camera and 1.0 is at the view distance. The count it returns
+    /// is clamped between 1 and the number of octaves of the source. As the
+    /// count is whole, the output changes abruptly where it changes.
+    pub fn set_lod_curve(self, lod_curve: fn(f64) -> usize) -> Self {
+        Self {
+            lod_curve: Some(lod_curve),
+            ..self
+        }
+    }
+
+    /// Returns the number of octaves to evaluate at `(x, y)`, out of
+    /// `max_octaves`.
+    pub fn octaves_at(&self, x: f64, y: f64, max_octaves: usize) -> f64 {
+        let dx = x - self.camera_pos[0];
+        let dy = y - self.camera_pos[1];
+        let distance = float::sqrt(dx * dx + dy * dy);
+
+        // Points beyond the view distance, or any point when the view
+        // distance is zero, get the lowest level of detail.
+        let relative = if distance < self.view_distance {
+            distance / self.view_distance
+        } else {
+            1.0
+        };
+
+        let octaves = match self.lod_curve {
+            Some(curve) => curve(relative) as f64,
+            None => max_octaves as f64 - (max_octaves as f64 - 1.0) * relative,
+        };
+
+        math::clamp(octaves, 1.0, max_octaves.max(1) as f64)
+    }
+}
+
+impl<Source> NoiseFn<[f64; 2]> for LodNoise<Source>
+where
+    Source: OctaveNoiseFn<[f64; 2]>,
+{
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let octaves = self.octaves_at(point[0], point[1], self.source.octaves());
+        self.source.get_with_fractional_octaves(point, octaves)
+    }
+}
+
+impl<Source> NoiseFn<[f64; 3]> for LodNoise<Source>
+where
+    Source: OctaveNoiseFn<[f64; 3]>,
+{
+    fn get(&self, point: [f64; 3]) -> f64 {
+        let octaves = self.octaves_at(point[0], point[1], self.source.octaves());
+        self.source.get_with_fractional_octaves(point, octaves)
+    }
+}
+
+impl<Source> NoiseFn<[f64; 4]> for LodNoise<Source>
+where
+    Source: OctaveNoiseFn<[f64; 4]>,
+{
+    fn get(&self, point: [f64; 4]) -> f64 {
+        let octaves = self.octaves_at(point[0], point[1], self.source.octaves());
+        self.source.get_with_fractional_octaves(point, octaves)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Billow, Fbm, MultiFractal, RidgedMulti, Worley};
+
+    #[test]
+    fn detail_falls_off_with_distance() {
+        let fbm = Fbm::new().set_octaves(8);
+        let lod = LodNoise::new(&fbm)
+            .set_camera_pos([3.0, -2.0])
+            .set_view_distance(10.0);
+
+        assert_eq!(lod.octaves_at(3.0, -2.0, 8), 8.0);
+        assert_eq!(lod.octaves_at(8.0, -2.0, 8), 4.5);
+        assert_eq!(lod.octaves_at(3.0, 20.0, 8), 1.0);
+
+        let near = [3.1, -1.8, 0.5];
+        let octaves = lod.octaves_at(near[0], near[1], 8);
+        assert!(octaves > 7.8 && octaves < 8.0);
+        assert_eq!(
+            lod.get(near),
+            fbm.get_with_fractional_octaves(near, octaves)
+        );
+
+        let far = [40.0, 12.0, 0.5];
+        assert_eq!(lod.get(far), fbm.get_with_octaves(far, 1));
+    }
+
+    #[test]
+    fn works_with_other_fractals() {
+        let ridged = RidgedMulti::new().set_octaves(8);
+        let lod = LodNoise::new(&ridged).set_view_distance(4.0);
+
+        let near = [0.1, 0.2, 0.5];
+        let octaves = lod.octaves_at(near[0], near[1], 8);
+        assert_eq!(
+            lod.get(near),
+            ridged.get_with_fractional_octaves(near, octaves)
+        );
+        assert_eq!(lod.get([0.0, 0.0, 0.5]), ridged.get([0.0, 0.0, 0.5]));
+
+        let mut previous = lod.get([0.0, 0.3]);
+        for i in 1..=5000 {
+            let value = lod.get([i as f64 * 0.001, 0.3]);
+            assert!((value - previous).abs() < 0.05, "jump at {}", i);
+            previous = value;
+        }
+
+        let billow = Billow::with_base(Worley::new()).set_octaves(4);
+        let lod = LodNoise::new(&billow).set_view_distance(10.0);
+
+        let far = [40.0, 12.0];
+        assert_eq!(lod.get(far), billow.get_with_octaves(far, 1));
+    }
+
+    #[test]
+    fn output_is_continuous_across_levels() {
+        let fbm = Fbm::new().set_octaves(8);
+        let lod = LodNoise::new(&fbm).set_view_distance(4.0);
+
+        let mut previous = lod.get([0.0, 0.3]);
+        for i in 1..=5000 {
+            let value = lod.get([i as f64 * 0.001, 0.3]);
+            assert!((value - previous).abs() < 0.05, "jump at {}", i);
+            previous = value;
+        }
+    }
+
+    #[test]
+    fn custom_curve_picks_octaves() {
+        let fbm = Fbm::new().set_octaves(6);
+        let lod = LodNoise::new(&fbm)
+            .set_view_distance(2.0)
+            .set_lod_curve(|relative| if relative < 0.5 { 10 } else { 2 });
+
+        assert_eq!(lod.octaves_at(0.5, 0.0, 6), 6.0);
+        assert_eq!(lod.octaves_at(1.5, 0.0, 6), 2.0);
+        assert_eq!(lod.get([1.5, 0.0]), fbm.get_with_octaves([1.5, 0.0], 2));
+    }
+}
@@ -1,5 +1,8 @@
-use crate::math::{self, scale_shift};
-use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
+use crate::math::{self, float, scale_shift};
+use crate::noise_fns::{
+    MultiFractal, MultiFractalMut, NoiseBounds, NoiseFn, OctaveNoiseFn, Perlin, Seedable,
+};
+use alloc::vec::Vec;
 
 /// Noise function that outputs ridged-multifractal noise.
 ///
@@ -9,11 +12,9 @@ use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
 /// an absolute-value function. Modifying the octave values in this way
 /// produces ridge-like formations.
 ///
-/// The values output from this function will usually range from -1.0 to 1.0 with
-/// default values for the parameters, but there are no guarantees that all
-/// output values will exist within this range. If the parameters are modified
-/// from their defaults, then the output will need to be scaled to remain in
-/// the [-1,1] range.
+/// The output is scaled by the largest value the octaves can accumulate for
+/// the current parameters, so the values output from this function will
+/// always range from -1.0 to 1.0.
 ///
 /// Ridged-multifractal noise is often used to generate craggy mountainous
 /// terrain or marble-like textures.
@@ -23,7 +24,8 @@ pub struct RidgedMulti {
     ///
     /// The number of octaves control the _amount of detail_ in the noise
     /// function. Adding more octaves increases the detail, with the drawback
-    /// of increasing the calculation time.
+    /// of increasing the calculation time. There is no upper limit on the
+    /// number of octaves; the output is rescaled to stay within [-1,1].
     pub octaves: usize,
 
     /// The number of cycles per unit length that the noise function outputs.
@@ -45,6 +47,9 @@ pub struct RidgedMulti {
     /// The amplitude of each successive octave is equal to the product of the
     /// previous octave's amplitude and the persistence value. Increasing the
     /// persistence produces "rougher" noise.
+    ///
+    /// The persistence is ignored once a spectral exponent or explicit
+    /// spectral weights have been set.
     pub persistence: f64,
 
     /// The attenuation to apply to the weight on each octave. This reduces
@@ -53,18 +58,44 @@ pub struct RidgedMulti {
     /// half the height of the previous.
     pub attenuation: f64,
 
+    /// The height of the ridges before they are sharpened. Each octave is
+    /// folded into `offset - |noise|`, so raising the offset widens the
+    /// ridges, and lowering it below 1.0 flattens the valleys between them
+    /// to zero. The default offset is 1.0.
+    pub offset: f64,
+
+    /// The exponent the ridges are raised to after folding each octave. The
+    /// ridges become narrower and more pointed as the sharpness increases,
+    /// and rounder as it decreases towards 0.0. The default sharpness is
+    /// 2.0.
+    pub sharpness: f64,
+
     seed: u32,
     sources: Vec<Perlin>,
+    spectrum: Spectrum,
+    spectral_weights: Vec<f64>,
+}
+
+/// How the spectral weight of each octave is derived.
+#[derive(Clone, Debug)]
+enum Spectrum {
+    /// Powers of the persistence.
+    Persistence,
+    /// Powers of the octave frequency, raised to the negated exponent.
+    Exponent(f64),
+    /// Weights given explicitly by the user.
+    Custom(Vec<f64>),
 }
 
 impl RidgedMulti {
     pub const DEFAULT_SEED: u32 = 0;
     pub const DEFAULT_OCTAVE_COUNT: usize = 6;
     pub const DEFAULT_FREQUENCY: f64 = 1.0;
-    pub const DEFAULT_LACUNARITY: f64 = std::f64::consts::PI * 2.0 / 3.0;
+    pub const DEFAULT_LACUNARITY: f64 = core::f64::consts::PI * 2.0 / 3.0;
     pub const DEFAULT_PERSISTENCE: f64 = 1.0;
     pub const DEFAULT_ATTENUATION: f64 = 2.0;
-    pub const MAX_OCTAVES: usize = 32;
+    pub const DEFAULT_SHARPNESS: f64 = 2.0;
+    pub const DEFAULT_OFFSET: f64 = 1.0;
 
     pub fn new() -> Self {
         Self {
@@ -74,7 +105,115 @@ impl RidgedMulti {
             lacunarity: Self::DEFAULT_LACUNARITY,
             persistence: Self::DEFAULT_PERSISTENCE,
             attenuation: Self::DEFAULT_ATTENUATION,
+            sharpness: Self::DEFAULT_SHARPNESS,
+            offset: Self::DEFAULT_OFFSET,
             sources: super::build_sources(Self::DEFAULT_SEED, Self::DEFAULT_OCTAVE_COUNT),
+            spectrum: Spectrum::Persistence,
+            spectral_weights: Vec::new(),
+        }
+        .with_spectral_weights()
+    }
+
+    /// Sets the spectral exponent _H_, following Musgrave's formulation.
+    ///
+    /// The amplitude of each octave is its frequency raised to the power of
+    /// -_H_, so with a lacunarity of 2.0 an exponent of 1.0 halves the
+    /// amplitude each octave. Lower exponents keep more of the high
+    /// frequencies, producing rougher terrain. Setting the exponent replaces
+    /// the amplitudes derived from the persistence.
+    ///
+    /// ```
+    /// use noice::{NoiseFn, RidgedMulti};
+    ///
+    /// // Sum of the squared differences between neighbouring samples.
+    /// let roughness = |ridged: &RidgedMulti| {
+    ///     (1..256)
+    ///         .map(|x| {
+    ///             let a = ridged.get([(x - 1) as f64 * 0.01, 0.5]);
+    ///             let b = ridged.get([x as f64 * 0.01, 0.5]);
+    ///             (b - a).powi(2)
+    ///         })
+    ///         .sum::<f64>()
+    /// };
+    ///
+    /// let smooth = RidgedMulti::new().set_h(1.0);
+    /// let rough = RidgedMulti::new().set_h(0.25);
+    ///
+    /// assert!(roughness(&rough) > roughness(&smooth));
+    /// ```
+    pub fn set_h(self, h: f64) -> Self {
+        Self {
+            spectrum: Spectrum::Exponent(h),
+            ..self
+        }
+        .with_spectral_weights()
+    }
+
+    /// Sets the amplitude of each octave explicitly, replacing those derived
+    /// from the persistence or spectral exponent.
+    ///
+    /// This also sets the number of octaves to the number of weights. If the
+    /// number of octaves is raised later, the additional octaves are given a
+    /// weight of 0.0.
+    pub fn set_spectral_weights(self, weights: Vec<f64>) -> Self {
+        Self {
+            spectrum: Spectrum::Custom(weights.clone()),
+            ..self
+        }
+        .set_octaves(weights.len())
+        .with_spectral_weights()
+    }
+
+    /// Returns the amplitude applied to each octave.
+    pub fn spectral_weights(&self) -> &[f64] {
+        &self.spectral_weights
+    }
+
+    /// Recalculates the amplitude of each octave from the current spectrum.
+    fn with_spectral_weights(mut self) -> Self {
+        self.update_spectral_weights();
+        self
+    }
+
+    /// Recalculates the amplitude of each octave in place.
+    fn update_spectral_weights(&mut self) {
+        self.spectral_weights = (0..self.octaves)
+            .map(|x| match &self.spectrum {
+                Spectrum::Persistence => float::powi(self.persistence, x as i32),
+                Spectrum::Exponent(h) => float::powf(self.lacunarity, -h * x as f64),
+                Spectrum::Custom(weights) => weights.get(x).copied().unwrap_or(0.0),
+            })
+            .collect();
+    }
+
+    /// Returns the largest value the accumulated octaves can reach.
+    ///
+    /// This follows the same weighting as `get`, assuming every octave
+    /// produces the sharpest possible ridge.
+    ///
+    /// Only the first `count` octaves are included, and the contribution of
+    /// the last one is multiplied by `fade`.
+    fn scale(&self, count: usize, fade: f64) -> f64 {
+        let peak = self.sharpen(self.offset.max(0.0));
+        let mut result = 0.0;
+        let mut weight = 1.0;
+
+        for x in 0..count {
+            let signal = peak * weight;
+            weight = math::clamp(signal / self.attenuation, 0.0, 1.0);
+
+            let mut contribution = signal * self.spectral_weights[x].abs();
+            if x == count - 1 {
+                contribution *= fade;
+            }
+            result += contribution;
+        }
+
+        // Without any ridges the output is flat, so any scale will do.
+        if result > 0.0 {
+            result
+        } else {
+            1.0
         }
     }
 
@@ -84,6 +223,42 @@ impl RidgedMulti {
             ..self
         }
     }
+
+    /// Sets the gain, the factor each octave's signal is multiplied by to
+    /// weight the octave that follows it.
+    ///
+    /// This is the parameterization used by Musgrave, and is the reciprocal
+    /// of the attenuation; setting it replaces the attenuation. Higher gains
+    /// let the ridges of the higher octaves build up along those of the lower
+    /// ones, while lower gains confine the detail to the tallest ridges.
+    pub fn set_gain(self, gain: f64) -> Self {
+        self.set_attenuation(1.0 / gain)
+    }
+
+    /// Returns the gain, which is the reciprocal of the attenuation.
+    pub fn gain(&self) -> f64 {
+        1.0 / self.attenuation
+    }
+
+    pub fn set_sharpness(self, sharpness: f64) -> Self {
+        Self { sharpness, ..self }
+    }
+
+    pub fn set_offset(self, offset: f64) -> Self {
+        Self { offset, ..self }
+    }
+
+    /// Raises a folded octave to the sharpness.
+    #[inline]
+    fn sharpen(&self, signal: f64) -> f64 {
+        // Squaring is exact, so the default sharpness does not depend on the
+        // precision of powf on the current platform.
+        if self.sharpness == 2.0 {
+            signal * signal
+        } else {
+            float::powf(signal, self.sharpness)
+        }
+    }
 }
 
 impl Default for RidgedMulti {
@@ -92,18 +267,25 @@ impl Default for RidgedMulti {
     }
 }
 
+/// The output is scaled by the largest value the octaves can accumulate.
+impl NoiseBounds for RidgedMulti {
+    const MIN: f64 = -1.0;
+    const MAX: f64 = 1.0;
+}
+
 impl MultiFractal for RidgedMulti {
     fn set_octaves(self, mut octaves: usize) -> Self {
         if self.octaves == octaves {
             return self;
         }
 
-        octaves = math::clamp(octaves, 1, Self::MAX_OCTAVES);
+        octaves = octaves.max(1);
         Self {
             octaves,
             sources: super::build_sources(self.seed, octaves),
             ..self
         }
+        .with_spectral_weights()
     }
 
     fn set_frequency(self, frequency: f64) -> Self {
@@ -111,7 +293,7 @@ impl MultiFractal for RidgedMulti {
     }
 
     fn set_lacunarity(self, lacunarity: f64) -> Self {
-        Self { lacunarity, ..self }
+        Self { lacunarity, ..self }.with_spectral_weights()
     }
 
     fn set_persistence(self, persistence: f64) -> Self {
@@ -119,9 +301,45 @@ impl MultiFractal for RidgedMulti {
             persistence,
             ..self
         }
+        .with_spectral_weights()
+    }
+
+    /// `RidgedMulti` output is always normalized, so this has no effect.
+    fn set_normalized(self, _normalized: bool) -> Self {
+        self
     }
 }
 
+impl MultiFractalMut for RidgedMulti {
+    fn update_octaves(&mut self, octaves: usize) {
+        let octaves = octaves.max(1);
+        if self.octaves == octaves {
+            return;
+        }
+
+        self.octaves = octaves;
+        self.sources = super::build_sources(self.seed, octaves);
+        self.update_spectral_weights();
+    }
+
+    fn update_frequency(&mut self, frequency: f64) {
+        self.frequency = frequency;
+    }
+
+    fn update_lacunarity(&mut self, lacunarity: f64) {
+        self.lacunarity = lacunarity;
+        self.update_spectral_weights();
+    }
+
+    fn update_persistence(&mut self, persistence: f64) {
+        self.persistence = persistence;
+        self.update_spectral_weights();
+    }
+
+    /// `RidgedMulti` output is always normalized, so this has no effect.
+    fn update_normalized(&mut self, _normalized: bool) {}
+}
+
 impl Seedable for RidgedMulti {
     fn set_seed(self, seed: u32) -> Self {
         if self.seed == seed {
@@ -142,22 +360,33 @@ impl Seedable for RidgedMulti {
 
 /// 2-dimensional `RidgedMulti` noise
 impl NoiseFn<[f64; 2]> for RidgedMulti {
-    fn get(&self, mut point: [f64; 2]) -> f64 {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.get_with_octaves(point, self.octaves)
+    }
+}
+
+impl OctaveNoiseFn<[f64; 2]> for RidgedMulti {
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn get_with_fractional_octaves(&self, mut point: [f64; 2], octaves: f64) -> f64 {
+        let (count, fade) = super::octave_count(octaves, self.octaves);
         let mut result = 0.0;
         let mut weight = 1.0;
 
         point = math::mul2(point, self.frequency);
 
-        for x in 0..self.octaves {
+        for x in 0..count {
             // Get the value.
             let mut signal = self.sources[x].get(point);
 
             // Make the ridges.
             signal = signal.abs();
-            signal = 1.0 - signal;
+            signal = (self.offset - signal).max(0.0);
 
-            // Square the signal to increase the sharpness of the ridges.
-            signal *= signal;
+            // Raise the signal to the sharpness to narrow the ridges.
+            signal = self.sharpen(signal);
 
             // Apply the weighting from the previous octave to the signal.
             // Larger values have higher weights, producing sharp points along
@@ -170,8 +399,13 @@ impl NoiseFn<[f64; 2]> for RidgedMulti {
             // Clamp the weight to [0,1] to prevent the result from diverging.
             weight = math::clamp(weight, 0.0, 1.0);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Scale the amplitude by the spectral weight of this octave.
+            signal *= self.spectral_weights[x];
+
+            // Fade in the last octave when evaluating a fractional count.
+            if x == count - 1 {
+                signal *= fade;
+            }
 
             // Add the signal to the result.
             result += signal;
@@ -181,29 +415,39 @@ impl NoiseFn<[f64; 2]> for RidgedMulti {
         }
 
         // Scale and shift the result into the [-1,1] range
-        let scale = 2.0 - 0.5_f64.powi(self.octaves as i32 - 1);
-        scale_shift(result, 2.0 / scale)
+        scale_shift(result, 2.0 / self.scale(count, fade))
     }
 }
 
 /// 3-dimensional `RidgedMulti` noise
 impl NoiseFn<[f64; 3]> for RidgedMulti {
-    fn get(&self, mut point: [f64; 3]) -> f64 {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.get_with_octaves(point, self.octaves)
+    }
+}
+
+impl OctaveNoiseFn<[f64; 3]> for RidgedMulti {
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn get_with_fractional_octaves(&self, mut point: [f64; 3], octaves: f64) -> f64 {
+        let (count, fade) = super::octave_count(octaves, self.octaves);
         let mut result = 0.0;
         let mut weight = 1.0;
 
         point = math::mul3(point, self.frequency);
 
-        for x in 0..self.octaves {
+        for x in 0..count {
             // Get the value.
             let mut signal = self.sources[x].get(point);
 
             // Make the ridges.
             signal = signal.abs();
-            signal = 1.0 - signal;
+            signal = (self.offset - signal).max(0.0);
 
-            // Square the signal to increase the sharpness of the ridges.
-            signal *= signal;
+            // Raise the signal to the sharpness to narrow the ridges.
+            signal = self.sharpen(signal);
 
             // Apply the weighting from the previous octave to the signal.
             // Larger values have higher weights, producing sharp points along
@@ -216,8 +460,13 @@ impl NoiseFn<[f64; 3]> for RidgedMulti {
             // Clamp the weight to [0,1] to prevent the result from diverging.
             weight = math::clamp(weight, 0.0, 1.0);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Scale the amplitude by the spectral weight of this octave.
+            signal *= self.spectral_weights[x];
+
+            // Fade in the last octave when evaluating a fractional count.
+            if x == count - 1 {
+                signal *= fade;
+            }
 
             // Add the signal to the result.
             result += signal;
@@ -227,29 +476,39 @@ impl NoiseFn<[f64; 3]> for RidgedMulti {
         }
 
         // Scale and shift the result into the [-1,1] range
-        let scale = 2.0 - 0.5_f64.powi(self.octaves as i32 - 1);
-        scale_shift(result, 2.0 / scale)
+        scale_shift(result, 2.0 / self.scale(count, fade))
     }
 }
 
 /// 4-dimensional `RidgedMulti` noise
 impl NoiseFn<[f64; 4]> for RidgedMulti {
-    fn get(&self, mut point: [f64; 4]) -> f64 {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.get_with_octaves(point, self.octaves)
+    }
+}
+
+impl OctaveNoiseFn<[f64; 4]> for RidgedMulti {
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn get_with_fractional_octaves(&self, mut point: [f64; 4], octaves: f64) -> f64 {
+        let (count, fade) = super::octave_count(octaves, self.octaves);
         let mut result = 0.0;
         let mut weight = 1.0;
 
         point = math::mul4(point, self.frequency);
 
-        for x in 0..self.octaves {
+        for x in 0..count {
             // Get the value.
             let mut signal = self.sources[x].get(point);
 
             // Make the ridges.
             signal = signal.abs();
-            signal = 1.0 - signal;
+            signal = (self.offset - signal).max(0.0);
 
-            // Square the signal to increase the sharpness of the ridges.
-            signal *= signal;
+            // Raise the signal to the sharpness to narrow the ridges.
+            signal = self.sharpen(signal);
 
             // Apply the weighting from the previous octave to the signal.
             // Larger values have higher weights, producing sharp points along
@@ -257,13 +516,18 @@ impl NoiseFn<[f64; 4]> for RidgedMulti {
             signal *= weight;
 
             // Weight successive contributions by the previous signal.
-            weight = signal * self.attenuation;
+            weight = signal / self.attenuation;
 
             // Clamp the weight to [0,1] to prevent the result from diverging.
             weight = math::clamp(weight, 0.0, 1.0);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Scale the amplitude by the spectral weight of this octave.
+            signal *= self.spectral_weights[x];
+
+            // Fade in the last octave when evaluating a fractional count.
+            if x == count - 1 {
+                signal *= fade;
+            }
 
             // Add the signal to the result.
             result += signal;
@@ -273,7 +537,6 @@ impl NoiseFn<[f64; 4]> for RidgedMulti {
         }
 
         // Scale and shift the result into the [-1,1] range
-        let scale = 2.0 - 0.5_f64.powi(self.octaves as i32 - 1);
-        scale_shift(result, 2.0 / scale)
+        scale_shift(result, 2.0 / self.scale(count, fade))
     }
 }
@@ -3,11 +3,12 @@
 //! <http://uniblock.tumblr.com/post/97868843242/noise>
 
 use crate::{
-    gradient, math,
-    noise_fns::{NoiseFn, Seedable},
+    gradient::{DefaultGradients, GradientTable},
+    math::{self, float},
+    noise_fns::{NoiseBounds, NoiseFn, Seedable},
     permutationtable::PermutationTable,
 };
-use std::ops::Add;
+use core::ops::Add;
 
 const STRETCH_CONSTANT_2D: f64 = -0.211_324_865_405_187; //(1/sqrt(2+1)-1)/2;
 const SQUISH_CONSTANT_2D: f64 = 0.366_025_403_784_439; //(sqrt(2+1)-1)/2;
@@ -22,18 +23,42 @@ const NORM_CONSTANT_4D: f64 = 1.0 / 6.869_909_007_095_662_5;
 
 /// Noise function that outputs 2/3/4-dimensional Open Simplex noise.
 #[derive(Clone, Copy, Debug)]
-pub struct OpenSimplex {
+pub struct OpenSimplex<G = DefaultGradients> {
+    /// The number of cycles per unit length that the noise function outputs.
+    /// Default is 1.0.
+    pub frequency: f64,
+
     seed: u32,
     perm_table: PermutationTable,
+    gradients: G,
 }
 
 impl OpenSimplex {
     const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
 
     pub fn new() -> Self {
         Self {
             seed: Self::DEFAULT_SEED,
             perm_table: PermutationTable::new(Self::DEFAULT_SEED),
+            frequency: Self::DEFAULT_FREQUENCY,
+            gradients: DefaultGradients,
+        }
+    }
+
+    /// Creates a noise function with the given frequency.
+    pub fn with_frequency(frequency: f64) -> Self {
+        Self::new().set_frequency(frequency)
+    }
+
+    /// Creates a noise function that takes its gradients from `gradients`
+    /// instead of the default set.
+    pub fn with_gradients<G: GradientTable>(gradients: G) -> OpenSimplex<G> {
+        OpenSimplex {
+            seed: Self::DEFAULT_SEED,
+            perm_table: PermutationTable::new(Self::DEFAULT_SEED),
+            frequency: Self::DEFAULT_FREQUENCY,
+            gradients,
         }
     }
 }
@@ -44,7 +69,19 @@ impl Default for OpenSimplex {
     }
 }
 
-impl Seedable for OpenSimplex {
+impl<G> OpenSimplex<G> {
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+}
+
+/// This only holds for the default gradients, which have unit length.
+impl NoiseBounds for OpenSimplex {
+    const MIN: f64 = -1.0;
+    const MAX: f64 = 1.0;
+}
+
+impl<G> Seedable for OpenSimplex<G> {
     /// Sets the seed value for Open Simplex noise
     fn set_seed(self, seed: u32) -> Self {
         // If the new seed is the same as the current seed, just return self.
@@ -56,6 +93,7 @@ impl Seedable for OpenSimplex {
         Self {
             seed,
             perm_table: PermutationTable::new(seed),
+            ..self
         }
     }
 
@@ -67,25 +105,31 @@ impl Seedable for OpenSimplex {
 /// 2-dimensional [`OpenSimplex` Noise](http://uniblock.tumblr.com/post/97868843242/noise)
 ///
 /// This is a slower but higher quality form of gradient noise than `Perlin` 2D.
-impl NoiseFn<[f64; 2]> for OpenSimplex {
+impl<G: GradientTable> NoiseFn<[f64; 2]> for OpenSimplex<G> {
     fn get(&self, point: [f64; 2]) -> f64 {
-        fn gradient(perm_table: &PermutationTable, vertex: [f64; 2], pos: [f64; 2]) -> f64 {
+        fn gradient<G: GradientTable>(
+            noise: &OpenSimplex<G>,
+            vertex: [f64; 2],
+            pos: [f64; 2],
+        ) -> f64 {
             let attn = 2.0 - math::dot2(pos, pos);
             if attn > 0.0 {
-                let index = perm_table.get2(math::to_isize2(vertex));
-                let vec = gradient::get2(index);
-                attn.powi(4) * math::dot2(pos, vec)
+                let index = noise.perm_table.get2(math::to_isize2(vertex));
+                let vec = noise.gradients.get2(index);
+                float::powi(attn, 4) * math::dot2(pos, vec)
             } else {
                 0.0
             }
         }
 
+        let point = math::mul2(point, self.frequency);
+
         // Place input coordinates onto grid.
         let stretch_offset = math::fold2(point, Add::add) * STRETCH_CONSTANT_2D;
         let stretched = math::map2(point, |v| v + stretch_offset);
 
         // Floor to get grid coordinates of rhombus (stretched square) cell origin.
-        let stretched_floor = math::map2(stretched, f64::floor);
+        let stretched_floor = math::map2(stretched, float::floor);
 
         // Skew out to get actual coordinates of rhombus origin. We'll need these later.
         let squish_offset = math::fold2(stretched_floor, Add::add) * SQUISH_CONSTANT_2D;
@@ -118,12 +162,12 @@ impl NoiseFn<[f64; 2]> for OpenSimplex {
         // Contribution (1, 0)
         vertex = math::add2(stretched_floor, [1.0, 0.0]);
         dpos = math::sub2(pos0, [t1, t0]);
-        value += gradient(&self.perm_table, vertex, dpos);
+        value += gradient(self, vertex, dpos);
 
         // Contribution (0, 1)
         vertex = math::add2(stretched_floor, [0.0, 1.0]);
         dpos = math::sub2(pos0, [t0, t1]);
-        value += gradient(&self.perm_table, vertex, dpos);
+        value += gradient(self, vertex, dpos);
 
         // See the graph for an intuitive explanation; the sum of `x` and `y` is
         // only greater than `1` if we're on Region B.
@@ -139,7 +183,7 @@ impl NoiseFn<[f64; 2]> for OpenSimplex {
         }
 
         // Point (0, 0) or (1, 1)
-        value += gradient(&self.perm_table, vertex, dpos);
+        value += gradient(self, vertex, dpos);
 
         value * NORM_CONSTANT_2D
     }
@@ -148,26 +192,32 @@ impl NoiseFn<[f64; 2]> for OpenSimplex {
 /// 3-dimensional [`OpenSimplex` Noise](http://uniblock.tumblr.com/post/97868843242/noise)
 ///
 /// This is a slower but higher quality form of gradient noise than `Perlin` 3D.
-impl NoiseFn<[f64; 3]> for OpenSimplex {
+impl<G: GradientTable> NoiseFn<[f64; 3]> for OpenSimplex<G> {
     fn get(&self, point: [f64; 3]) -> f64 {
-        fn gradient(perm_table: &PermutationTable, vertex: [f64; 3], pos: [f64; 3]) -> f64 {
+        fn gradient<G: GradientTable>(
+            noise: &OpenSimplex<G>,
+            vertex: [f64; 3],
+            pos: [f64; 3],
+        ) -> f64 {
             let attn = 2.0 - math::dot3(pos, pos);
             if attn > 0.0 {
-                let index = perm_table.get3(math::to_isize3(vertex));
-                let vec = gradient::get3(index);
-                attn.powi(4) * math::dot3(pos, vec)
+                let index = noise.perm_table.get3(math::to_isize3(vertex));
+                let vec = noise.gradients.get3(index);
+                float::powi(attn, 4) * math::dot3(pos, vec)
             } else {
                 0.0
             }
         }
 
+        let point = math::mul3(point, self.frequency);
+
         // Place input coordinates on simplectic h1.0ycomb.
         let stretch_offset = math::fold3(point, Add::add) * STRETCH_CONSTANT_3D;
         let stretched = math::map3(point, |v| v + stretch_offset);
 
         // Floor to get simplectic h1.0ycomb coordinates of rhombohedron
         // (stretched cube) super-cell origin.
-        let stretched_floor = math::map3(stretched, f64::floor);
+        let stretched_floor = math::map3(stretched, float::floor);
 
         // Skew out to get actual coordinates of rhombohedron origin. We'll need
         // these later.
@@ -196,22 +246,22 @@ impl NoiseFn<[f64; 3]> for OpenSimplex {
             // Contribution at (0, 0, 0)
             vertex = math::add3(stretched_floor, [0.0, 0.0, 0.0]);
             dpos = math::sub3(pos0, [0.0, 0.0, 0.0]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(self, vertex, dpos);
 
             // Contribution at (1, 0, 0)
             vertex = math::add3(stretched_floor, [1.0, 0.0, 0.0]);
             dpos = math::sub3(pos0, [t1, t0, t0]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(self, vertex, dpos);
 
             // Contribution at (0, 1, 0)
             vertex = math::add3(stretched_floor, [0.0, 1.0, 0.0]);
             dpos = math::sub3(pos0, [t0, t1, t0]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(self, vertex, dpos);
 
             // Contribution at (0, 0, 1)
             vertex = math::add3(stretched_floor, [0.0, 0.0, 1.0]);
             dpos = math::sub3(pos0, [t0, t0, t1]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(self, vertex, dpos);
         } else if region_sum >= 2.0 {
             // We're inside the tetrahedron (3-Simplex) at (1, 1, 1)
             let t0 = 2.0 * SQUISH_CONSTANT_3D;
@@ -221,22 +271,22 @@ impl NoiseFn<[f64; 3]> for OpenSimplex {
             // Contribution at (1, 1, 0)
             vertex = math::add3(stretched_floor, [1.0, 1.0, 0.0]);
             dpos = math::sub3(pos0, [t1, t1, t0]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(self, vertex, dpos);
 
             // Contribution at (1, 0, 1)
             vertex = math::add3(stretched_floor, [1.0, 0.0, 1.0]);
             dpos = math::sub3(pos0, [t1, t0, t1]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(self, vertex, dpos);
 
             // Contribution at (0, 1, 1)
             vertex = math::add3(stretched_floor, [0.0, 1.0, 1.0]);
             dpos = math::sub3(pos0, [t0, t1, t1]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(self, vertex, dpos);
 
             // Contribution at (1, 1, 1)
             vertex = math::add3(stretched_floor, [1.0, 1.0, 1.0]);
             dpos = math::sub3(pos0, [t2, t2, t2]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(self, vertex, dpos);
         } else {
             // We're inside the octahedron (Rectified 3-Simplex) inbetween.
             let t0 = SQUISH_CONSTANT_3D;
@@ -247,32 +297,32 @@ impl NoiseFn<[f64; 3]> for OpenSimplex {
             // Contribution at (1, 0, 0)
             vertex = math::add3(stretched_floor, [1.0, 0.0, 0.0]);
             dpos = math::sub3(pos0, [t1, t0, t0]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(self, vertex, dpos);
 
             // Contribution at (0, 1, 0)
             vertex = math::add3(stretched_floor, [0.0, 1.0, 0.0]);
             dpos = math::sub3(pos0, [t0, t1, t0]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(self, vertex, dpos);
 
             // Contribution at (0, 0, 1)
             vertex = math::add3(stretched_floor, [0.0, 0.0, 1.0]);
             dpos = math::sub3(pos0, [t0, t0, t1]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(self, vertex, dpos);
 
             // Contribution at (1, 1, 0)
             vertex = math::add3(stretched_floor, [1.0, 1.0, 0.0]);
             dpos = math::sub3(pos0, [t3, t3, t2]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(self, vertex, dpos);
 
             // Contribution at (1, 0, 1)
             vertex = math::add3(stretched_floor, [1.0, 0.0, 1.0]);
             dpos = math::sub3(pos0, [t3, t2, t3]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(self, vertex, dpos);
 
             // Contribution at (0, 1, 1)
             vertex = math::add3(stretched_floor, [0.0, 1.0, 1.0]);
             dpos = math::sub3(pos0, [t2, t3, t3]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(self, vertex, dpos);
         }
 
         value * NORM_CONSTANT_3D
@@ -282,27 +332,33 @@ impl NoiseFn<[f64; 3]> for OpenSimplex {
 /// 4-dimensional [`OpenSimplex` Noise](http://uniblock.tumblr.com/post/97868843242/noise)
 ///
 /// This is a slower but higher quality form of gradient noise than `Perlin` 4D.
-impl NoiseFn<[f64; 4]> for OpenSimplex {
+impl<G: GradientTable> NoiseFn<[f64; 4]> for OpenSimplex<G> {
     fn get(&self, point: [f64; 4]) -> f64 {
         #[inline(always)]
-        fn gradient(perm_table: &PermutationTable, vertex: [f64; 4], pos: [f64; 4]) -> f64 {
+        fn gradient<G: GradientTable>(
+            noise: &OpenSimplex<G>,
+            vertex: [f64; 4],
+            pos: [f64; 4],
+        ) -> f64 {
             let attn = 2.0 - math::dot4(pos, pos);
             if attn > 0.0 {
-                let index = perm_table.get4(math::to_isize4(vertex));
-                let vec = gradient::get4(index);
-                attn.powi(4) * math::dot4(pos, vec)
+                let index = noise.perm_table.get4(math::to_isize4(vertex));
+                let vec = noise.gradients.get4(index);
+                float::powi(attn, 4) * math::dot4(pos, vec)
             } else {
                 0.0
             }
         }
 
+        let point = math::mul4(point, self.frequency);
+
         // Place input coordinates on simplectic h1.0ycomb.
         let stretch_offset = math::fold4(point, Add::add) * STRETCH_CONSTANT_4D;
         let stretched = math::map4(point, |v| v + stretch_offset);
 
         // Floor to get simplectic h1.0ycomb coordinates of rhombo-hypercube
         // super-cell origin.
-        let stretched_floor = math::map4(stretched, f64::floor);
+        let stretched_floor = math::map4(stretched, float::floor);
 
         // Skew out to get actual coordinates of stretched rhombo-hypercube origin.
         // We'll need these later.
@@ -325,7 +381,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             // We're inside the pentachoron (4-Simplex) at (0, 0, 0, 0)
 
             // Contribution at (0, 0, 0, 0)
-            value += gradient(&self.perm_table, stretched_floor, pos0);
+            value += gradient(self, stretched_floor, pos0);
 
             // Contribution at (1, 0, 0, 0)
             let pos1;
@@ -340,7 +396,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
                         SQUISH_CONSTANT_4D,
                     ],
                 );
-                value += gradient(&self.perm_table, vertex, pos1);
+                value += gradient(self, vertex, pos1);
             }
 
             // Contribution at (0, 1, 0, 0)
@@ -348,7 +404,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 1.0, 0.0, 0.0]);
                 pos2 = [pos1[0] + 1.0, pos1[1] - 1.0, pos1[2], pos1[3]];
-                value += gradient(&self.perm_table, vertex, pos2);
+                value += gradient(self, vertex, pos2);
             }
 
             // Contribution at (0, 0, 1, 0)
@@ -356,7 +412,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 0.0, 1.0, 0.0]);
                 pos3 = [pos2[0], pos1[1], pos1[2] - 1.0, pos1[3]];
-                value += gradient(&self.perm_table, vertex, pos3);
+                value += gradient(self, vertex, pos3);
             }
 
             // Contribution at (0, 0, 0, 1)
@@ -364,7 +420,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 0.0, 0.0, 1.0]);
                 pos4 = [pos2[0], pos1[1], pos1[2], pos1[3] - 1.0];
-                value += gradient(&self.perm_table, vertex, pos4);
+                value += gradient(self, vertex, pos4);
             }
         } else if region_sum >= 3.0 {
             // We're inside the pentachoron (4-Simplex) at (1, 1, 1, 1)
@@ -383,7 +439,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
                         squish_constant_3,
                     ],
                 );
-                value += gradient(&self.perm_table, vertex, pos4);
+                value += gradient(self, vertex, pos4);
             }
 
             // Contribution at (1, 1, 0, 1)
@@ -391,7 +447,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [1.0, 1.0, 0.0, 1.0]);
                 pos3 = [pos4[0], pos4[1], pos4[2] + 1.0, pos4[3] - 1.0];
-                value += gradient(&self.perm_table, vertex, pos3);
+                value += gradient(self, vertex, pos3);
             }
 
             // Contribution at (1, 0, 1, 1)
@@ -399,7 +455,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [1.0, 0.0, 1.0, 1.0]);
                 pos2 = [pos4[0], pos4[1] + 1.0, pos4[2], pos3[3]];
-                value += gradient(&self.perm_table, vertex, pos2);
+                value += gradient(self, vertex, pos2);
             }
 
             // Contribution at (0, 1, 1, 1)
@@ -407,7 +463,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 1.0, 1.0, 1.0]);
                 pos1 = [pos0[0] - squish_constant_3, pos4[1], pos4[2], pos3[3]];
-                value += gradient(&self.perm_table, vertex, pos1);
+                value += gradient(self, vertex, pos1);
             }
 
             // Contribution at (1, 1, 1, 1)
@@ -417,7 +473,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
                 pos0[1] = pos4[1] - SQUISH_CONSTANT_4D;
                 pos0[2] = pos4[2] - SQUISH_CONSTANT_4D;
                 pos0[3] = pos3[3] - SQUISH_CONSTANT_4D;
-                value += gradient(&self.perm_table, vertex, pos0);
+                value += gradient(self, vertex, pos0);
             }
         } else if region_sum <= 2.0 {
             // We're inside the first dispentachoron (Rectified 4-Simplex)
@@ -435,7 +491,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
                         SQUISH_CONSTANT_4D,
                     ],
                 );
-                value += gradient(&self.perm_table, vertex, pos1);
+                value += gradient(self, vertex, pos1);
             }
 
             // Contribution at (0, 1, 0, 0)
@@ -443,7 +499,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 1.0, 0.0, 0.0]);
                 pos2 = [pos1[0] + 1.0, pos1[1] - 1.0, pos1[2], pos1[3]];
-                value += gradient(&self.perm_table, vertex, pos2);
+                value += gradient(self, vertex, pos2);
             }
 
             // Contribution at (0, 0, 1, 0)
@@ -451,7 +507,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 0.0, 1.0, 0.0]);
                 pos3 = [pos2[0], pos1[1], pos1[2] - 1.0, pos1[3]];
-                value += gradient(&self.perm_table, vertex, pos3);
+                value += gradient(self, vertex, pos3);
             }
 
             // Contribution at (0, 0, 0, 1)
@@ -459,7 +515,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 0.0, 0.0, 1.0]);
                 pos4 = [pos2[0], pos1[1], pos1[2], pos1[3] - 1.0];
-                value += gradient(&self.perm_table, vertex, pos4);
+                value += gradient(self, vertex, pos4);
             }
 
             // Contribution at (1, 1, 0, 0)
@@ -472,7 +528,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
                     pos1[2] - SQUISH_CONSTANT_4D,
                     pos1[3] - SQUISH_CONSTANT_4D,
                 ];
-                value += gradient(&self.perm_table, vertex, pos5);
+                value += gradient(self, vertex, pos5);
             }
 
             // Contribution at (1, 0, 1, 0)
@@ -480,7 +536,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [1.0, 0.0, 1.0, 0.0]);
                 pos6 = [pos5[0], pos5[1] + 1.0, pos5[2] - 1.0, pos5[3]];
-                value += gradient(&self.perm_table, vertex, pos6);
+                value += gradient(self, vertex, pos6);
             }
 
             // Contribution at (1, 0, 0, 1)
@@ -488,7 +544,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [1.0, 0.0, 0.0, 1.0]);
                 pos7 = [pos5[0], pos6[1], pos5[2], pos5[3] - 1.0];
-                value += gradient(&self.perm_table, vertex, pos7);
+                value += gradient(self, vertex, pos7);
             }
 
             // Contribution at (0, 1, 1, 0)
@@ -496,7 +552,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 1.0, 1.0, 0.0]);
                 pos8 = [pos5[0] + 1.0, pos5[1], pos6[2], pos5[3]];
-                value += gradient(&self.perm_table, vertex, pos8);
+                value += gradient(self, vertex, pos8);
             }
 
             // Contribution at (0, 1, 0, 1)
@@ -504,7 +560,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 1.0, 0.0, 1.0]);
                 pos9 = [pos8[0], pos5[1], pos5[2], pos7[3]];
-                value += gradient(&self.perm_table, vertex, pos9);
+                value += gradient(self, vertex, pos9);
             }
 
             // Contribution at (0, 0, 1, 1)
@@ -512,7 +568,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 0.0, 1.0, 1.0]);
                 pos10 = [pos8[0], pos6[1], pos6[2], pos7[3]];
-                value += gradient(&self.perm_table, vertex, pos10);
+                value += gradient(self, vertex, pos10);
             }
         } else {
             // We're inside the second dispentachoron (Rectified 4-Simplex)
@@ -531,7 +587,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
                         squish_constant_3,
                     ],
                 );
-                value += gradient(&self.perm_table, vertex, pos4);
+                value += gradient(self, vertex, pos4);
             }
 
             // Contribution at (1, 1, 0, 1)
@@ -539,7 +595,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [1.0, 1.0, 0.0, 1.0]);
                 pos3 = [pos4[0], pos4[1], pos4[2] + 1.0, pos4[3] - 1.0];
-                value += gradient(&self.perm_table, vertex, pos3);
+                value += gradient(self, vertex, pos3);
             }
 
             // Contribution at (1, 0, 1, 1)
@@ -547,7 +603,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [1.0, 0.0, 1.0, 1.0]);
                 pos2 = [pos4[0], pos4[1] + 1.0, pos4[2], pos3[3]];
-                value += gradient(&self.perm_table, vertex, pos2);
+                value += gradient(self, vertex, pos2);
             }
 
             // Contribution at (0, 1, 1, 1)
@@ -555,7 +611,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 1.0, 1.0, 1.0]);
                 pos1 = [pos4[0] + 1.0, pos4[1], pos4[2], pos3[3]];
-                value += gradient(&self.perm_table, vertex, pos1);
+                value += gradient(self, vertex, pos1);
             }
 
             // Contribution at (1, 1, 0, 0)
@@ -568,7 +624,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
                     pos3[2] + SQUISH_CONSTANT_4D,
                     pos4[3] + SQUISH_CONSTANT_4D,
                 ];
-                value += gradient(&self.perm_table, vertex, pos5);
+                value += gradient(self, vertex, pos5);
             }
 
             // Contribution at (1, 0, 1, 0)
@@ -576,7 +632,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [1.0, 0.0, 1.0, 0.0]);
                 pos6 = [pos5[0], pos5[1] + 1.0, pos5[2] - 1.0, pos5[3]];
-                value += gradient(&self.perm_table, vertex, pos6);
+                value += gradient(self, vertex, pos6);
             }
 
             // Contribution at (1, 0, 0, 1)
@@ -584,7 +640,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [1.0, 0.0, 0.0, 1.0]);
                 pos7 = [pos5[0], pos6[1], pos5[2], pos5[3] - 1.0];
-                value += gradient(&self.perm_table, vertex, pos7);
+                value += gradient(self, vertex, pos7);
             }
 
             // Contribution at (0, 1, 1, 0)
@@ -592,7 +648,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 1.0, 1.0, 0.0]);
                 pos8 = [pos5[0] + 1.0, pos5[1], pos6[2], pos5[3]];
-                value += gradient(&self.perm_table, vertex, pos8);
+                value += gradient(self, vertex, pos8);
             }
 
             // Contribution at (0, 1, 0, 1)
@@ -600,7 +656,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 1.0, 0.0, 1.0]);
                 pos9 = [pos8[0], pos5[1], pos5[2], pos7[3]];
-                value += gradient(&self.perm_table, vertex, pos9);
+                value += gradient(self, vertex, pos9);
             }
 
             // Contribution at (0, 0, 1, 1)
@@ -608,10 +664,21 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 0.0, 1.0, 1.0]);
                 pos10 = [pos8[0], pos6[1], pos6[2], pos7[3]];
-                value += gradient(&self.perm_table, vertex, pos10);
+                value += gradient(self, vertex, pos10);
             }
         }
 
         value * NORM_CONSTANT_4D
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::assert_doubles_feature_count;
+
+    #[test]
+    fn doubling_frequency_doubles_feature_count() {
+        assert_doubles_feature_count(&OpenSimplex::new(), &OpenSimplex::with_frequency(2.0));
+    }
+}
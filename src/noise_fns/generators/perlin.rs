@@ -1,34 +1,68 @@
 use crate::{
-    math::{self, interpolate},
-    noise_fns::{NoiseFn, Seedable},
+    math::{self, float, interpolate},
+    noise_fns::{NoiseBounds, NoiseFn, Seedable},
     permutationtable::PermutationTable,
 };
 
 /// Noise function that outputs 2/3/4-dimensional Perlin noise.
+///
+/// The permutation table holds 256 entries by default, so the output repeats
+/// every 256 units along each axis. Use `Perlin::with_table_size` for a
+/// different period.
 #[derive(Clone, Copy, Debug)]
-pub struct Perlin {
+pub struct Perlin<const N: usize = 256> {
+    /// The number of cycles per unit length that the noise function outputs.
+    /// Default is 1.0.
+    pub frequency: f64,
+
     seed: u32,
-    perm_table: PermutationTable,
+    perm_table: PermutationTable<N>,
 }
 
 impl Perlin {
     pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
 
     pub fn new() -> Self {
-        Self {
+        Self::with_table_size()
+    }
+
+    /// Creates a noise function with the given frequency.
+    pub fn with_frequency(frequency: f64) -> Self {
+        Self::new().set_frequency(frequency)
+    }
+
+    /// Creates a noise function whose permutation table holds `N` entries,
+    /// so that its output repeats every `N` units along each axis at a
+    /// frequency of 1.0. `N` must be a power of two between 64 and 65536.
+    pub fn with_table_size<const N: usize>() -> Perlin<N> {
+        Perlin {
             seed: Self::DEFAULT_SEED,
             perm_table: PermutationTable::new(Self::DEFAULT_SEED),
+            frequency: Self::DEFAULT_FREQUENCY,
         }
     }
 }
 
+impl<const N: usize> Perlin<N> {
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+}
+
 impl Default for Perlin {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl Seedable for Perlin {
+/// The output is clamped to [-1,1].
+impl<const N: usize> NoiseBounds for Perlin<N> {
+    const MIN: f64 = -1.0;
+    const MAX: f64 = 1.0;
+}
+
+impl<const N: usize> Seedable for Perlin<N> {
     /// Sets the seed value for Perlin noise
     fn set_seed(self, seed: u32) -> Self {
         // If the new seed is the same as the current seed, just return self.
@@ -40,6 +74,7 @@ impl Seedable for Perlin {
         Self {
             seed,
             perm_table: PermutationTable::new(seed),
+            ..self
         }
     }
 
@@ -49,14 +84,15 @@ impl Seedable for Perlin {
 }
 
 /// 2-dimensional perlin noise
-impl NoiseFn<[f64; 2]> for Perlin {
+impl<const N: usize> NoiseFn<[f64; 2]> for Perlin<N> {
     fn get(&self, point: [f64; 2]) -> f64 {
-        perlin_2d(self.perm_table, point[0], point[1])
+        let point = math::mul2(point, self.frequency);
+        perlin_2d(&self.perm_table, point[0], point[1])
     }
 }
 
 #[inline(always)]
-pub(crate) fn perlin_2d(perm_table: PermutationTable, x: f64, y: f64) -> f64 {
+pub(crate) fn perlin_2d<const N: usize>(perm_table: &PermutationTable<N>, x: f64, y: f64) -> f64 {
     #[inline(always)]
     #[rustfmt::skip]
     fn gradient_dot_v(perm: usize, x: f64, y: f64) -> f64 {
@@ -74,11 +110,11 @@ pub(crate) fn perlin_2d(perm_table: PermutationTable, x: f64, y: f64) -> f64 {
     // Unscaled range of linearly interpolated perlin noise should be (-sqrt(N/4), sqrt(N/4)).
     // Need to invert this value and multiply the unscaled result by the value to get a scaled
     // range of (-1, 1).
-    let scale_factor = (2.0_f64).sqrt(); // 1/sqrt(N/4), N=2 -> 1/sqrt(1/2) -> sqrt(2)
+    let scale_factor = float::sqrt(2.0); // 1/sqrt(N/4), N=2 -> 1/sqrt(1/2) -> sqrt(2)
 
-    let floored = math::map2(point, f64::floor);
+    let floored = math::map2(point, float::floor);
     let near_corner = math::to_isize2(floored);
-    let far_corner = math::add2(near_corner, [1; 2]);
+    let far_corner = math::wrapping_add2(near_corner, [1; 2]);
     let near_distance = math::sub2(point, floored);
     let far_distance = math::sub2(near_distance, [1.0; 2]);
 
@@ -128,15 +164,21 @@ fn bilinear_interpolation(u: f64, v: f64, g00: f64, g01: f64, g10: f64, g11: f64
 }
 
 /// 3-dimensional perlin noise
-impl NoiseFn<[f64; 3]> for Perlin {
+impl<const N: usize> NoiseFn<[f64; 3]> for Perlin<N> {
     fn get(&self, point: [f64; 3]) -> f64 {
-        perlin_3d(self.perm_table, point[0], point[1], point[2])
+        let point = math::mul3(point, self.frequency);
+        perlin_3d(&self.perm_table, point[0], point[1], point[2])
     }
 }
 
 #[inline(always)]
 #[allow(clippy::many_single_char_names)]
-pub(crate) fn perlin_3d(perm_table: PermutationTable, x: f64, y: f64, z: f64) -> f64 {
+pub(crate) fn perlin_3d<const N: usize>(
+    perm_table: &PermutationTable<N>,
+    x: f64,
+    y: f64,
+    z: f64,
+) -> f64 {
     #[inline(always)]
     #[rustfmt::skip]
     fn gradient_dot_v(perm: usize, point: [f64; 3]) -> f64 {
@@ -170,11 +212,11 @@ pub(crate) fn perlin_3d(perm_table: PermutationTable, x: f64, y: f64, z: f64) ->
     // Unscaled range of linearly interpolated perlin noise should be (-sqrt(N/4), sqrt(N/4)).
     // Need to invert this value and multiply the unscaled result by the value to get a scaled
     // range of (-1, 1).
-    let scale_factor = 2.0_f64 / ((3.0_f64).sqrt()); // 1/sqrt(N/4), N=3 -> 1/sqrt(3/4) -> 2/sqrt(3)
+    let scale_factor = 2.0 / float::sqrt(3.0); // 1/sqrt(N/4), N=3 -> 1/sqrt(3/4) -> 2/sqrt(3)
 
-    let floored = math::map3(point, f64::floor);
+    let floored = math::map3(point, float::floor);
     let near_corner = math::to_isize3(floored);
-    let far_corner = math::add3(near_corner, [1; 3]);
+    let far_corner = math::wrapping_add3(near_corner, [1; 3]);
     let near_distance = math::sub3(point, floored);
     let far_distance = math::sub3(near_distance, [1.0; 3]);
 
@@ -231,16 +273,17 @@ pub(crate) fn perlin_3d(perm_table: PermutationTable, x: f64, y: f64, z: f64) ->
 }
 
 /// 4-dimensional perlin noise
-impl NoiseFn<[f64; 4]> for Perlin {
+impl<const N: usize> NoiseFn<[f64; 4]> for Perlin<N> {
     fn get(&self, point: [f64; 4]) -> f64 {
-        perlin_4d(self.perm_table, point[0], point[1], point[2], point[3])
+        let point = math::mul4(point, self.frequency);
+        perlin_4d(&self.perm_table, point[0], point[1], point[2], point[3])
     }
 }
 
 #[inline(always)]
 #[rustfmt::skip]
 #[allow(clippy::many_single_char_names)]
-pub(crate) fn perlin_4d(perm_table: PermutationTable, x: f64, y: f64, z: f64, w: f64) -> f64 {
+pub(crate) fn perlin_4d<const N: usize>(perm_table: &PermutationTable<N>, x: f64, y: f64, z: f64, w: f64) -> f64 {
     #[inline(always)]
     fn gradient_dot_v(perm: usize, x: f64, y: f64, z: f64, w: f64) -> f64 {
         match perm & 0b11111 {
@@ -287,9 +330,9 @@ pub(crate) fn perlin_4d(perm_table: PermutationTable, x: f64, y: f64, z: f64, w:
     // range of (-1, 1).
     let scale_factor = 2.0; // 1/sqrt(N/4), N=4 -> 1/sqrt(4/4) -> 2/sqrt(1)
 
-    let floored = math::map4(point, f64::floor);
+    let floored = math::map4(point, float::floor);
     let near_corner = math::to_isize4(floored);
-    let far_corner = math::add4(near_corner, [1; 4]);
+    let far_corner = math::wrapping_add4(near_corner, [1; 4]);
     let near_distance = math::sub4(point, floored);
     let far_distance = math::sub4(near_distance, [1.0; 4]);
 
@@ -492,11 +535,19 @@ pub(crate) fn perlin_4d(perm_table: PermutationTable, x: f64, y: f64, z: f64, w:
     let k8 = g0000 + g0110 - g0100 - g0010;
     let k9 = g0000 + g0101 - g0100 - g0001;
     let k10 = g0000 + g0011 - g0010 - g0001;
-    let k11 = g1110 + g1000 + g0100 + g0010 - g0000 - g0111 - g1011 - g1101;
-    let k12 = g1101 + g1000 + g0100 + g0001 - g0000 - g0111 - g1011 - g1110;
-    let k13 = g1011 + g1000 + g0010 + g0001 - g0000 - g0111 - g1101 - g1110;
-    let k14 = g0111 + g0100 + g0010 + g0001 - g0000 - g1011 - g1101 - g1110;
-    let k15 = g1111 + g1000 + g0100 + g0010 + g0001 - g0000 - g0111 - g1011 - g1101 - g1110;
+    let k11 = g1110 + g1000 + g0100 + g0010 - g0000 - g1100 - g1010 - g0110;
+    let k12 = g1101 + g1000 + g0100 + g0001 - g0000 - g1100 - g1001 - g0101;
+    let k13 = g1011 + g1000 + g0010 + g0001 - g0000 - g1010 - g1001 - g0011;
+    let k14 = g0111 + g0100 + g0010 + g0001 - g0000 - g0110 - g0101 - g0011;
+    let k15 = g1111 + g0000 + g1100 + g1010 + g1001 + g0110 + g0101 + g0011
+        - g1000
+        - g0100
+        - g0010
+        - g0001
+        - g1110
+        - g1101
+        - g1011
+        - g0111;
 
     let unscaled_result = k0
         + k1 * a
@@ -524,3 +575,65 @@ pub(crate) fn perlin_4d(perm_table: PermutationTable, x: f64, y: f64, z: f64, w:
 
     math::clamp(scaled_result, -1.0, 1.0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::assert_doubles_feature_count;
+
+    #[test]
+    fn perlin_4d_is_continuous_across_cell_boundaries() {
+        const STEP: f64 = 1e-9;
+        let perlin = Perlin::new();
+
+        for i in 0..64 {
+            let point = [
+                i as f64 * 0.37 + 0.1,
+                i as f64 * -0.53 + 0.2,
+                i as f64 * 0.71 + 0.3,
+                i as f64 * -0.29 + 0.4,
+            ];
+
+            for axis in 0..4 {
+                let boundary = point[axis].round();
+                let mut below = point;
+                let mut above = point;
+                below[axis] = boundary - STEP;
+                above[axis] = boundary + STEP;
+
+                let jump = (perlin.get(below) - perlin.get(above)).abs();
+                assert!(
+                    jump < 1e-6,
+                    "jump of {} crossing axis {} at {:?}",
+                    jump,
+                    axis,
+                    above
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn doubling_frequency_doubles_feature_count() {
+        assert_doubles_feature_count(&Perlin::new(), &Perlin::with_frequency(2.0));
+    }
+
+    #[test]
+    fn output_repeats_at_table_size() {
+        let perlin = Perlin::with_table_size::<64>().set_seed(4);
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.37 - 9.0, i as f64 * -0.23, 0.61];
+            let value = perlin.get(point);
+
+            assert!((perlin.get([point[0] + 64.0, point[1], point[2]]) - value).abs() < 1e-9);
+            assert!((perlin.get([point[0], point[1] - 128.0, point[2]]) - value).abs() < 1e-9);
+        }
+
+        // The default table repeats every 256 units instead.
+        let perlin = Perlin::new();
+        let point = [0.3, 0.7];
+        assert!((perlin.get([point[0] + 256.0, point[1]]) - perlin.get(point)).abs() < 1e-9);
+        assert_ne!(perlin.get([point[0] + 64.0, point[1]]), perlin.get(point));
+    }
+}
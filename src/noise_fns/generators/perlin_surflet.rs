@@ -1,34 +1,73 @@
 use crate::{
-    gradient, math,
-    noise_fns::{NoiseFn, Seedable},
+    gradient::{DefaultGradients, GradientTable},
+    math::{self, float},
+    noise_fns::{NoiseBounds, NoiseFn, Seedable},
     permutationtable::PermutationTable,
 };
 
-/// Noise function that outputs 2/3/4-dimensional Perlin noise.
+/// Noise function that outputs 2/3/4-dimensional Perlin noise built from
+/// radially attenuated surflets rather than interpolated gradients.
 #[derive(Clone, Copy, Debug)]
-pub struct Perlin {
+pub struct PerlinSurflet<G = DefaultGradients> {
+    /// The number of cycles per unit length that the noise function outputs.
+    /// Default is 1.0.
+    pub frequency: f64,
+
     seed: u32,
     perm_table: PermutationTable,
+    gradients: G,
 }
 
-impl Perlin {
+impl PerlinSurflet {
     pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
 
     pub fn new() -> Self {
         Self {
             seed: Self::DEFAULT_SEED,
             perm_table: PermutationTable::new(Self::DEFAULT_SEED),
+            frequency: Self::DEFAULT_FREQUENCY,
+            gradients: DefaultGradients,
+        }
+    }
+
+    /// Creates a noise function with the given frequency.
+    pub fn with_frequency(frequency: f64) -> Self {
+        Self::new().set_frequency(frequency)
+    }
+
+    /// Creates a noise function that takes its gradients from `gradients`
+    /// instead of the default set.
+    pub fn with_gradients<G: GradientTable>(gradients: G) -> PerlinSurflet<G> {
+        PerlinSurflet {
+            seed: Self::DEFAULT_SEED,
+            perm_table: PermutationTable::new(Self::DEFAULT_SEED),
+            frequency: Self::DEFAULT_FREQUENCY,
+            gradients,
         }
     }
 }
 
-impl Default for Perlin {
+impl Default for PerlinSurflet {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl Seedable for Perlin {
+impl<G> PerlinSurflet<G> {
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+}
+
+/// The output is clamped to [-1,1]. This only holds for the default
+/// gradients, which have unit length.
+impl NoiseBounds for PerlinSurflet {
+    const MIN: f64 = -1.0;
+    const MAX: f64 = 1.0;
+}
+
+impl<G> Seedable for PerlinSurflet<G> {
     /// Sets the seed value for Perlin noise
     fn set_seed(self, seed: u32) -> Self {
         // If the new seed is the same as the current seed, just return self.
@@ -40,6 +79,7 @@ impl Seedable for Perlin {
         Self {
             seed,
             perm_table: PermutationTable::new(seed),
+            ..self
         }
     }
 
@@ -49,43 +89,52 @@ impl Seedable for Perlin {
 }
 
 /// 2-dimensional perlin noise
-impl NoiseFn<[f64; 2]> for Perlin {
+impl<G: GradientTable> NoiseFn<[f64; 2]> for PerlinSurflet<G> {
     fn get(&self, point: [f64; 2]) -> f64 {
+        let point = math::mul2(point, self.frequency);
         const SCALE_FACTOR: f64 = 3.160_493_827_160_493_7;
 
         #[inline(always)]
-        fn surflet(perm_table: &PermutationTable, corner: [isize; 2], distance: [f64; 2]) -> f64 {
+        fn surflet<G: GradientTable>(
+            noise: &PerlinSurflet<G>,
+            corner: [isize; 2],
+            distance: [f64; 2],
+        ) -> f64 {
             let attn = 1.0 - math::dot2(distance, distance);
             if attn > 0.0 {
-                attn.powi(4) * math::dot2(distance, gradient::get2(perm_table.get2(corner)))
+                float::powi(attn, 4)
+                    * math::dot2(
+                        distance,
+                        noise.gradients.get2(noise.perm_table.get2(corner)),
+                    )
             } else {
                 0.0
             }
         }
 
-        let floored = math::map2(point, f64::floor);
+        let floored = math::map2(point, float::floor);
         let near_corner = math::to_isize2(floored);
-        let far_corner = math::add2(near_corner, math::one2());
+        let far_corner = math::wrapping_add2(near_corner, math::one2());
         let near_distance = math::sub2(point, floored);
         let far_distance = math::sub2(near_distance, math::one2());
 
         let f00 = surflet(
-            &self.perm_table,
+            self,
             [near_corner[0], near_corner[1]],
             [near_distance[0], near_distance[1]],
         );
         let f10 = surflet(
-            &self.perm_table,
+            self,
             [far_corner[0], near_corner[1]],
             [far_distance[0], near_distance[1]],
         );
         let f01 = surflet(
-            &self.perm_table,
+            self,
             [near_corner[0], far_corner[1]],
             [near_distance[0], far_distance[1]],
         );
         let f11 = surflet(
-            &self.perm_table,
+            self,
             [far_corner[0], far_corner[1]],
             [far_distance[0], far_distance[1]],
         );
@@ -96,63 +145,72 @@ impl NoiseFn<[f64; 2]> for Perlin {
 }
 
 /// 3-dimensional perlin noise
-impl NoiseFn<[f64; 3]> for Perlin {
+impl<G: GradientTable> NoiseFn<[f64; 3]> for PerlinSurflet<G> {
     fn get(&self, point: [f64; 3]) -> f64 {
+        let point = math::mul3(point, self.frequency);
         const SCALE_FACTOR: f64 = 3.889_855_325_553_107_4;
 
         #[inline(always)]
-        fn surflet(perm_table: &PermutationTable, corner: [isize; 3], distance: [f64; 3]) -> f64 {
+        fn surflet<G: GradientTable>(
+            noise: &PerlinSurflet<G>,
+            corner: [isize; 3],
+            distance: [f64; 3],
+        ) -> f64 {
             let attn = 1.0 - math::dot3(distance, distance);
             if attn > 0.0 {
-                attn.powi(4) * math::dot3(distance, gradient::get3(perm_table.get3(corner)))
+                float::powi(attn, 4)
+                    * math::dot3(
+                        distance,
+                        noise.gradients.get3(noise.perm_table.get3(corner)),
+                    )
             } else {
                 0.0
             }
         }
 
-        let floored = math::map3(point, f64::floor);
+        let floored = math::map3(point, float::floor);
         let near_corner = math::to_isize3(floored);
-        let far_corner = math::add3(near_corner, math::one3());
+        let far_corner = math::wrapping_add3(near_corner, math::one3());
         let near_distance = math::sub3(point, floored);
         let far_distance = math::sub3(near_distance, math::one3());
 
         let f000 = surflet(
-            &self.perm_table,
+            self,
             [near_corner[0], near_corner[1], near_corner[2]],
             [near_distance[0], near_distance[1], near_distance[2]],
         );
         let f100 = surflet(
-            &self.perm_table,
+            self,
             [far_corner[0], near_corner[1], near_corner[2]],
             [far_distance[0], near_distance[1], near_distance[2]],
         );
         let f010 = surflet(
-            &self.perm_table,
+            self,
             [near_corner[0], far_corner[1], near_corner[2]],
             [near_distance[0], far_distance[1], near_distance[2]],
         );
         let f110 = surflet(
-            &self.perm_table,
+            self,
             [far_corner[0], far_corner[1], near_corner[2]],
             [far_distance[0], far_distance[1], near_distance[2]],
         );
         let f001 = surflet(
-            &self.perm_table,
+            self,
             [near_corner[0], near_corner[1], far_corner[2]],
             [near_distance[0], near_distance[1], far_distance[2]],
         );
         let f101 = surflet(
-            &self.perm_table,
+            self,
             [far_corner[0], near_corner[1], far_corner[2]],
             [far_distance[0], near_distance[1], far_distance[2]],
         );
         let f011 = surflet(
-            &self.perm_table,
+            self,
             [near_corner[0], far_corner[1], far_corner[2]],
             [near_distance[0], far_distance[1], far_distance[2]],
         );
         let f111 = surflet(
-            &self.perm_table,
+            self,
             [far_corner[0], far_corner[1], far_corner[2]],
             [far_distance[0], far_distance[1], far_distance[2]],
         );
@@ -167,28 +225,37 @@ impl NoiseFn<[f64; 3]> for Perlin {
 }
 
 /// 4-dimensional perlin noise
-impl NoiseFn<[f64; 4]> for Perlin {
+impl<G: GradientTable> NoiseFn<[f64; 4]> for PerlinSurflet<G> {
     fn get(&self, point: [f64; 4]) -> f64 {
+        let point = math::mul4(point, self.frequency);
         const SCALE_FACTOR: f64 = 4.424_369_240_215_691;
 
         #[inline(always)]
-        fn surflet(perm_table: &PermutationTable, corner: [isize; 4], distance: [f64; 4]) -> f64 {
+        fn surflet<G: GradientTable>(
+            noise: &PerlinSurflet<G>,
+            corner: [isize; 4],
+            distance: [f64; 4],
+        ) -> f64 {
             let attn = 1.0 - math::dot4(distance, distance);
             if attn > 0.0 {
-                attn.powi(4) * math::dot4(distance, gradient::get4(perm_table.get4(corner)))
+                float::powi(attn, 4)
+                    * math::dot4(
+                        distance,
+                        noise.gradients.get4(noise.perm_table.get4(corner)),
+                    )
             } else {
                 0.0
             }
         }
 
-        let floored = math::map4(point, f64::floor);
+        let floored = math::map4(point, float::floor);
         let near_corner = math::to_isize4(floored);
-        let far_corner = math::add4(near_corner, math::one4());
+        let far_corner = math::wrapping_add4(near_corner, math::one4());
         let near_distance = math::sub4(point, floored);
         let far_distance = math::sub4(near_distance, math::one4());
 
         let f0000 = surflet(
-            &self.perm_table,
+            self,
             [
                 near_corner[0],
                 near_corner[1],
@@ -203,7 +270,7 @@ impl NoiseFn<[f64; 4]> for Perlin {
             ],
         );
         let f1000 = surflet(
-            &self.perm_table,
+            self,
             [
                 far_corner[0],
                 near_corner[1],
@@ -218,7 +285,7 @@ impl NoiseFn<[f64; 4]> for Perlin {
             ],
         );
         let f0100 = surflet(
-            &self.perm_table,
+            self,
             [
                 near_corner[0],
                 far_corner[1],
@@ -233,7 +300,7 @@ impl NoiseFn<[f64; 4]> for Perlin {
             ],
         );
         let f1100 = surflet(
-            &self.perm_table,
+            self,
             [far_corner[0], far_corner[1], near_corner[2], near_corner[3]],
             [
                 far_distance[0],
@@ -243,7 +310,7 @@ impl NoiseFn<[f64; 4]> for Perlin {
             ],
         );
         let f0010 = surflet(
-            &self.perm_table,
+            self,
             [
                 near_corner[0],
                 near_corner[1],
@@ -258,7 +325,7 @@ impl NoiseFn<[f64; 4]> for Perlin {
             ],
         );
         let f1010 = surflet(
-            &self.perm_table,
+            self,
             [far_corner[0], near_corner[1], far_corner[2], near_corner[3]],
             [
                 far_distance[0],
@@ -268,7 +335,7 @@ impl NoiseFn<[f64; 4]> for Perlin {
             ],
         );
         let f0110 = surflet(
-            &self.perm_table,
+            self,
             [near_corner[0], far_corner[1], far_corner[2], near_corner[3]],
             [
                 near_distance[0],
@@ -278,7 +345,7 @@ impl NoiseFn<[f64; 4]> for Perlin {
             ],
         );
         let f1110 = surflet(
-            &self.perm_table,
+            self,
             [far_corner[0], far_corner[1], far_corner[2], near_corner[3]],
             [
                 far_distance[0],
@@ -288,7 +355,7 @@ impl NoiseFn<[f64; 4]> for Perlin {
             ],
         );
         let f0001 = surflet(
-            &self.perm_table,
+            self,
             [
                 near_corner[0],
                 near_corner[1],
@@ -303,7 +370,7 @@ impl NoiseFn<[f64; 4]> for Perlin {
             ],
         );
         let f1001 = surflet(
-            &self.perm_table,
+            self,
             [far_corner[0], near_corner[1], near_corner[2], far_corner[3]],
             [
                 far_distance[0],
@@ -313,7 +380,7 @@ impl NoiseFn<[f64; 4]> for Perlin {
             ],
         );
         let f0101 = surflet(
-            &self.perm_table,
+            self,
             [near_corner[0], far_corner[1], near_corner[2], far_corner[3]],
             [
                 near_distance[0],
@@ -323,7 +390,7 @@ impl NoiseFn<[f64; 4]> for Perlin {
             ],
         );
         let f1101 = surflet(
-            &self.perm_table,
+            self,
             [far_corner[0], far_corner[1], near_corner[2], far_corner[3]],
             [
                 far_distance[0],
@@ -333,7 +400,7 @@ impl NoiseFn<[f64; 4]> for Perlin {
             ],
         );
         let f0011 = surflet(
-            &self.perm_table,
+            self,
             [near_corner[0], near_corner[1], far_corner[2], far_corner[3]],
             [
                 near_distance[0],
@@ -343,7 +410,7 @@ impl NoiseFn<[f64; 4]> for Perlin {
             ],
         );
         let f1011 = surflet(
-            &self.perm_table,
+            self,
             [far_corner[0], near_corner[1], far_corner[2], far_corner[3]],
             [
                 far_distance[0],
@@ -353,7 +420,7 @@ impl NoiseFn<[f64; 4]> for Perlin {
             ],
         );
         let f0111 = surflet(
-            &self.perm_table,
+            self,
             [near_corner[0], far_corner[1], far_corner[2], far_corner[3]],
             [
                 near_distance[0],
@@ -363,7 +430,7 @@ impl NoiseFn<[f64; 4]> for Perlin {
             ],
         );
         let f1111 = surflet(
-            &self.perm_table,
+            self,
             [far_corner[0], far_corner[1], far_corner[2], far_corner[3]],
             [
                 far_distance[0],
@@ -397,3 +464,14 @@ impl NoiseFn<[f64; 4]> for Perlin {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::assert_doubles_feature_count;
+
+    #[test]
+    fn doubling_frequency_doubles_feature_count() {
+        assert_doubles_feature_count(&PerlinSurflet::new(), &PerlinSurflet::with_frequency(2.0));
+    }
+}
@@ -0,0 +1,1065 @@
+//! Ken Perlin's simplex noise, following Stefan Gustavson's reference
+//! implementation as described in "Simplex noise demystified":
+//! <https://weber.itn.liu.se/~stegu/simplexnoise/simplexnoise.pdf>
+
+use crate::{
+    gradient::{GradientTable, SimplexGradients},
+    math,
+    noise_fns::{NoiseBounds, NoiseFn, Seedable},
+    permutationtable::PermutationTable,
+    seed,
+};
+
+const SKEW_FACTOR_2D: f64 = 0.366_025_403_784_438_6; // (sqrt(3) - 1) / 2
+const UNSKEW_FACTOR_2D: f64 = 0.211_324_865_405_187_1; // (3 - sqrt(3)) / 6
+const SKEW_FACTOR_3D: f64 = 1.0 / 3.0;
+const UNSKEW_FACTOR_3D: f64 = 1.0 / 6.0;
+const SKEW_FACTOR_4D: f64 = 0.309_016_994_374_947_4; // (sqrt(5) - 1) / 4
+const UNSKEW_FACTOR_4D: f64 = 0.138_196_601_125_010_5; // (5 - sqrt(5)) / 20
+
+const NORM_CONSTANT_1D: f64 = 0.395;
+// The largest sum of the three surflets in 2D is reached halfway along the
+// long diagonal of a skewed cell, at a distance of 1/sqrt(6) from its two
+// ends. The middle corner is then exactly 1/sqrt(2) away and contributes
+// nothing, while each end contributes (0.5 - 1/6)^4 * (1/(2 sqrt(3)) * 2)
+// when its gradient is the diagonal pointing towards the point. The sum is
+// 2 / (81 sqrt(3)), so scaling by its inverse maps the extremes to exactly
+// -1.0 and 1.0. The commonly used constant of 70.0 stops just short of that,
+// at about 0.998.
+const NORM_CONSTANT_2D: f64 = 70.148_057_706_539_53; // 81 * sqrt(3) / 2
+const NORM_CONSTANT_3D: f64 = 32.0;
+const NORM_CONSTANT_4D: f64 = 27.0;
+
+/// Noise function that outputs 1/2/3/4-dimensional Simplex noise.
+///
+/// Simplex noise sums the contributions of the corners of the simplex
+/// (triangle, tetrahedron, ...) that contains the input point, rather than
+/// interpolating between the corners of a hypercube like `Perlin` does. This
+/// scales much better to higher dimensions and shows fewer directional
+/// artifacts.
+///
+/// The output only depends on IEEE 754 arithmetic that is exactly specified,
+/// with the contributions of the corners always summed in the same order, so
+/// the same seed and point produce bitwise identical values on every
+/// platform.
+///
+/// The gradients of the 2, 3 and 4-dimensional noise are looked up through a
+/// `GradientTable`, `SimplexGradients` by default. The 1-dimensional noise
+/// always uses its own set of gradients.
+#[derive(Clone, Copy, Debug)]
+pub struct Simplex<G = SimplexGradients> {
+    /// The number of cycles per unit length that the noise function outputs.
+    /// Default is 1.0.
+    pub frequency: f64,
+
+    seed: u32,
+    perm_table: PermutationTable,
+    gradients: G,
+}
+
+impl Simplex {
+    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+
+    pub fn new() -> Self {
+        Self::with_gradients(SimplexGradients)
+    }
+
+    /// Creates a noise function with the given frequency.
+    pub fn with_frequency(frequency: f64) -> Self {
+        Self::new().set_frequency(frequency)
+    }
+
+    /// Creates a noise function seeded with the hash of `name`, as returned
+    /// by [`seed_from_str`](crate::seed_from_str).
+    pub fn from_named_seed(name: &str) -> Self {
+        Self::new().set_seed(seed::seed_from_str(name))
+    }
+
+    /// Creates a noise function that takes its gradients from `gradients`
+    /// instead of the default set.
+    ///
+    /// The normalization constants are chosen for `SimplexGradients`, so
+    /// other sets of gradients may not reach, or may exceed, the [-1,1]
+    /// range.
+    pub fn with_gradients<G: GradientTable>(gradients: G) -> Simplex<G> {
+        Simplex {
+            seed: Self::DEFAULT_SEED,
+            perm_table: PermutationTable::new(Self::DEFAULT_SEED),
+            frequency: Self::DEFAULT_FREQUENCY,
+            gradients,
+        }
+    }
+}
+
+impl<G> Simplex<G> {
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+}
+
+impl<G: GradientTable> Simplex<G> {
+    /// Returns the partial derivatives of 2-dimensional simplex noise at
+    /// `point`, calculated analytically.
+    ///
+    /// The derivatives form the gradient of the noise, which points in the
+    /// direction the output value increases most quickly. This is useful for
+    /// generating normal maps without sampling neighbouring points.
+    ///
+    /// The derivatives are with respect to `point` itself, so they include
+    /// the factor of the frequency.
+    pub fn get_derivative_2d(&self, point: [f64; 2]) -> [f64; 2] {
+        let point = math::mul2(point, self.frequency);
+        math::mul2(
+            simplex_2d_derivative(&self.perm_table, &self.gradients, point),
+            self.frequency,
+        )
+    }
+
+    /// Returns the partial derivatives of 3-dimensional simplex noise at
+    /// `point`, calculated analytically.
+    pub fn get_derivative_3d(&self, point: [f64; 3]) -> [f64; 3] {
+        let point = math::mul3(point, self.frequency);
+        math::mul3(
+            simplex_3d_derivative(&self.perm_table, &self.gradients, point),
+            self.frequency,
+        )
+    }
+
+    /// Calculates 2-dimensional simplex noise at four points at once.
+    ///
+    /// With the `simd` feature, the four points are evaluated together with
+    /// SIMD instructions, and the results match `get` to within `1e-12`.
+    /// Otherwise each point is evaluated with `get` in turn.
+    #[inline]
+    pub fn get4x(&self, points: [[f64; 2]; 4]) -> [f64; 4] {
+        let points = math::map4(points, |point| math::mul2(point, self.frequency));
+
+        #[cfg(feature = "simd")]
+        {
+            simd::simplex_2d(&self.perm_table, &self.gradients, points)
+        }
+
+        #[cfg(not(feature = "simd"))]
+        {
+            math::map4(points, |point| {
+                simplex_2d(&self.perm_table, &self.gradients, point)
+            })
+        }
+    }
+}
+
+impl Default for Simplex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The normalization constants of each dimension scale the largest possible
+/// sum of the surflets to at most 1.0. This only holds for the default
+/// gradients.
+impl NoiseBounds for Simplex {
+    const MIN: f64 = -1.0;
+    const MAX: f64 = 1.0;
+}
+
+impl<G> Seedable for Simplex<G> {
+    /// Sets the seed value for Simplex noise
+    fn set_seed(self, seed: u32) -> Self {
+        // If the new seed is the same as the current seed, just return self.
+        if self.seed == seed {
+            return self;
+        }
+
+        // Otherwise, regenerate the permutation table based on the new seed.
+        Self {
+            seed,
+            perm_table: PermutationTable::new(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed
+    }
+}
+
+/// 1-dimensional simplex noise
+impl<G> NoiseFn<[f64; 1]> for Simplex<G> {
+    #[inline]
+    fn get(&self, point: [f64; 1]) -> f64 {
+        let point = [point[0] * self.frequency];
+        simplex_1d(&self.perm_table, point)
+    }
+}
+
+#[inline(always)]
+fn gradient_dot_v1(perm: usize, x: f64) -> f64 {
+    // Gradients are the integers 1 to 8, with a random sign.
+    let gradient = (1 + (perm & 0b111)) as f64;
+    if perm & 0b1000 == 0 {
+        gradient * x
+    } else {
+        -gradient * x
+    }
+}
+
+#[inline(always)]
+pub(crate) fn simplex_1d(perm_table: &PermutationTable, point: [f64; 1]) -> f64 {
+    #[inline(always)]
+    fn surflet(perm: usize, x: f64) -> f64 {
+        let attn = 1.0 - x * x;
+        pow4(attn) * gradient_dot_v1(perm, x)
+    }
+
+    let [x] = point;
+
+    // In one dimension the simplices are the unit intervals, so the two
+    // corners are the integers on either side of the point.
+    let cell = math::fast_floor_f64(x);
+    let x0 = x - cell;
+    let x1 = x0 - 1.0;
+
+    let corner = math::fast_floor(x);
+
+    let n0 = surflet(perm_table.get1([corner]), x0);
+    let n1 = surflet(perm_table.get1([corner.wrapping_add(1)]), x1);
+
+    (n0 + n1) * NORM_CONSTANT_1D
+}
+
+/// 2-dimensional simplex noise
+impl<G: GradientTable> NoiseFn<[f64; 2]> for Simplex<G> {
+    #[inline]
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let point = math::mul2(point, self.frequency);
+        simplex_2d(&self.perm_table, &self.gradients, point)
+    }
+
+    /// Evaluates four points at a time with SIMD instructions when the `simd`
+    /// feature is enabled.
+    #[cfg(feature = "simd")]
+    fn get_batch(&self, points: &[[f64; 2]], output: &mut [f64]) {
+        assert_eq!(
+            points.len(),
+            output.len(),
+            "points and output must have the same length"
+        );
+
+        let mut point_chunks = points.chunks_exact(4);
+        let mut output_chunks = output.chunks_exact_mut(4);
+
+        for (points, output) in (&mut point_chunks).zip(&mut output_chunks) {
+            output.copy_from_slice(&self.get4x([points[0], points[1], points[2], points[3]]));
+        }
+
+        let remainder = point_chunks.remainder();
+        for (point, value) in remainder.iter().zip(output_chunks.into_remainder()) {
+            *value = self.get(*point);
+        }
+    }
+}
+
+/// Raises `x` to the fourth power, multiplying in the same order as the SIMD
+/// implementation so that both produce identical results.
+#[inline(always)]
+fn pow4(x: f64) -> f64 {
+    let x2 = x * x;
+    x2 * x2
+}
+
+#[inline(always)]
+fn dot2(gradient: [f64; 2], x: f64, y: f64) -> f64 {
+    gradient[0] * x + gradient[1] * y
+}
+
+#[inline(always)]
+pub(crate) fn simplex_2d<G: GradientTable>(
+    perm_table: &PermutationTable,
+    gradients: &G,
+    point: [f64; 2],
+) -> f64 {
+    let surflet = |(perm, x, y): (usize, f64, f64)| {
+        let attn = 0.5 - x * x - y * y;
+        if attn > 0.0 {
+            pow4(attn) * dot2(gradients.get2(perm), x, y)
+        } else {
+            0.0
+        }
+    };
+
+    let [c0, c1, c2] = simplex_2d_corners(perm_table, point);
+
+    ((surflet(c0) + surflet(c1)) + surflet(c2)) * NORM_CONSTANT_2D
+}
+
+/// Calculates the partial derivatives of 2-dimensional simplex noise.
+#[inline(always)]
+pub(crate) fn simplex_2d_derivative<G: GradientTable>(
+    perm_table: &PermutationTable,
+    gradients: &G,
+    point: [f64; 2],
+) -> [f64; 2] {
+    let surflet_derivative = |(perm, x, y): (usize, f64, f64)| {
+        let attn = 0.5 - x * x - y * y;
+        if attn > 0.0 {
+            // d/dx (attn^4 * (g . d)) = attn^4 * g_x - 8 * attn^3 * x * (g . d)
+            let gradient = gradients.get2(perm);
+            let dot = dot2(gradient, x, y);
+            let attn3 = attn * attn * attn;
+
+            [
+                attn3 * (attn * gradient[0] - 8.0 * x * dot),
+                attn3 * (attn * gradient[1] - 8.0 * y * dot),
+            ]
+        } else {
+            [0.0; 2]
+        }
+    };
+
+    let [c0, c1, c2] = simplex_2d_corners(perm_table, point);
+
+    let derivative = math::add2(
+        math::add2(surflet_derivative(c0), surflet_derivative(c1)),
+        surflet_derivative(c2),
+    );
+
+    math::mul2(derivative, NORM_CONSTANT_2D)
+}
+
+/// Finds the three corners of the simplex containing `point`, returning the
+/// hash of each corner along with the distance from the corner to the point.
+#[inline(always)]
+fn simplex_2d_corners(perm_table: &PermutationTable, point: [f64; 2]) -> [(usize, f64, f64); 3] {
+    let [x, y] = point;
+
+    // Skew the input space to find which simplex cell the point is in.
+    let skew = (x + y) * SKEW_FACTOR_2D;
+    let skewed = [x + skew, y + skew];
+    let cell = math::map2(skewed, math::fast_floor_f64);
+
+    // Unskew the cell origin back to input space, and find the distance from
+    // it to the point.
+    let unskew = (cell[0] + cell[1]) * UNSKEW_FACTOR_2D;
+    let x0 = x - (cell[0] - unskew);
+    let y0 = y - (cell[1] - unskew);
+
+    // The cell is split into an upper and a lower triangle. Find the middle
+    // corner of the triangle the point is in.
+    let offset = if x0 > y0 { [1, 0] } else { [0, 1] };
+
+    let x1 = x0 - offset[0] as f64 + UNSKEW_FACTOR_2D;
+    let y1 = y0 - offset[1] as f64 + UNSKEW_FACTOR_2D;
+    let x2 = x0 - 1.0 + 2.0 * UNSKEW_FACTOR_2D;
+    let y2 = y0 - 1.0 + 2.0 * UNSKEW_FACTOR_2D;
+
+    let corner = math::map2(skewed, math::fast_floor);
+
+    [
+        (perm_table.get2(corner), x0, y0),
+        (perm_table.get2(math::wrapping_add2(corner, offset)), x1, y1),
+        (perm_table.get2(math::wrapping_add2(corner, [1, 1])), x2, y2),
+    ]
+}
+
+/// 3-dimensional simplex noise
+impl<G: GradientTable> NoiseFn<[f64; 3]> for Simplex<G> {
+    #[inline]
+    fn get(&self, point: [f64; 3]) -> f64 {
+        let point = math::mul3(point, self.frequency);
+        simplex_3d(&self.perm_table, &self.gradients, point)
+    }
+
+    /// Evaluates four points at a time with SIMD instructions when the `simd`
+    /// feature is enabled.
+    #[cfg(feature = "simd")]
+    fn get_batch(&self, points: &[[f64; 3]], output: &mut [f64]) {
+        assert_eq!(
+            points.len(),
+            output.len(),
+            "points and output must have the same length"
+        );
+
+        let mut point_chunks = points.chunks_exact(4);
+        let mut output_chunks = output.chunks_exact_mut(4);
+
+        for (points, output) in (&mut point_chunks).zip(&mut output_chunks) {
+            let points = [points[0], points[1], points[2], points[3]];
+            let points = math::map4(points, |point| math::mul3(point, self.frequency));
+            output.copy_from_slice(&simd::simplex_3d(&self.perm_table, &self.gradients, points));
+        }
+
+        let remainder = point_chunks.remainder();
+        for (point, value) in remainder.iter().zip(output_chunks.into_remainder()) {
+            *value = self.get(*point);
+        }
+    }
+}
+
+#[inline(always)]
+fn dot3(gradient: [f64; 3], x: f64, y: f64, z: f64) -> f64 {
+    (gradient[0] * x + gradient[1] * y) + gradient[2] * z
+}
+
+#[inline(always)]
+pub(crate) fn simplex_3d<G: GradientTable>(
+    perm_table: &PermutationTable,
+    gradients: &G,
+    point: [f64; 3],
+) -> f64 {
+    let surflet = |(perm, x, y, z): (usize, f64, f64, f64)| {
+        let attn = 0.6 - x * x - y * y - z * z;
+        if attn > 0.0 {
+            pow4(attn) * dot3(gradients.get3(perm), x, y, z)
+        } else {
+            0.0
+        }
+    };
+
+    let [c0, c1, c2, c3] = simplex_3d_corners(perm_table, point);
+
+    (((surflet(c0) + surflet(c1)) + surflet(c2)) + surflet(c3)) * NORM_CONSTANT_3D
+}
+
+/// Calculates the partial derivatives of 3-dimensional simplex noise.
+#[inline(always)]
+pub(crate) fn simplex_3d_derivative<G: GradientTable>(
+    perm_table: &PermutationTable,
+    gradients: &G,
+    point: [f64; 3],
+) -> [f64; 3] {
+    let surflet_derivative = |(perm, x, y, z): (usize, f64, f64, f64)| {
+        let attn = 0.6 - x * x - y * y - z * z;
+        if attn > 0.0 {
+            // d/dx (attn^4 * (g . d)) = attn^4 * g_x - 8 * attn^3 * x * (g . d)
+            let gradient = gradients.get3(perm);
+            let dot = dot3(gradient, x, y, z);
+            let attn3 = attn * attn * attn;
+
+            [
+                attn3 * (attn * gradient[0] - 8.0 * x * dot),
+                attn3 * (attn * gradient[1] - 8.0 * y * dot),
+                attn3 * (attn * gradient[2] - 8.0 * z * dot),
+            ]
+        } else {
+            [0.0; 3]
+        }
+    };
+
+    let [c0, c1, c2, c3] = simplex_3d_corners(perm_table, point);
+
+    let derivative = math::add3(
+        math::add3(
+            math::add3(surflet_derivative(c0), surflet_derivative(c1)),
+            surflet_derivative(c2),
+        ),
+        surflet_derivative(c3),
+    );
+
+    math::mul3(derivative, NORM_CONSTANT_3D)
+}
+
+/// Finds the four corners of the simplex containing `point`, returning the
+/// hash of each corner along with the distance from the corner to the point.
+#[inline(always)]
+fn simplex_3d_corners(
+    perm_table: &PermutationTable,
+    point: [f64; 3],
+) -> [(usize, f64, f64, f64); 4] {
+    let [x, y, z] = point;
+
+    // Skew the input space to find which simplex cell the point is in.
+    let skew = (x + y + z) * SKEW_FACTOR_3D;
+    let skewed = [x + skew, y + skew, z + skew];
+    let cell = math::map3(skewed, math::fast_floor_f64);
+
+    // Unskew the cell origin back to input space, and find the distance from
+    // it to the point.
+    let unskew = (cell[0] + cell[1] + cell[2]) * UNSKEW_FACTOR_3D;
+    let x0 = x - (cell[0] - unskew);
+    let y0 = y - (cell[1] - unskew);
+    let z0 = z - (cell[2] - unskew);
+
+    // The cell is split into six tetrahedra. Find the second and third
+    // corners of the one the point is in.
+    let (offset1, offset2) = if x0 >= y0 {
+        if y0 >= z0 {
+            ([1, 0, 0], [1, 1, 0])
+        } else if x0 >= z0 {
+            ([1, 0, 0], [1, 0, 1])
+        } else {
+            ([0, 0, 1], [1, 0, 1])
+        }
+    } else if y0 < z0 {
+        ([0, 0, 1], [0, 1, 1])
+    } else if x0 < z0 {
+        ([0, 1, 0], [0, 1, 1])
+    } else {
+        ([0, 1, 0], [1, 1, 0])
+    };
+
+    let x1 = x0 - offset1[0] as f64 + UNSKEW_FACTOR_3D;
+    let y1 = y0 - offset1[1] as f64 + UNSKEW_FACTOR_3D;
+    let z1 = z0 - offset1[2] as f64 + UNSKEW_FACTOR_3D;
+    let x2 = x0 - offset2[0] as f64 + 2.0 * UNSKEW_FACTOR_3D;
+    let y2 = y0 - offset2[1] as f64 + 2.0 * UNSKEW_FACTOR_3D;
+    let z2 = z0 - offset2[2] as f64 + 2.0 * UNSKEW_FACTOR_3D;
+    let x3 = x0 - 1.0 + 3.0 * UNSKEW_FACTOR_3D;
+    let y3 = y0 - 1.0 + 3.0 * UNSKEW_FACTOR_3D;
+    let z3 = z0 - 1.0 + 3.0 * UNSKEW_FACTOR_3D;
+
+    let corner = math::map3(skewed, math::fast_floor);
+
+    [
+        (perm_table.get3(corner), x0, y0, z0),
+        (
+            perm_table.get3(math::wrapping_add3(corner, offset1)),
+            x1,
+            y1,
+            z1,
+        ),
+        (
+            perm_table.get3(math::wrapping_add3(corner, offset2)),
+            x2,
+            y2,
+            z2,
+        ),
+        (
+            perm_table.get3(math::wrapping_add3(corner, [1, 1, 1])),
+            x3,
+            y3,
+            z3,
+        ),
+    ]
+}
+
+/// 4-dimensional simplex noise
+impl<G: GradientTable> NoiseFn<[f64; 4]> for Simplex<G> {
+    #[inline]
+    fn get(&self, point: [f64; 4]) -> f64 {
+        let point = math::mul4(point, self.frequency);
+        simplex_4d(&self.perm_table, &self.gradients, point)
+    }
+}
+
+#[inline(always)]
+pub(crate) fn simplex_4d<G: GradientTable>(
+    perm_table: &PermutationTable,
+    gradients: &G,
+    point: [f64; 4],
+) -> f64 {
+    let surflet = |perm: usize, distance: [f64; 4]| {
+        let [x, y, z, w] = distance;
+        let attn = 0.6 - x * x - y * y - z * z - w * w;
+        if attn > 0.0 {
+            let gradient = gradients.get4(perm);
+            let dot = ((gradient[0] * x + gradient[1] * y) + gradient[2] * z) + gradient[3] * w;
+            pow4(attn) * dot
+        } else {
+            0.0
+        }
+    };
+
+    // Skew the input space to find which simplex cell the point is in.
+    let skew = (point[0] + point[1] + point[2] + point[3]) * SKEW_FACTOR_4D;
+    let skewed = math::map4(point, |v| v + skew);
+    let cell = math::map4(skewed, math::fast_floor_f64);
+
+    // Unskew the cell origin back to input space, and find the distance from
+    // it to the point.
+    let unskew = (cell[0] + cell[1] + cell[2] + cell[3]) * UNSKEW_FACTOR_4D;
+    let distance0 = math::sub4(point, math::map4(cell, |v| v - unskew));
+
+    // The cell is split into 24 simplices. Rank the components of the
+    // distance by magnitude; the corners of the simplex the point is in are
+    // reached by stepping along the axes from the largest to the smallest.
+    let mut rank = [0; 4];
+    for i in 0..4 {
+        for j in (i + 1)..4 {
+            if distance0[i] > distance0[j] {
+                rank[i] += 1;
+            } else {
+                rank[j] += 1;
+            }
+        }
+    }
+
+    let offset1 = math::map4(rank, |r| (r >= 3) as isize);
+    let offset2 = math::map4(rank, |r| (r >= 2) as isize);
+    let offset3 = math::map4(rank, |r| (r >= 1) as isize);
+
+    let distance = |offset: [isize; 4], corner: f64| {
+        math::add4(
+            math::sub4(distance0, math::to_f64_4(offset)),
+            [corner * UNSKEW_FACTOR_4D; 4],
+        )
+    };
+
+    let corner = math::map4(skewed, math::fast_floor);
+
+    let n0 = surflet(perm_table.get4(corner), distance0);
+    let n1 = surflet(
+        perm_table.get4(math::wrapping_add4(corner, offset1)),
+        distance(offset1, 1.0),
+    );
+    let n2 = surflet(
+        perm_table.get4(math::wrapping_add4(corner, offset2)),
+        distance(offset2, 2.0),
+    );
+    let n3 = surflet(
+        perm_table.get4(math::wrapping_add4(corner, offset3)),
+        distance(offset3, 3.0),
+    );
+    let n4 = surflet(
+        perm_table.get4(math::wrapping_add4(corner, [1; 4])),
+        distance([1; 4], 4.0),
+    );
+
+    ((((n0 + n1) + n2) + n3) + n4) * NORM_CONSTANT_4D
+}
+
+#[cfg(feature = "simd")]
+mod simd {
+    use super::*;
+    use std::simd::{cmp::SimdPartialOrd, f64x4, Select, StdFloat};
+
+    /// Calculates 2-dimensional simplex noise for four points at once.
+    ///
+    /// This performs the same operations as the scalar `simplex_2d`, except
+    /// that the permutation table and gradient lookups are done per lane.
+    #[inline(always)]
+    pub(super) fn simplex_2d<G: GradientTable>(
+        perm_table: &PermutationTable,
+        gradient_table: &G,
+        points: [[f64; 2]; 4],
+    ) -> [f64; 4] {
+        #[inline(always)]
+        fn surflet(gradients: [[f64; 2]; 4], x: f64x4, y: f64x4) -> f64x4 {
+            let gx = f64x4::from_array(math::map4(gradients, |g| g[0]));
+            let gy = f64x4::from_array(math::map4(gradients, |g| g[1]));
+
+            let attn = f64x4::splat(0.5) - x * x - y * y;
+            let attn2 = attn * attn;
+            let value = attn2 * attn2 * (gx * x + gy * y);
+
+            attn.simd_gt(f64x4::splat(0.0))
+                .select(value, f64x4::splat(0.0))
+        }
+
+        let gradients = |corners: [[isize; 2]; 4]| {
+            math::map4(corners, |corner| {
+                gradient_table.get2(perm_table.get2(corner))
+            })
+        };
+
+        let x = f64x4::from_array(math::map4(points, |p| p[0]));
+        let y = f64x4::from_array(math::map4(points, |p| p[1]));
+
+        let skew = (x + y) * f64x4::splat(SKEW_FACTOR_2D);
+        let cell_x = (x + skew).floor();
+        let cell_y = (y + skew).floor();
+
+        let unskew = (cell_x + cell_y) * f64x4::splat(UNSKEW_FACTOR_2D);
+        let x0 = x - (cell_x - unskew);
+        let y0 = y - (cell_y - unskew);
+
+        let lower = x0.simd_gt(y0);
+        let offset_x = lower.select(f64x4::splat(1.0), f64x4::splat(0.0));
+        let offset_y = lower.select(f64x4::splat(0.0), f64x4::splat(1.0));
+
+        let x1 = x0 - offset_x + f64x4::splat(UNSKEW_FACTOR_2D);
+        let y1 = y0 - offset_y + f64x4::splat(UNSKEW_FACTOR_2D);
+        let x2 = x0 - f64x4::splat(1.0) + f64x4::splat(2.0 * UNSKEW_FACTOR_2D);
+        let y2 = y0 - f64x4::splat(1.0) + f64x4::splat(2.0 * UNSKEW_FACTOR_2D);
+
+        let cell_x = cell_x.to_array();
+        let cell_y = cell_y.to_array();
+        let offset_x = offset_x.to_array();
+        let offset_y = offset_y.to_array();
+
+        let corner0 = [0, 1, 2, 3].map(|i| math::to_isize2([cell_x[i], cell_y[i]]));
+        let corner1 = [0, 1, 2, 3]
+            .map(|i| math::to_isize2([cell_x[i] + offset_x[i], cell_y[i] + offset_y[i]]));
+        let corner2 = corner0.map(|corner| math::wrapping_add2(corner, [1, 1]));
+
+        let n0 = surflet(gradients(corner0), x0, y0);
+        let n1 = surflet(gradients(corner1), x1, y1);
+        let n2 = surflet(gradients(corner2), x2, y2);
+
+        (((n0 + n1) + n2) * f64x4::splat(NORM_CONSTANT_2D)).to_array()
+    }
+
+    /// Calculates 3-dimensional simplex noise for four points at once.
+    ///
+    /// The tetrahedron each point is in is chosen with per-lane masks, which
+    /// select the same corners as the branches of the scalar
+    /// `simplex_3d_corners`.
+    #[inline(always)]
+    pub(super) fn simplex_3d<G: GradientTable>(
+        perm_table: &PermutationTable,
+        gradient_table: &G,
+        points: [[f64; 3]; 4],
+    ) -> [f64; 4] {
+        #[inline(always)]
+        fn surflet(gradients: [[f64; 3]; 4], x: f64x4, y: f64x4, z: f64x4) -> f64x4 {
+            let gx = f64x4::from_array(math::map4(gradients, |g| g[0]));
+            let gy = f64x4::from_array(math::map4(gradients, |g| g[1]));
+            let gz = f64x4::from_array(math::map4(gradients, |g| g[2]));
+
+            let attn = f64x4::splat(0.6) - x * x - y * y - z * z;
+            let attn2 = attn * attn;
+            let value = attn2 * attn2 * ((gx * x + gy * y) + gz * z);
+
+            attn.simd_gt(f64x4::splat(0.0))
+                .select(value, f64x4::splat(0.0))
+        }
+
+        let gradients = |corners: [[isize; 3]; 4]| {
+            math::map4(corners, |corner| {
+                gradient_table.get3(perm_table.get3(corner))
+            })
+        };
+
+        #[inline(always)]
+        fn offsets(cell: [f64x4; 3], offset: [f64x4; 3]) -> [[isize; 3]; 4] {
+            let [cell_x, cell_y, cell_z] = cell.map(|c| c.to_array());
+            let [offset_x, offset_y, offset_z] = offset.map(|o| o.to_array());
+
+            [0, 1, 2, 3].map(|i| {
+                math::to_isize3([
+                    cell_x[i] + offset_x[i],
+                    cell_y[i] + offset_y[i],
+                    cell_z[i] + offset_z[i],
+                ])
+            })
+        }
+
+        let one = f64x4::splat(1.0);
+        let zero = f64x4::splat(0.0);
+
+        let x = f64x4::from_array(math::map4(points, |p| p[0]));
+        let y = f64x4::from_array(math::map4(points, |p| p[1]));
+        let z = f64x4::from_array(math::map4(points, |p| p[2]));
+
+        let skew = (x + y + z) * f64x4::splat(SKEW_FACTOR_3D);
+        let cell_x = (x + skew).floor();
+        let cell_y = (y + skew).floor();
+        let cell_z = (z + skew).floor();
+
+        let unskew = (cell_x + cell_y + cell_z) * f64x4::splat(UNSKEW_FACTOR_3D);
+        let x0 = x - (cell_x - unskew);
+        let y0 = y - (cell_y - unskew);
+        let z0 = z - (cell_z - unskew);
+
+        let x_ge_y = x0.simd_ge(y0);
+        let y_ge_z = y0.simd_ge(z0);
+        let x_ge_z = x0.simd_ge(z0);
+
+        let offset1 = [
+            (x_ge_y & x_ge_z).select(one, zero),
+            (!x_ge_y & y_ge_z).select(one, zero),
+            (!x_ge_z & !y_ge_z).select(one, zero),
+        ];
+        let offset2 = [
+            (x_ge_y | x_ge_z).select(one, zero),
+            (!x_ge_y | y_ge_z).select(one, zero),
+            (!x_ge_z | !y_ge_z).select(one, zero),
+        ];
+
+        let unskew1 = f64x4::splat(UNSKEW_FACTOR_3D);
+        let unskew2 = f64x4::splat(2.0 * UNSKEW_FACTOR_3D);
+        let unskew3 = f64x4::splat(3.0 * UNSKEW_FACTOR_3D);
+
+        let x1 = x0 - offset1[0] + unskew1;
+        let y1 = y0 - offset1[1] + unskew1;
+        let z1 = z0 - offset1[2] + unskew1;
+        let x2 = x0 - offset2[0] + unskew2;
+        let y2 = y0 - offset2[1] + unskew2;
+        let z2 = z0 - offset2[2] + unskew2;
+        let x3 = x0 - one + unskew3;
+        let y3 = y0 - one + unskew3;
+        let z3 = z0 - one + unskew3;
+
+        let cell = [cell_x, cell_y, cell_z];
+        let corner0 = offsets(cell, [zero; 3]);
+        let corner1 = offsets(cell, offset1);
+        let corner2 = offsets(cell, offset2);
+        let corner3 = corner0.map(|corner| math::wrapping_add3(corner, [1, 1, 1]));
+
+        let n0 = surflet(gradients(corner0), x0, y0, z0);
+        let n1 = surflet(gradients(corner1), x1, y1, z1);
+        let n2 = surflet(gradients(corner2), x2, y2, z2);
+        let n3 = surflet(gradients(corner3), x3, y3, z3);
+
+        ((((n0 + n1) + n2) + n3) * f64x4::splat(NORM_CONSTANT_3D)).to_array()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::assert_doubles_feature_count;
+
+    #[test]
+    fn simplex_1d_stays_within_bounds() {
+        // The output peaks halfway between two corners with opposite
+        // gradients of the largest magnitude, where both surflets add
+        // 0.75^4 * 8 * 0.5 for a total of 2.53125 before normalization.
+        let peak = 2.53125 * NORM_CONSTANT_1D;
+        let mut largest = 0.0_f64;
+
+        for seed in 0..8 {
+            let simplex = Simplex::new().set_seed(seed);
+
+            for i in -4096..4096 {
+                let value = simplex.get([i as f64 / 32.0]);
+                assert!(value.abs() <= peak, "{} at {}", value, i as f64 / 32.0);
+                largest = largest.max(value.abs());
+            }
+        }
+
+        assert_eq!(largest, peak);
+        assert!((peak - 0.9998).abs() < 1e-4);
+    }
+
+    #[test]
+    fn derivative_matches_finite_differences() {
+        const STEP: f64 = 1e-6;
+
+        let simplex = Simplex::new().set_seed(3);
+
+        for i in 0..200 {
+            let point = [i as f64 * 0.0731 - 4.0, i as f64 * -0.0419 + 2.0];
+            let derivative = simplex.get_derivative_2d(point);
+
+            let dx = (simplex.get([point[0] + STEP, point[1]])
+                - simplex.get([point[0] - STEP, point[1]]))
+                / (2.0 * STEP);
+            let dy = (simplex.get([point[0], point[1] + STEP])
+                - simplex.get([point[0], point[1] - STEP]))
+                / (2.0 * STEP);
+
+            assert!(
+                (derivative[0] - dx).abs() < 1e-5,
+                "{} != {}",
+                derivative[0],
+                dx
+            );
+            assert!(
+                (derivative[1] - dy).abs() < 1e-5,
+                "{} != {}",
+                derivative[1],
+                dy
+            );
+        }
+    }
+
+    #[test]
+    fn derivative_3d_matches_finite_differences() {
+        const STEP: f64 = 1e-6;
+
+        let simplex = Simplex::new().set_seed(3);
+
+        for i in 0..200 {
+            let point = [
+                i as f64 * 0.0731 - 4.0,
+                i as f64 * -0.0419 + 2.0,
+                i as f64 * 0.0253,
+            ];
+            let derivative = simplex.get_derivative_3d(point);
+
+            for axis in 0..3 {
+                let mut ahead = point;
+                let mut behind = point;
+                ahead[axis] += STEP;
+                behind[axis] -= STEP;
+                let difference = (simplex.get(ahead) - simplex.get(behind)) / (2.0 * STEP);
+
+                assert!(
+                    (derivative[axis] - difference).abs() < 1e-5,
+                    "{} != {}",
+                    derivative[axis],
+                    difference
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn get_batch_matches_get() {
+        let simplex = Simplex::new();
+        let points: Vec<[f64; 2]> = (0..103)
+            .map(|i| [i as f64 * 0.173 - 9.0, i as f64 * -0.319 + 4.0])
+            .collect();
+        let mut output = vec![0.0; points.len()];
+
+        simplex.get_batch(&points, &mut output);
+
+        for (point, value) in points.iter().zip(output.iter()) {
+            assert!((simplex.get(*point) - value).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn get4x_matches_get() {
+        let simplex = Simplex::new().set_seed(9);
+
+        // The grid includes lattice points, where the comparisons choosing
+        // the simplex are tied.
+        for y in -40..40 {
+            for x in (-40..40).step_by(4) {
+                let points = [0, 1, 2, 3].map(|i| [(x + i) as f64 * 0.25, y as f64 * 0.25]);
+                let values = simplex.get4x(points);
+
+                for (point, value) in points.iter().zip(values.iter()) {
+                    assert!((simplex.get(*point) - value).abs() < 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn get_batch_3d_matches_get() {
+        let simplex = Simplex::new().set_seed(9);
+        let points: Vec<[f64; 3]> = (0..4000)
+            .map(|i| {
+                let [x, y, z] = [i % 20, (i / 20) % 20, i / 400];
+                [x as f64 * 0.25 - 2.0, y as f64 * 0.25, z as f64 * -0.25]
+            })
+            .collect();
+        let mut output = vec![0.0; points.len()];
+
+        simplex.get_batch(&points, &mut output);
+
+        for (point, value) in points.iter().zip(output.iter()) {
+            assert!((simplex.get(*point) - value).abs() < 1e-12);
+        }
+    }
+
+    // Hard-coded outputs for a fixed seed and point. These must never change,
+    // as users rely on the same seed generating the same noise everywhere.
+    #[test]
+    fn golden_values() {
+        let simplex = Simplex::new().set_seed(42);
+
+        assert_eq!(simplex.get([0.3, -1.7]), -0.41773805901518946);
+        assert_eq!(simplex.get([0.3, -1.7, 2.9]), 0.23462828799999977);
+        assert_eq!(simplex.get([0.3, -1.7, 2.9, -4.1]), -0.38716932685303285);
+    }
+
+    #[test]
+    fn output_2d_stays_within_unit_range() {
+        let simplex = Simplex::new();
+        let mut max = 0.0_f64;
+
+        // Sample a dense grid over enough cells to meet most combinations of
+        // gradients at the corners.
+        for y in 0..1000 {
+            for x in 0..1000 {
+                let value = simplex.get([x as f64 * 0.0437, y as f64 * 0.0437]);
+                max = max.max(value.abs());
+            }
+        }
+
+        // The extremes are reached, but never exceeded.
+        assert!(max <= 1.0 + 1e-6, "max is {}", max);
+        assert!(max > 0.99, "max is {}", max);
+    }
+
+    #[test]
+    fn large_negative_coordinates_are_continuous() {
+        const STEP: f64 = 1e-4;
+
+        let simplex = Simplex::new();
+
+        // Straddle the lattice coordinates that a direct cast to `isize`
+        // saturates at on 32-bit targets.
+        for &base in &[i32::MIN as f64, i32::MIN as f64 * 4.0, -1e12] {
+            for i in -20..20 {
+                let x = base + i as f64 * 0.05;
+                let point = [x, x * 0.5 + 0.3, 0.7, -0.2];
+
+                let value = simplex.get(point);
+                let next = simplex.get([point[0] + STEP, point[1], point[2], point[3]]);
+                assert!(value.is_finite());
+                assert!(
+                    (next - value).abs() < 0.01,
+                    "discontinuity at {:?}: {} vs {}",
+                    point,
+                    value,
+                    next
+                );
+
+                let value = simplex.get([point[0], point[1]]);
+                let next = simplex.get([point[0] + STEP, point[1]]);
+                assert!((next - value).abs() < 0.01);
+
+                let value = simplex.get([point[0], point[1], point[2]]);
+                let next = simplex.get([point[0] + STEP, point[1], point[2]]);
+                assert!((next - value).abs() < 0.01);
+            }
+        }
+
+        // Coordinates beyond the range of `i64` don't overflow the lattice
+        // arithmetic either.
+        let _ = simplex.get([-1e30, -1e30]);
+        let _ = simplex.get([f64::MAX, f64::MIN, 1.0]);
+        let _ = simplex.get([-1e30, 1e30, -1e30, 1e30]);
+    }
+
+    #[test]
+    fn named_seed_matches_hashed_seed() {
+        let simplex = Simplex::from_named_seed("forest");
+
+        assert_eq!(simplex.seed(), crate::seed_from_str("forest"));
+        assert_eq!(
+            simplex.get([0.3, -1.7]),
+            Simplex::new().set_seed(0x4f29_2a60).get([0.3, -1.7])
+        );
+    }
+
+    #[test]
+    fn doubling_frequency_doubles_feature_count() {
+        assert_doubles_feature_count(&Simplex::new(), &Simplex::with_frequency(2.0));
+
+        let simplex = Simplex::new().set_frequency(2.0);
+        assert_eq!(simplex.get([0.3]), Simplex::new().get([0.6]));
+        assert_eq!(
+            simplex.get_derivative_2d([0.3, 0.7]),
+            Simplex::new()
+                .get_derivative_2d([0.6, 1.4])
+                .map(|d| d * 2.0)
+        );
+    }
+
+    #[test]
+    fn gradients_come_from_the_table() {
+        let simplex = Simplex::new().set_seed(9);
+        let explicit = Simplex::with_gradients(SimplexGradients).set_seed(9);
+        let unit = Simplex::with_gradients(crate::gradient::DefaultGradients).set_seed(9);
+
+        let mut differs = false;
+        for i in 0..100 {
+            let point = [
+                i as f64 * 0.173 - 9.0,
+                i as f64 * -0.291 + 4.0,
+                i as f64 * 0.05,
+            ];
+            let point2 = [point[0], point[1]];
+            let point4 = [point[0], point[1], point[2], point[0] * 0.5];
+
+            assert_eq!(explicit.get(point2), simplex.get(point2));
+            assert_eq!(explicit.get(point), simplex.get(point));
+            assert_eq!(explicit.get(point4), simplex.get(point4));
+            assert_eq!(
+                explicit.get_derivative_3d(point),
+                simplex.get_derivative_3d(point)
+            );
+
+            differs |= unit.get(point2) != simplex.get(point2);
+            differs |= unit.get(point) != simplex.get(point);
+        }
+
+        assert!(differs);
+    }
+}
@@ -0,0 +1,119 @@
+use crate::noise_fns::{NoiseFn, Seedable, Simplex};
+
+/// Noise function that outputs a 2-dimensional vector at each point of the
+/// plane.
+///
+/// `get` samples two simplex noise functions, seeded with `seed` and
+/// `seed.wrapping_add(1)`, as the x and y components. The components are
+/// independent, so the field has sources and sinks where the vectors converge.
+///
+/// `get_curl` instead rotates the analytical gradient of the first function by
+/// 90 degrees, as in Bridson's curl noise. The result is divergence-free,
+/// which makes it suitable for advecting particles like an incompressible
+/// fluid.
+#[derive(Clone, Copy, Debug)]
+pub struct SimplexVec2 {
+    seed: u32,
+    x: Simplex,
+    y: Simplex,
+}
+
+impl SimplexVec2 {
+    pub const DEFAULT_SEED: u32 = 0;
+
+    pub fn new() -> Self {
+        Self {
+            seed: Self::DEFAULT_SEED,
+            x: Simplex::new().set_seed(Self::DEFAULT_SEED),
+            y: Simplex::new().set_seed(Self::DEFAULT_SEED.wrapping_add(1)),
+        }
+    }
+
+    /// Returns the vector at `point`, with each component in [-1,1].
+    pub fn get(&self, point: [f64; 2]) -> [f64; 2] {
+        [self.x.get(point), self.y.get(point)]
+    }
+
+    /// Returns the divergence-free curl vector at `point`, `(∂F/∂y, -∂F/∂x)`
+    /// where `F` is the x component of `get`.
+    pub fn get_curl(&self, point: [f64; 2]) -> [f64; 2] {
+        curl_2d(&self.x, point)
+    }
+}
+
+/// Returns the curl of the scalar potential `F` at `point`, `(∂F/∂y, -∂F/∂x)`.
+pub(super) fn curl_2d(potential: &Simplex, point: [f64; 2]) -> [f64; 2] {
+    let [dx, dy] = potential.get_derivative_2d(point);
+
+    [dy, -dx]
+}
+
+impl Default for SimplexVec2 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Seedable for SimplexVec2 {
+    fn set_seed(self, seed: u32) -> Self {
+        if self.seed == seed {
+            return self;
+        }
+
+        Self {
+            seed,
+            x: self.x.set_seed(seed),
+            y: self.y.set_seed(seed.wrapping_add(1)),
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn components_are_seeded_simplex() {
+        let vec2 = SimplexVec2::new().set_seed(u32::MAX);
+        let x = Simplex::new().set_seed(u32::MAX);
+        let y = Simplex::new().set_seed(0);
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173 - 8.0, i as f64 * -0.291 + 3.0];
+            assert_eq!(vec2.get(point), [x.get(point), y.get(point)]);
+        }
+    }
+
+    #[test]
+    fn curl_is_divergence_free() {
+        const STEP: f64 = 1e-5;
+
+        let vec2 = SimplexVec2::new().set_seed(9);
+
+        for i in 0..200 {
+            let point = [i as f64 * 0.0731 - 4.0, i as f64 * -0.0419 + 2.0];
+
+            let dx = vec2.get_curl([point[0] + STEP, point[1]])[0]
+                - vec2.get_curl([point[0] - STEP, point[1]])[0];
+            let dy = vec2.get_curl([point[0], point[1] + STEP])[1]
+                - vec2.get_curl([point[0], point[1] - STEP])[1];
+            let divergence = (dx + dy) / (2.0 * STEP);
+
+            assert!(
+                divergence.abs() < 1e-3,
+                "divergence {} at {:?}",
+                divergence,
+                point
+            );
+
+            // The curl runs along the contours of the noise.
+            let curl = vec2.get_curl(point);
+            let gradient = vec2.x.get_derivative_2d(point);
+            assert_eq!(curl[0] * gradient[0] + curl[1] * gradient[1], 0.0);
+        }
+    }
+}
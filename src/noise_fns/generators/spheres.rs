@@ -0,0 +1,126 @@
+use crate::{
+    math::float,
+    noise_fns::{NoiseBounds, NoiseFn},
+};
+
+/// Noise function that outputs concentric spheres.
+///
+/// This noise function outputs concentric spheres centered on the origin,
+/// like the concentric rings of an onion. The distance between neighbouring
+/// spheres is the inverse of the frequency.
+#[derive(Clone, Copy, Debug)]
+pub struct Spheres {
+    /// Frequency of the concentric objects.
+    pub frequency: f64,
+}
+
+impl Spheres {
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+
+    pub fn new() -> Self {
+        Self {
+            frequency: Self::DEFAULT_FREQUENCY,
+        }
+    }
+
+    /// Creates a noise function with the given frequency.
+    pub fn with_frequency(frequency: f64) -> Self {
+        Self::new().set_frequency(frequency)
+    }
+
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency }
+    }
+}
+
+impl Default for Spheres {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NoiseBounds for Spheres {
+    const MIN: f64 = -1.0;
+    const MAX: f64 = 1.0;
+}
+
+impl NoiseFn<[f64; 2]> for Spheres {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        calculate_spheres(&point, self.frequency)
+    }
+}
+
+impl NoiseFn<[f64; 3]> for Spheres {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        calculate_spheres(&point, self.frequency)
+    }
+}
+
+impl NoiseFn<[f64; 4]> for Spheres {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        calculate_spheres(&point, self.frequency)
+    }
+}
+
+fn calculate_spheres(point: &[f64], frequency: f64) -> f64 {
+    // Scale the inputs by the frequency, and calculate the distance of the
+    // point from the origin.
+    let dist_from_center = float::sqrt(
+        point
+            .iter()
+            .map(|&a| float::powi(a * frequency, 2))
+            .sum::<f64>(),
+    );
+
+    let dist_from_smaller_sphere = dist_from_center - float::floor(dist_from_center);
+    let dist_from_larger_sphere = 1.0 - dist_from_smaller_sphere;
+    let nearest_dist = dist_from_smaller_sphere.min(dist_from_larger_sphere);
+
+    // Shift the result to be in the -1.0 to +1.0 range.
+    1.0 - (nearest_dist * 4.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn output_is_spherically_symmetric() {
+        let spheres = Spheres::new().set_frequency(1.3);
+
+        for i in 0..20 {
+            let radius = i as f64 * 0.23;
+            let expected = spheres.get([radius, 0.0, 0.0]);
+
+            for j in 0..8 {
+                for k in 0..8 {
+                    let (sin_a, cos_a) = (j as f64 * PI / 4.0).sin_cos();
+                    let (sin_b, cos_b) = (k as f64 * PI / 8.0).sin_cos();
+                    let point = [
+                        radius * sin_b * cos_a,
+                        radius * sin_b * sin_a,
+                        radius * cos_b,
+                    ];
+
+                    let value = spheres.get(point);
+                    assert!((value - expected).abs() < 1e-9);
+                    assert!((-1.0..=1.0).contains(&value));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn doubling_frequency_halves_spacing() {
+        let spheres = Spheres::new().set_frequency(1.5);
+        let doubled = spheres.set_frequency(3.0);
+
+        for i in 0..100 {
+            let radius = i as f64 * 0.037;
+            let value = spheres.get([0.0, radius, 0.0]);
+
+            assert!((doubled.get([0.0, radius / 2.0, 0.0]) - value).abs() < 1e-9);
+        }
+    }
+}
@@ -1,7 +1,10 @@
-use crate::noise_fns::{NoiseFn, Seedable};
+use crate::noise_fns::{NoiseBounds, NoiseFn, Seedable};
 use crate::permutationtable::PermutationTable;
-use crate::{gradient, math};
-use std::ops::Add;
+use crate::{
+    gradient::{DefaultGradients, GradientTable},
+    math::{self, float},
+};
+use core::ops::Add;
 
 const TO_REAL_CONSTANT_2D: f64 = -0.211_324_865_405_187; // (1 / sqrt(2 + 1) - 1) / 2
 const TO_SIMPLEX_CONSTANT_2D: f64 = 0.366_025_403_784_439; // (sqrt(2 + 1) - 1) / 2
@@ -88,18 +91,42 @@ const LATTICE_LOOKUP_3D: [[i8; 3]; 4 * 16] =
 
 /// Noise function that outputs 2/3-dimensional Super Simplex noise.
 #[derive(Clone, Copy, Debug)]
-pub struct SuperSimplex {
+pub struct SuperSimplex<G = DefaultGradients> {
+    /// The number of cycles per unit length that the noise function outputs.
+    /// Default is 1.0.
+    pub frequency: f64,
+
     seed: u32,
     perm_table: PermutationTable,
+    gradients: G,
 }
 
 impl SuperSimplex {
     pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
 
     pub fn new() -> Self {
         Self {
             seed: Self::DEFAULT_SEED,
             perm_table: PermutationTable::new(Self::DEFAULT_SEED),
+            frequency: Self::DEFAULT_FREQUENCY,
+            gradients: DefaultGradients,
+        }
+    }
+
+    /// Creates a noise function with the given frequency.
+    pub fn with_frequency(frequency: f64) -> Self {
+        Self::new().set_frequency(frequency)
+    }
+
+    /// Creates a noise function that takes its gradients from `gradients`
+    /// instead of the default set.
+    pub fn with_gradients<G: GradientTable>(gradients: G) -> SuperSimplex<G> {
+        SuperSimplex {
+            seed: Self::DEFAULT_SEED,
+            perm_table: PermutationTable::new(Self::DEFAULT_SEED),
+            frequency: Self::DEFAULT_FREQUENCY,
+            gradients,
         }
     }
 }
@@ -110,7 +137,20 @@ impl Default for SuperSimplex {
     }
 }
 
-impl Seedable for SuperSimplex {
+impl<G> SuperSimplex<G> {
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+}
+
+/// The normalization constants are the inverse of the largest possible output
+/// for the default gradients, which this only holds for.
+impl NoiseBounds for SuperSimplex {
+    const MIN: f64 = -1.0;
+    const MAX: f64 = 1.0;
+}
+
+impl<G> Seedable for SuperSimplex<G> {
     /// Sets the seed value for Super Simplex noise
     fn set_seed(self, seed: u32) -> Self {
         // If the new seed is the same as the current seed, just return self.
@@ -122,6 +162,7 @@ impl Seedable for SuperSimplex {
         Self {
             seed,
             perm_table: PermutationTable::new(seed),
+            ..self
         }
     }
 
@@ -131,8 +172,9 @@ impl Seedable for SuperSimplex {
 }
 
 /// 2-dimensional Super Simplex noise
-impl NoiseFn<[f64; 2]> for SuperSimplex {
+impl<G: GradientTable> NoiseFn<[f64; 2]> for SuperSimplex<G> {
     fn get(&self, point: [f64; 2]) -> f64 {
+        let point = math::mul2(point, self.frequency);
         let mut value = 0.0;
 
         // Transform point from real space to simplex space
@@ -140,12 +182,12 @@ impl NoiseFn<[f64; 2]> for SuperSimplex {
         let simplex_point = math::map2(point, |v| v + to_simplex_offset);
 
         // Get base point of simplex and barycentric coordinates in simplex space
-        let simplex_base_point = math::map2(simplex_point, f64::floor);
+        let simplex_base_point = math::map2(simplex_point, float::floor);
         let simplex_base_point_i = math::to_isize2(simplex_base_point);
         let simplex_rel_coords = math::sub2(simplex_point, simplex_base_point);
 
         // Create index to lookup table from barycentric coordinates
-        let region_sum = math::fold2(simplex_rel_coords, Add::add).floor();
+        let region_sum = float::floor(math::fold2(simplex_rel_coords, Add::add));
         let index = ((region_sum >= 1.0) as usize) << 2
             | ((simplex_rel_coords[0] - simplex_rel_coords[1] * 0.5 + 1.0 - region_sum * 0.5 >= 1.0)
                 as usize)
@@ -162,9 +204,10 @@ impl NoiseFn<[f64; 2]> for SuperSimplex {
             let dpos = math::add2(real_rel_coords, math::cast2(lattice_lookup.1));
             let attn = (2.0 / 3.0) - math::dot2(dpos, dpos);
             if attn > 0.0 {
-                let lattice_point = math::add2(simplex_base_point_i, math::cast2(lattice_lookup.0));
-                let gradient = gradient::get2(self.perm_table.get2(lattice_point));
-                value += attn.powi(4) * math::dot2(gradient, dpos);
+                let lattice_point =
+                    math::wrapping_add2(simplex_base_point_i, math::cast2(lattice_lookup.0));
+                let gradient = self.gradients.get2(self.perm_table.get2(lattice_point));
+                value += float::powi(attn, 4) * math::dot2(gradient, dpos);
             }
         }
 
@@ -173,8 +216,9 @@ impl NoiseFn<[f64; 2]> for SuperSimplex {
 }
 
 /// 3-dimensional Super Simplex noise
-impl NoiseFn<[f64; 3]> for SuperSimplex {
+impl<G: GradientTable> NoiseFn<[f64; 3]> for SuperSimplex<G> {
     fn get(&self, point: [f64; 3]) -> f64 {
+        let point = math::mul3(point, self.frequency);
         let mut value = 0.0;
 
         // Transform point from real space to simplex space
@@ -183,10 +227,10 @@ impl NoiseFn<[f64; 3]> for SuperSimplex {
         let second_simplex_point = math::map3(simplex_point, |v| v + 512.5);
 
         // Get base point of simplex and barycentric coordinates in simplex space
-        let simplex_base_point = math::map3(simplex_point, f64::floor);
+        let simplex_base_point = math::map3(simplex_point, float::floor);
         let simplex_base_point_i = math::to_isize3(simplex_base_point);
         let simplex_rel_coords = math::sub3(simplex_point, simplex_base_point);
-        let second_simplex_base_point = math::map3(second_simplex_point, f64::floor);
+        let second_simplex_base_point = math::map3(second_simplex_point, float::floor);
         let second_simplex_base_point_i = math::to_isize3(second_simplex_base_point);
         let second_simplex_rel_coords = math::sub3(second_simplex_point, second_simplex_base_point);
 
@@ -227,9 +271,10 @@ impl NoiseFn<[f64; 3]> for SuperSimplex {
             let dpos = math::sub3(simplex_rel_coords, math::cast3(lattice_lookup));
             let attn = 0.75 - math::dot3(dpos, dpos);
             if attn > 0.0 {
-                let lattice_point = math::add3(simplex_base_point_i, math::cast3(lattice_lookup));
-                let gradient = gradient::get3(self.perm_table.get3(lattice_point));
-                value += attn.powi(4) * math::dot3(gradient, dpos);
+                let lattice_point =
+                    math::wrapping_add3(simplex_base_point_i, math::cast3(lattice_lookup));
+                let gradient = self.gradients.get3(self.perm_table.get3(lattice_point));
+                value += float::powi(attn, 4) * math::dot3(gradient, dpos);
             }
         }
 
@@ -239,12 +284,23 @@ impl NoiseFn<[f64; 3]> for SuperSimplex {
             let attn = 0.75 - math::dot3(dpos, dpos);
             if attn > 0.0 {
                 let lattice_point =
-                    math::add3(second_simplex_base_point_i, math::cast3(lattice_lookup));
-                let gradient = gradient::get3(self.perm_table.get3(lattice_point));
-                value += attn.powi(4) * math::dot3(gradient, dpos);
+                    math::wrapping_add3(second_simplex_base_point_i, math::cast3(lattice_lookup));
+                let gradient = self.gradients.get3(self.perm_table.get3(lattice_point));
+                value += float::powi(attn, 4) * math::dot3(gradient, dpos);
             }
         }
 
         value * NORM_CONSTANT_3D
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::assert_doubles_feature_count;
+
+    #[test]
+    fn doubling_frequency_doubles_feature_count() {
+        assert_doubles_feature_count(&SuperSimplex::new(), &SuperSimplex::with_frequency(2.0));
+    }
+}
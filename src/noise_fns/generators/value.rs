@@ -1,34 +1,68 @@
 use crate::{
-    math::{self, interpolate},
-    noise_fns::{NoiseFn, Seedable},
+    math::{self, float, interpolate},
+    noise_fns::{NoiseBounds, NoiseFn, Seedable},
     permutationtable::PermutationTable,
 };
 
 /// Noise function that outputs 2/3/4-dimensional Value noise.
+///
+/// The permutation table holds 256 entries by default, so the output repeats
+/// every 256 units along each axis. Use `Value::with_table_size` for a
+/// different period.
 #[derive(Clone, Copy, Debug)]
-pub struct Value {
+pub struct Value<const N: usize = 256> {
+    /// The number of cycles per unit length that the noise function outputs.
+    /// Default is 1.0.
+    pub frequency: f64,
+
     seed: u32,
-    perm_table: PermutationTable,
+    perm_table: PermutationTable<N>,
 }
 
 impl Value {
     pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
 
     pub fn new() -> Self {
-        Self {
+        Self::with_table_size()
+    }
+
+    /// Creates a noise function with the given frequency.
+    pub fn with_frequency(frequency: f64) -> Self {
+        Self::new().set_frequency(frequency)
+    }
+
+    /// Creates a noise function whose permutation table holds `N` entries,
+    /// so that its output repeats every `N` units along each axis at a
+    /// frequency of 1.0. `N` must be a power of two between 64 and 65536.
+    pub fn with_table_size<const N: usize>() -> Value<N> {
+        Value {
             seed: Self::DEFAULT_SEED,
             perm_table: PermutationTable::new(Self::DEFAULT_SEED),
+            frequency: Self::DEFAULT_FREQUENCY,
         }
     }
 }
 
+impl<const N: usize> Value<N> {
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+}
+
 impl Default for Value {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl Seedable for Value {
+/// The output interpolates between lattice values within [-1,1].
+impl<const N: usize> NoiseBounds for Value<N> {
+    const MIN: f64 = -1.0;
+    const MAX: f64 = 1.0;
+}
+
+impl<const N: usize> Seedable for Value<N> {
     /// Sets the seed value for Value noise
     fn set_seed(self, seed: u32) -> Self {
         // If the new seed is the same as the current seed, just return self.
@@ -40,6 +74,7 @@ impl Seedable for Value {
         Self {
             seed,
             perm_table: PermutationTable::new(seed),
+            ..self
         }
     }
 
@@ -49,15 +84,17 @@ impl Seedable for Value {
 }
 
 /// 2-dimensional value noise
-impl NoiseFn<[f64; 2]> for Value {
+impl<const N: usize> NoiseFn<[f64; 2]> for Value<N> {
     fn get(&self, point: [f64; 2]) -> f64 {
-        fn get(perm_table: &PermutationTable, corner: [isize; 2]) -> f64 {
-            perm_table.get2(corner) as f64 / 255.0
+        fn get<const N: usize>(perm_table: &PermutationTable<N>, corner: [isize; 2]) -> f64 {
+            perm_table.get2(corner) as f64 / (N - 1) as f64
         }
 
-        let floored = math::map2(point, f64::floor);
+        let point = math::mul2(point, self.frequency);
+
+        let floored = math::map2(point, float::floor);
         let near_corner = math::to_isize2(floored);
-        let far_corner = math::add2(near_corner, math::one2());
+        let far_corner = math::wrapping_add2(near_corner, math::one2());
         let weight = math::map2(math::sub2(point, floored), interpolate::s_curve5);
 
         let f00 = get(&self.perm_table, [near_corner[0], near_corner[1]]);
@@ -74,15 +111,17 @@ impl NoiseFn<[f64; 2]> for Value {
 }
 
 /// 3-dimensional value noise
-impl NoiseFn<[f64; 3]> for Value {
+impl<const N: usize> NoiseFn<[f64; 3]> for Value<N> {
     fn get(&self, point: [f64; 3]) -> f64 {
-        fn get(perm_table: &PermutationTable, corner: [isize; 3]) -> f64 {
-            perm_table.get3(corner) as f64 / 255.0
+        fn get<const N: usize>(perm_table: &PermutationTable<N>, corner: [isize; 3]) -> f64 {
+            perm_table.get3(corner) as f64 / (N - 1) as f64
         }
 
-        let floored = math::map3(point, f64::floor);
+        let point = math::mul3(point, self.frequency);
+
+        let floored = math::map3(point, float::floor);
         let near_corner = math::to_isize3(floored);
-        let far_corner = math::add3(near_corner, math::one3());
+        let far_corner = math::wrapping_add3(near_corner, math::one3());
         let weight = math::map3(math::sub3(point, floored), interpolate::s_curve5);
 
         let f000 = get(
@@ -131,15 +170,17 @@ impl NoiseFn<[f64; 3]> for Value {
 }
 
 /// 4-dimensional value noise
-impl NoiseFn<[f64; 4]> for Value {
+impl<const N: usize> NoiseFn<[f64; 4]> for Value<N> {
     fn get(&self, point: [f64; 4]) -> f64 {
-        fn get(perm_table: &PermutationTable, corner: [isize; 4]) -> f64 {
-            perm_table.get4(corner) as f64 / 255.0
+        fn get<const N: usize>(perm_table: &PermutationTable<N>, corner: [isize; 4]) -> f64 {
+            perm_table.get4(corner) as f64 / (N - 1) as f64
         }
 
-        let floored = math::map4(point, f64::floor);
+        let point = math::mul4(point, self.frequency);
+
+        let floored = math::map4(point, float::floor);
         let near_corner = math::to_isize4(floored);
-        let far_corner = math::add4(near_corner, math::one4());
+        let far_corner = math::wrapping_add4(near_corner, math::one4());
         let weight = math::map4(math::sub4(point, floored), interpolate::s_curve5);
 
         let f0000 = get(
@@ -251,3 +292,30 @@ impl NoiseFn<[f64; 4]> for Value {
         d * 2.0 - 1.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::assert_doubles_feature_count;
+
+    #[test]
+    fn doubling_frequency_doubles_feature_count() {
+        assert_doubles_feature_count(&Value::new(), &Value::with_frequency(2.0));
+    }
+
+    #[test]
+    fn output_repeats_at_table_size() {
+        let value = Value::with_table_size::<128>();
+        let large = Value::with_table_size::<4096>().set_seed(2);
+
+        for i in 0..200 {
+            let point = [i as f64 * 0.53 - 40.0, i as f64 * 0.17, -0.4];
+            let expected = value.get(point);
+
+            assert!((value.get([point[0], point[1] + 128.0, point[2]]) - expected).abs() < 1e-9);
+
+            // Entries of larger tables are scaled to the same range.
+            assert!((-1.0..=1.0).contains(&large.get(point)));
+        }
+    }
+}
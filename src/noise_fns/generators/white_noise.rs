@@ -0,0 +1,200 @@
+use crate::{
+    math::{self, float},
+    noise_fns::{NoiseBounds, NoiseFn, Seedable},
+    permutationtable::PermutationTable,
+};
+
+/// Noise function that outputs 2/3/4-dimensional white noise.
+///
+/// Every integer lattice cell is given an uncorrelated random value, taken
+/// from the permutation table, which is held for the whole cell. There is no
+/// interpolation between cells, so the output is discontinuous at every cell
+/// boundary, which makes it suited to stippling and dithering rather than to
+/// smooth terrain.
+///
+/// The permutation table holds 256 entries by default, so there are 256
+/// distinct output values and the output repeats every 256 cells along each
+/// axis. Use `WhiteNoise::with_table_size` for a different period.
+#[derive(Clone, Copy, Debug)]
+pub struct WhiteNoise<const N: usize = 256> {
+    /// The number of cells per unit length that the noise function outputs.
+    /// Default is 1.0.
+    pub frequency: f64,
+
+    seed: u32,
+    perm_table: PermutationTable<N>,
+}
+
+impl WhiteNoise {
+    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+
+    pub fn new() -> Self {
+        Self::with_table_size()
+    }
+
+    /// Creates a noise function with the given frequency.
+    pub fn with_frequency(frequency: f64) -> Self {
+        Self::new().set_frequency(frequency)
+    }
+
+    /// Creates a noise function whose permutation table holds `N` entries,
+    /// so that its output repeats every `N` units along each axis at a
+    /// frequency of 1.0. `N` must be a power of two between 64 and 65536.
+    pub fn with_table_size<const N: usize>() -> WhiteNoise<N> {
+        WhiteNoise {
+            seed: Self::DEFAULT_SEED,
+            perm_table: PermutationTable::new(Self::DEFAULT_SEED),
+            frequency: Self::DEFAULT_FREQUENCY,
+        }
+    }
+}
+
+impl<const N: usize> WhiteNoise<N> {
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+}
+
+impl Default for WhiteNoise {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Each cell value is a permutation table entry mapped into [-1,1].
+impl<const N: usize> NoiseBounds for WhiteNoise<N> {
+    const MIN: f64 = -1.0;
+    const MAX: f64 = 1.0;
+}
+
+impl<const N: usize> Seedable for WhiteNoise<N> {
+    /// Sets the seed value for white noise
+    fn set_seed(self, seed: u32) -> Self {
+        // If the new seed is the same as the current seed, just return self.
+        if self.seed == seed {
+            return self;
+        }
+
+        // Otherwise, regenerate the permutation table based on the new seed.
+        Self {
+            seed,
+            perm_table: PermutationTable::new(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed
+    }
+}
+
+// Maps an entry of a permutation table of size `N` from [0,N-1] into [-1,1].
+#[inline]
+fn to_output<const N: usize>(hash: usize) -> f64 {
+    hash as f64 / (N - 1) as f64 * 2.0 - 1.0
+}
+
+/// 2-dimensional white noise
+impl<const N: usize> NoiseFn<[f64; 2]> for WhiteNoise<N> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let point = math::mul2(point, self.frequency);
+        let cell = math::to_isize2(math::map2(point, float::floor));
+
+        to_output::<N>(self.perm_table.get2(cell))
+    }
+}
+
+/// 3-dimensional white noise
+impl<const N: usize> NoiseFn<[f64; 3]> for WhiteNoise<N> {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        let point = math::mul3(point, self.frequency);
+        let cell = math::to_isize3(math::map3(point, float::floor));
+
+        to_output::<N>(self.perm_table.get3(cell))
+    }
+}
+
+/// 4-dimensional white noise
+impl<const N: usize> NoiseFn<[f64; 4]> for WhiteNoise<N> {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        let point = math::mul4(point, self.frequency);
+        let cell = math::to_isize4(math::map4(point, float::floor));
+
+        to_output::<N>(self.perm_table.get4(cell))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_cell_returns_same_value() {
+        let noise = WhiteNoise::new().set_seed(7);
+
+        for x in -4..4 {
+            for y in -4..4 {
+                let (x, y) = (x as f64, y as f64);
+                let expected = noise.get([x, y]);
+
+                for &(dx, dy) in &[(0.0, 0.0), (0.25, 0.75), (0.5, 0.5), (0.999, 0.001)] {
+                    assert_eq!(noise.get([x + dx, y + dy]), expected);
+                }
+                assert_eq!(noise.get([x + 0.3, y + 0.6, 2.1]), noise.get([x, y, 2.0]));
+            }
+        }
+
+        // The same seed always rebuilds the same values.
+        let rebuilt = WhiteNoise::new().set_seed(7);
+        assert_eq!(noise.get([3.5, -1.5]), rebuilt.get([3.5, -1.5]));
+    }
+
+    #[test]
+    fn distribution_is_roughly_uniform() {
+        const BUCKETS: usize = 8;
+        const SIDE: isize = 128;
+
+        let noise = WhiteNoise::new().set_seed(3);
+        let mut counts = [0usize; BUCKETS];
+
+        for x in 0..SIDE {
+            for y in 0..SIDE {
+                let value = noise.get([x as f64 + 0.5, y as f64 + 0.5, 0.5]);
+                assert!((-1.0..=1.0).contains(&value));
+
+                let bucket = ((value + 1.0) / 2.0 * BUCKETS as f64) as usize;
+                counts[bucket.min(BUCKETS - 1)] += 1;
+            }
+        }
+
+        let expected = (SIDE * SIDE) as usize / BUCKETS;
+        for &count in &counts {
+            assert!(
+                count > expected * 9 / 10 && count < expected * 11 / 10,
+                "bucket counts {:?} are not roughly uniform",
+                counts
+            );
+        }
+    }
+
+    #[test]
+    fn output_repeats_at_table_size() {
+        let noise = WhiteNoise::with_table_size::<1024>().set_seed(5);
+        let mut extremes = (1.0f64, -1.0f64);
+
+        for x in 0..64 {
+            for y in 0..64 {
+                let (x, y) = (x as f64 * 7.0, y as f64 * 3.0);
+                let value = noise.get([x, y]);
+
+                assert_eq!(noise.get([x + 1024.0, y - 2048.0]), value);
+                extremes = (extremes.0.min(value), extremes.1.max(value));
+            }
+        }
+
+        // Entries of larger tables are scaled to the same range.
+        assert!(extremes.0 >= -1.0 && extremes.0 < -0.99);
+        assert!(extremes.1 <= 1.0 && extremes.1 > 0.99);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    math,
+    math::{self, float},
     noise_fns::{NoiseFn, Seedable},
     permutationtable::PermutationTable,
 };
@@ -47,6 +47,11 @@ impl Worley {
         }
     }
 
+    /// Creates a noise function with the given frequency.
+    pub fn with_frequency(frequency: f64) -> Self {
+        Self::new().set_frequency(frequency)
+    }
+
     /// Sets the range function used by the Worley cells.
     pub fn set_range_function(self, range_function: RangeFunction) -> Self {
         Self {
@@ -130,6 +135,26 @@ pub enum RangeFunction {
     Quadratic,
 }
 
+/// Feature point of a Worley cell, as returned by
+/// `Worley::get_feature_point_2d` and `Worley::get_feature_point_3d`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeaturePoint<T> {
+    /// Position of the feature point, in the same coordinates as the input
+    /// point.
+    pub position: T,
+
+    /// Value hashed from the cell of the feature point, within [-1, 1].
+    ///
+    /// Unlike the output of `Worley::get`, this is not scaled by the
+    /// displacement.
+    pub cell_value: f64,
+}
+
+/// Maps the hash of a cell onto [-1, 1].
+fn cell_value(hash: usize) -> f64 {
+    hash as f64 / 255.0 * 2.0 - 1.0
+}
+
 fn calculate_range(range_function: RangeFunction, p1: &[f64], p2: &[f64]) -> f64 {
     match range_function {
         RangeFunction::Euclidean => range_euclidean(p1, p2),
@@ -141,12 +166,7 @@ fn calculate_range(range_function: RangeFunction, p1: &[f64], p2: &[f64]) -> f64
 }
 
 fn range_euclidean(p1: &[f64], p2: &[f64]) -> f64 {
-    p1.iter()
-        .zip(p2.iter())
-        .map(|(a, b)| *a - *b)
-        .map(|a| a * a)
-        .fold(0.0, |acc, x| acc + x)
-        .sqrt()
+    float::sqrt(range_euclidean_squared(p1, p2))
 }
 
 fn range_euclidean_squared(p1: &[f64], p2: &[f64]) -> f64 {
@@ -170,43 +190,59 @@ fn range_chebyshev(p1: &[f64], p2: &[f64]) -> f64 {
         .zip(p2.iter())
         .map(|(a, b)| *a - *b)
         .map(|a| a.abs())
-        .fold(std::f64::MIN, |a, b| a.max(b))
+        .fold(f64::MIN, |a, b| a.max(b))
 }
 
 fn range_quadratic(p1: &[f64], p2: &[f64]) -> f64 {
-    let temp: Vec<f64> = p1.iter().zip(p2.iter()).map(|(a, b)| *a - *b).collect();
+    let difference = |i: usize| p1[i] - p2[i];
 
     let mut result = 0.0;
 
-    for i in &temp {
-        for j in &temp {
-            result += *i * *j;
+    for i in 0..p1.len() {
+        for j in 0..p1.len() {
+            result += difference(i) * difference(j);
         }
     }
 
     result
 }
 
-impl NoiseFn<[f64; 2]> for Worley {
-    fn get(&self, point: [f64; 2]) -> f64 {
+impl Worley {
+    /// Returns the feature point nearest to `point`, whose cell `point` is
+    /// in.
+    ///
+    /// Every point in the same cell shares the same feature point, so the
+    /// cell value can be used to color each cell uniquely.
+    pub fn get_feature_point_2d(&self, point: [f64; 2]) -> FeaturePoint<[f64; 2]> {
+        let (seed_cell, seed_point, _) = self.nearest_2d(point);
+
+        FeaturePoint {
+            position: math::map2(seed_point, |x| x / self.frequency),
+            cell_value: cell_value(self.perm_table.get2(seed_cell)),
+        }
+    }
+
+    /// Finds the feature point nearest to `point`, returning its cell, its
+    /// position in frequency-scaled space, and its range from `point`.
+    fn nearest_2d(&self, point: [f64; 2]) -> ([isize; 2], [f64; 2], f64) {
         fn get_point(perm_table: &PermutationTable, whole: [isize; 2]) -> [f64; 2] {
             math::add2(get_vec2(perm_table.get2(whole)), math::to_f64_2(whole))
         }
 
         let point = &math::mul2(point, self.frequency);
 
-        let cell = math::map2(*point, f64::floor);
+        let cell = math::map2(*point, float::floor);
         let whole = math::to_isize2(cell);
         let frac = math::sub2(*point, cell);
 
         let x_half = frac[0] > 0.5;
         let y_half = frac[1] > 0.5;
 
-        let near = [whole[0] + (x_half as isize), whole[1] + (y_half as isize)];
-        let far = [whole[0] + (!x_half as isize), whole[1] + (!y_half as isize)];
+        let near = math::wrapping_add2(whole, [x_half as isize, y_half as isize]);
+        let far = math::wrapping_add2(whole, [!x_half as isize, !y_half as isize]);
 
         let mut seed_cell = near;
-        let seed_point = get_point(&self.perm_table, near);
+        let mut seed_point = get_point(&self.perm_table, near);
         let mut range = calculate_range(self.range_function, point, &seed_point);
 
         let x_range = (0.5 - frac[0]) * (0.5 - frac[0]); // x-distance squared to center line
@@ -220,6 +256,7 @@ impl NoiseFn<[f64; 2]> for Worley {
                     if cur_range < range {
                         range = cur_range;
                         seed_cell = [$x, $y];
+                        seed_point = cur_point;
                     }
                 }
             }
@@ -237,6 +274,14 @@ impl NoiseFn<[f64; 2]> for Worley {
             test_point![far[0], far[1]];
         }
 
+        (seed_cell, seed_point, range)
+    }
+}
+
+impl NoiseFn<[f64; 2]> for Worley {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let (seed_cell, _, range) = self.nearest_2d(point);
+
         let value = if self.enable_range {
             range
         } else {
@@ -250,7 +295,7 @@ impl NoiseFn<[f64; 2]> for Worley {
 #[rustfmt::skip]
 fn get_vec2(index: usize) -> [f64; 2] {
     let length = ((index & 0xF8) >> 3) as f64 * 0.5 / 31.0;
-    let diag = length * std::f64::consts::FRAC_1_SQRT_2;
+    let diag = length * core::f64::consts::FRAC_1_SQRT_2;
 
     match index & 0x07 {
         0 => [   diag,    diag],
@@ -265,15 +310,28 @@ fn get_vec2(index: usize) -> [f64; 2] {
     }
 }
 
-impl NoiseFn<[f64; 3]> for Worley {
-    fn get(&self, point: [f64; 3]) -> f64 {
+impl Worley {
+    /// Returns the feature point nearest to `point`, whose cell `point` is
+    /// in.
+    pub fn get_feature_point_3d(&self, point: [f64; 3]) -> FeaturePoint<[f64; 3]> {
+        let (seed_cell, seed_point, _) = self.nearest_3d(point);
+
+        FeaturePoint {
+            position: math::map3(seed_point, |x| x / self.frequency),
+            cell_value: cell_value(self.perm_table.get3(seed_cell)),
+        }
+    }
+
+    /// Finds the feature point nearest to `point`, returning its cell, its
+    /// position in frequency-scaled space, and its range from `point`.
+    fn nearest_3d(&self, point: [f64; 3]) -> ([isize; 3], [f64; 3], f64) {
         fn get_point(perm_table: &PermutationTable, whole: [isize; 3]) -> [f64; 3] {
             math::add3(get_vec3(perm_table.get3(whole)), math::to_f64_3(whole))
         }
 
         let point = &math::mul3(point, self.frequency);
 
-        let cell = math::map3(*point, f64::floor);
+        let cell = math::map3(*point, float::floor);
         let whole = math::to_isize3(cell);
         let frac = math::sub3(*point, cell);
 
@@ -281,19 +339,14 @@ impl NoiseFn<[f64; 3]> for Worley {
         let y_half = frac[1] > 0.5;
         let z_half = frac[2] > 0.5;
 
-        let near = [
-            whole[0] + (x_half as isize),
-            whole[1] + (y_half as isize),
-            whole[2] + (z_half as isize),
-        ];
-        let far = [
-            whole[0] + (!x_half as isize),
-            whole[1] + (!y_half as isize),
-            whole[2] + (!z_half as isize),
-        ];
+        let near = math::wrapping_add3(whole, [x_half as isize, y_half as isize, z_half as isize]);
+        let far = math::wrapping_add3(
+            whole,
+            [!x_half as isize, !y_half as isize, !z_half as isize],
+        );
 
         let mut seed_cell = near;
-        let seed_point = get_point(&self.perm_table, near);
+        let mut seed_point = get_point(&self.perm_table, near);
         let mut range = calculate_range(self.range_function, point, &seed_point);
 
         let x_range = (0.5 - frac[0]) * (0.5 - frac[0]); // x-distance squared to center line
@@ -308,6 +361,7 @@ impl NoiseFn<[f64; 3]> for Worley {
                     if cur_range < range {
                         range = cur_range;
                         seed_cell = [$x, $y, $z];
+                        seed_point = cur_point;
                     }
                 }
             }
@@ -337,6 +391,14 @@ impl NoiseFn<[f64; 3]> for Worley {
             test_point![far[0], far[1], far[2]];
         }
 
+        (seed_cell, seed_point, range)
+    }
+}
+
+impl NoiseFn<[f64; 3]> for Worley {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        let (seed_cell, _, range) = self.nearest_3d(point);
+
         let value = if self.enable_range {
             range
         } else {
@@ -350,7 +412,7 @@ impl NoiseFn<[f64; 3]> for Worley {
 #[rustfmt::skip]
 fn get_vec3(index: usize) -> [f64; 3] {
     let length = ((index & 0xE0) >> 5) as f64 * 0.5 / 7.0;
-    let diag = length * std::f64::consts::FRAC_1_SQRT_2;
+    let diag = length * core::f64::consts::FRAC_1_SQRT_2;
 
     match index % 18 {
         0  => [   diag,    diag,     0.0],
@@ -384,7 +446,7 @@ impl NoiseFn<[f64; 4]> for Worley {
 
         let point = &math::mul4(point, self.frequency);
 
-        let cell = math::map4(*point, f64::floor);
+        let cell = math::map4(*point, float::floor);
         let whole = math::to_isize4(cell);
         let frac = math::sub4(*point, cell);
 
@@ -393,18 +455,24 @@ impl NoiseFn<[f64; 4]> for Worley {
         let z_half = frac[2] > 0.5;
         let w_half = frac[3] > 0.5;
 
-        let near = [
-            whole[0] + (x_half as isize),
-            whole[1] + (y_half as isize),
-            whole[2] + (z_half as isize),
-            whole[3] + (w_half as isize),
-        ];
-        let far = [
-            whole[0] + (!x_half as isize),
-            whole[1] + (!y_half as isize),
-            whole[2] + (!z_half as isize),
-            whole[3] + (!w_half as isize),
-        ];
+        let near = math::wrapping_add4(
+            whole,
+            [
+                x_half as isize,
+                y_half as isize,
+                z_half as isize,
+                w_half as isize,
+            ],
+        );
+        let far = math::wrapping_add4(
+            whole,
+            [
+                !x_half as isize,
+                !y_half as isize,
+                !z_half as isize,
+                !w_half as isize,
+            ],
+        );
 
         let mut seed_cell = near;
         let seed_point = get_point(&self.perm_table, near);
@@ -528,3 +596,50 @@ fn get_vec4(index: usize) -> [f64; 4] {
         _ => panic!("Attempt to access 4D gradient {} of 32", index % 32),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cell_value_is_constant_within_cells() {
+        let worley = Worley::new().set_seed(3).set_frequency(2.0);
+
+        let mut boundaries = 0;
+        let mut previous = worley.get_feature_point_2d([0.0, 0.3]);
+
+        for i in 1..2000 {
+            let point = [i as f64 * 0.0025, 0.3];
+            let feature = worley.get_feature_point_2d(point);
+
+            // The default output is the same cell value, scaled by the
+            // displacement of 1.0.
+            assert_eq!(worley.get(point), feature.cell_value);
+
+            if feature.position == previous.position {
+                assert_eq!(feature.cell_value, previous.cell_value);
+            } else {
+                assert_ne!(feature.cell_value, previous.cell_value);
+                boundaries += 1;
+            }
+
+            previous = feature;
+        }
+
+        assert!(boundaries > 5, "only {} boundaries crossed", boundaries);
+    }
+
+    #[test]
+    fn feature_point_is_nearest() {
+        let worley = Worley::new().set_seed(3).set_frequency(1.5);
+
+        // A point at a feature point belongs to that feature point's cell.
+        for i in 0..100 {
+            let point = [i as f64 * 0.137 - 5.0, i as f64 * -0.291, 0.5];
+            let feature = worley.get_feature_point_3d(point);
+
+            assert_eq!(worley.get_feature_point_3d(feature.position), feature);
+            assert!((-1.0..=1.0).contains(&feature.cell_value));
+        }
+    }
+}
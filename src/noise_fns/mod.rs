@@ -1,17 +1,36 @@
+#[cfg(feature = "std")]
 pub use self::cache::*;
+#[cfg(feature = "lru")]
+pub use self::cached_noise::*;
+#[cfg(feature = "std")]
 pub use self::combiners::*;
 pub use self::generators::*;
+#[cfg(feature = "std")]
+#[cfg(feature = "std")]
 pub use self::modifiers::*;
+#[cfg(feature = "std")]
 pub use self::selectors::*;
+#[cfg(feature = "std")]
 pub use self::transformers::*;
 
+#[cfg(feature = "std")]
 mod cache;
+#[cfg(feature = "lru")]
+mod cached_noise;
+#[cfg(feature = "std")]
 mod combiners;
 mod generators;
+#[cfg(feature = "std")]
+#[cfg(feature = "std")]
 mod modifiers;
+#[cfg(feature = "std")]
 mod selectors;
+#[cfg(feature = "std")]
 mod transformers;
 
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, sync::Arc};
+
 /// Base trait for noise functions.
 ///
 /// A noise function is a object that calculates and outputs a value given a
@@ -21,19 +40,83 @@ mod transformers;
 /// value. Some of these methods include:
 ///
 /// * Calculating a value using a coherent-noise function or some other
-///     mathematical function.
+///   mathematical function.
 /// * Mathematically changing the output value from another noise function
-///     in various ways.
+///   in various ways.
 /// * Combining the output values from two noise functions in various ways.
 pub trait NoiseFn<T> {
     fn get(&self, point: T) -> f64;
+
+    /// Calculates the output value for each point in `points`, writing the
+    /// result into the matching element of `output`.
+    ///
+    /// The default implementation calls `get` for each point, but noise
+    /// functions may override it with a faster batched implementation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points` and `output` have different lengths.
+    fn get_batch(&self, points: &[T], output: &mut [f64])
+    where
+        T: Copy,
+    {
+        assert_eq!(
+            points.len(),
+            output.len(),
+            "points and output must have the same length"
+        );
+
+        for (point, value) in points.iter().zip(output.iter_mut()) {
+            *value = self.get(*point);
+        }
+    }
 }
 
-impl<'a, T, M: NoiseFn<T>> NoiseFn<T> for &'a M {
+impl<T, M: NoiseFn<T> + ?Sized> NoiseFn<T> for &M {
     #[inline]
     fn get(&self, point: T) -> f64 {
         M::get(*self, point)
     }
+
+    #[inline]
+    fn get_batch(&self, points: &[T], output: &mut [f64])
+    where
+        T: Copy,
+    {
+        M::get_batch(*self, points, output)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, M: NoiseFn<T> + ?Sized> NoiseFn<T> for Box<M> {
+    #[inline]
+    fn get(&self, point: T) -> f64 {
+        M::get(self, point)
+    }
+
+    #[inline]
+    fn get_batch(&self, points: &[T], output: &mut [f64])
+    where
+        T: Copy,
+    {
+        M::get_batch(self, points, output)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, M: NoiseFn<T> + ?Sized> NoiseFn<T> for Arc<M> {
+    #[inline]
+    fn get(&self, point: T) -> f64 {
+        M::get(self, point)
+    }
+
+    #[inline]
+    fn get_batch(&self, points: &[T], output: &mut [f64])
+    where
+        T: Copy,
+    {
+        M::get_batch(self, points, output)
+    }
 }
 
 /// Trait for functions that require a seed before generating their values
@@ -44,3 +127,153 @@ pub trait Seedable {
     /// Getter to retrieve the seed from the function
     fn seed(&self) -> u32;
 }
+
+/// Trait for noise functions whose output is guaranteed to stay within a
+/// fixed range, for any input value and any seed.
+///
+/// The bounds are derived from the construction of each noise function, so
+/// that its output can be fed into functions expecting a known range without
+/// clamping it first. The output is not guaranteed to reach the bounds.
+///
+/// Noise functions whose range depends on their runtime configuration don't
+/// implement this trait, since the bounds are associated constants:
+///
+/// - `Constant` outputs its `value` field.
+/// - `Worley` scales its output by `displacement`, or by the distance to the
+///   nearest seed point when `enable_range` is set, which depends on the
+///   `range_function`.
+/// - `Billow`, `BasicMulti` and `HybridMulti` only rescale their output by the
+///   largest value the octaves can accumulate when `normalize` is set, and
+///   otherwise scale it by a constant tuned for the default parameters.
+pub trait NoiseBounds {
+    /// The smallest value the noise function can output.
+    const MIN: f64;
+
+    /// The largest value the noise function can output.
+    const MAX: f64;
+
+    /// Returns the smallest and largest values the noise function can output.
+    fn range() -> (f64, f64) {
+        (Self::MIN, Self::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn references_and_boxes_are_noise_fns() {
+        let simplex = Simplex::new();
+        let other = Simplex::new().set_seed(1);
+        let (a, b) = (&simplex, &other);
+        let sum = Add::new(&a, &b);
+
+        let boxed: Box<dyn NoiseFn<[f64; 2]>> = Box::new(Simplex::new());
+        let shared = Arc::new(Simplex::new().set_seed(1));
+        let boxed_sum = Add::new(&boxed, &shared);
+
+        for i in 0..20 {
+            let point = [i as f64 * 0.31, i as f64 * -0.17];
+            let expected = simplex.get(point) + other.get(point);
+
+            assert_eq!(sum.get(point), expected);
+            assert_eq!(boxed_sum.get(point), expected);
+        }
+    }
+
+    #[test]
+    fn get_batch_matches_get() {
+        let perlin = Perlin::new();
+        let points: Vec<[f64; 3]> = (0..32)
+            .map(|i| [i as f64 * 0.37, i as f64 * -0.21, 1.5])
+            .collect();
+        let mut output = vec![0.0; points.len()];
+
+        perlin.get_batch(&points, &mut output);
+
+        for (point, value) in points.iter().zip(output.iter()) {
+            assert_eq!(perlin.get(*point), *value);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_batch_length_mismatch() {
+        let mut output = [0.0; 3];
+        Perlin::new().get_batch(&[[0.0, 0.0]; 4], &mut output);
+    }
+
+    fn random_points(samples: usize) -> impl Iterator<Item = [f64; 4]> {
+        let mut rng = XorShiftRng::seed_from_u64(17);
+
+        (0..samples).map(move |_| {
+            [
+                rng.gen_range(-1000.0, 1000.0),
+                rng.gen_range(-1000.0, 1000.0),
+                rng.gen_range(-1000.0, 1000.0),
+                rng.gen_range(-1000.0, 1000.0),
+            ]
+        })
+    }
+
+    fn assert_in_range<N: NoiseBounds>(value: f64, point: [f64; 4]) {
+        let (min, max) = N::range();
+
+        assert!(
+            min <= value && value <= max,
+            "{} is outside [{}, {}] at {:?}",
+            value,
+            min,
+            max,
+            point
+        );
+    }
+
+    // Evaluates `noise` in 2 and 3 dimensions at `samples` random points, and
+    // checks that every output value lies within its declared bounds.
+    fn assert_within_bounds_3d<N>(noise: &N, samples: usize)
+    where
+        N: NoiseBounds + NoiseFn<[f64; 2]> + NoiseFn<[f64; 3]>,
+    {
+        for point in random_points(samples) {
+            assert_in_range::<N>(noise.get([point[0], point[1]]), point);
+            assert_in_range::<N>(noise.get([point[0], point[1], point[2]]), point);
+        }
+    }
+
+    // Like `assert_within_bounds_3d`, but also evaluates `noise` in 4
+    // dimensions.
+    fn assert_within_bounds<N>(noise: &N, samples: usize)
+    where
+        N: NoiseBounds + NoiseFn<[f64; 2]> + NoiseFn<[f64; 3]> + NoiseFn<[f64; 4]>,
+    {
+        assert_within_bounds_3d(noise, samples);
+
+        for point in random_points(samples) {
+            assert_in_range::<N>(noise.get(point), point);
+        }
+    }
+
+    #[test]
+    fn simplex_within_bounds() {
+        assert_within_bounds(&Simplex::new(), 10_000_000);
+    }
+
+    #[test]
+    fn generators_within_bounds() {
+        assert_within_bounds(&Perlin::new(), 100_000);
+        assert_within_bounds(&PerlinSurflet::new(), 100_000);
+        assert_within_bounds(&OpenSimplex::new(), 100_000);
+        assert_within_bounds_3d(&SuperSimplex::new(), 100_000);
+        assert_within_bounds(&Value::new(), 100_000);
+        assert_within_bounds(&WhiteNoise::new(), 100_000);
+        assert_within_bounds(&Checkerboard::default(), 100_000);
+        assert_within_bounds(&Cylinders::new(), 100_000);
+        assert_within_bounds(&Spheres::new(), 100_000);
+        assert_within_bounds(&Fbm::new(), 20_000);
+        assert_within_bounds(&RidgedMulti::new(), 20_000);
+    }
+}
@@ -1,15 +1,35 @@
 pub use self::abs::*;
+pub use self::apply::*;
+pub use self::bias_gain::*;
 pub use self::clamp::*;
 pub use self::curve::*;
 pub use self::exponent::*;
+pub use self::gamma::*;
 pub use self::negate::*;
+pub use self::power_const::*;
+pub use self::quantize::*;
+pub use self::remap::*;
 pub use self::scale_bias::*;
+pub use self::smooth_step::*;
+pub use self::soft_clamp::*;
 pub use self::terrace::*;
+pub use self::threshold::*;
+pub use self::wrap::*;
 
 mod abs;
+mod apply;
+mod bias_gain;
 mod clamp;
 mod curve;
 mod exponent;
+mod gamma;
 mod negate;
+mod power_const;
+mod quantize;
+mod remap;
 mod scale_bias;
+mod smooth_step;
+mod soft_clamp;
 mod terrace;
+mod threshold;
+mod wrap;
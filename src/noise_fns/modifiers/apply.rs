@@ -0,0 +1,106 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that maps the output value from the source function with a
+/// closure.
+///
+/// This is useful for one-off transformations that don't warrant a noise
+/// function of their own. The closure receives the raw output value from the
+/// source function, without any clamping or normalization.
+///
+/// `Apply` is `Clone`, `Send` and `Sync` whenever both the source function and
+/// the closure are.
+#[derive(Clone)]
+pub struct Apply<Source, F> {
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Maps the output value from the source function.
+    pub function: F,
+}
+
+impl<Source, F> Apply<Source, F>
+where
+    F: Fn(f64) -> f64,
+{
+    pub fn new(source: Source, function: F) -> Self {
+        Self { source, function }
+    }
+}
+
+impl<T, Source, F> NoiseFn<T> for Apply<Source, F>
+where
+    Source: NoiseFn<T>,
+    F: Fn(f64) -> f64,
+{
+    fn get(&self, point: T) -> f64 {
+        (self.function)(self.source.get(point))
+    }
+
+    fn get_batch(&self, points: &[T], output: &mut [f64])
+    where
+        T: Copy,
+    {
+        self.source.get_batch(points, output);
+
+        for value in output.iter_mut() {
+            *value = (self.function)(*value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Constant, Fbm};
+    use std::{sync::Mutex, thread};
+
+    #[test]
+    fn closure_sees_raw_source_output() {
+        let seen = Mutex::new(Vec::new());
+        let apply = Apply::new(Constant::new(5.0), |value| {
+            seen.lock().unwrap().push(value);
+            value * 2.0
+        });
+
+        assert_eq!(apply.get([0.0, 0.0]), 10.0);
+        assert_eq!(apply.get([1.0, 2.0, 3.0, 4.0]), 10.0);
+        assert_eq!(*seen.lock().unwrap(), [5.0, 5.0]);
+    }
+
+    #[test]
+    fn composes_with_fbm() {
+        let fbm = Fbm::new();
+        let apply = Apply::new(&fbm, f64::sin);
+        let points = [[0.1, 0.2, 0.3], [1.5, -2.5, 0.7], [3.3, 4.4, -5.5]];
+
+        for &point in &points {
+            assert_eq!(apply.get(point), fbm.get(point).sin());
+        }
+
+        let mut batch = [0.0; 3];
+        apply.get_batch(&points, &mut batch);
+        assert_eq!(batch, points.map(|point| fbm.get(point).sin()));
+
+        // Apply nodes nest like any other noise function.
+        let nested = Apply::new(apply.clone(), |value| value + 1.0);
+        assert_eq!(nested.get(points[0]), apply.get(points[0]) + 1.0);
+    }
+
+    #[test]
+    fn can_be_shared_between_threads() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+        let apply = Apply::new(Fbm::new(), |value: f64| value.abs());
+        assert_send_sync(&apply);
+
+        thread::scope(|scope| {
+            for i in 0..4 {
+                let apply = &apply;
+                scope.spawn(move || {
+                    let value = apply.get([i as f64 * 0.3, 0.5]);
+                    assert!(value >= 0.0);
+                });
+            }
+        });
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{math, noise_fns::NoiseFn, Error};
+
+/// Noise function that applies Schlick's bias curve to the output value from
+/// the source function.
+///
+/// The output value is clamped to [-1,1] and mapped to [0,1] before the curve
+/// is applied, then mapped back to [-1,1], so -1.0, 0.0 at a bias of 0.5, and
+/// 1.0 are left unchanged. A bias above 0.5 pushes the output towards 1.0, and
+/// a bias below 0.5 pushes it towards -1.0. The curves for `b` and `1 - b` are
+/// the inverses of each other.
+pub struct Bias<'a, T> {
+    /// Outputs a value.
+    pub source: &'a dyn NoiseFn<T>,
+
+    bias: f64,
+}
+
+/// Noise function that applies Schlick's gain curve to the output value from
+/// the source function.
+///
+/// The output value is clamped to [-1,1] and mapped to [0,1] before the curve
+/// is applied, then mapped back to [-1,1], so -1.0, 0.0 and 1.0 are left
+/// unchanged. A gain above 0.5 pushes the output away from 0.0 towards the
+/// ends of the range, increasing contrast, and a gain below 0.5 pulls it in
+/// towards 0.0. The curve is symmetric about 0.0.
+pub struct Gain<'a, T> {
+    /// Outputs a value.
+    pub source: &'a dyn NoiseFn<T>,
+
+    gain: f64,
+}
+
+fn check_parameter(parameter: f64) -> Result<f64, Error> {
+    if parameter > 0.0 && parameter < 1.0 {
+        Ok(parameter)
+    } else {
+        Err(Error::InvalidParameter)
+    }
+}
+
+/// Schlick's bias curve over [0,1].
+fn bias(t: f64, b: f64) -> f64 {
+    t / ((1.0 / b - 2.0) * (1.0 - t) + 1.0)
+}
+
+/// Schlick's gain curve over [0,1], made of two bias curves mirrored about
+/// its midpoint.
+fn gain(t: f64, g: f64) -> f64 {
+    if t < 0.5 {
+        bias(t * 2.0, 1.0 - g) * 0.5
+    } else {
+        1.0 - bias(2.0 - t * 2.0, 1.0 - g) * 0.5
+    }
+}
+
+/// Applies `curve` to a value within [-1,1], mapped to [0,1].
+fn apply(value: f64, curve: impl Fn(f64) -> f64) -> f64 {
+    let t = (math::clamp(value, -1.0, 1.0) + 1.0) * 0.5;
+    curve(t) * 2.0 - 1.0
+}
+
+impl<'a, T> Bias<'a, T> {
+    /// Creates a modifier that applies the given bias.
+    ///
+    /// Returns an error if `bias` is not strictly between 0.0 and 1.0.
+    pub fn new(source: &'a dyn NoiseFn<T>, bias: f64) -> Result<Self, Error> {
+        Ok(Self {
+            source,
+            bias: check_parameter(bias)?,
+        })
+    }
+
+    pub fn bias(&self) -> f64 {
+        self.bias
+    }
+}
+
+impl<'a, T> NoiseFn<T> for Bias<'a, T> {
+    fn get(&self, point: T) -> f64 {
+        apply(self.source.get(point), |t| bias(t, self.bias))
+    }
+}
+
+impl<'a, T> Gain<'a, T> {
+    /// Creates a modifier that applies the given gain.
+    ///
+    /// Returns an error if `gain` is not strictly between 0.0 and 1.0.
+    pub fn new(source: &'a dyn NoiseFn<T>, gain: f64) -> Result<Self, Error> {
+        Ok(Self {
+            source,
+            gain: check_parameter(gain)?,
+        })
+    }
+
+    pub fn gain(&self) -> f64 {
+        self.gain
+    }
+}
+
+impl<'a, T> NoiseFn<T> for Gain<'a, T> {
+    fn get(&self, point: T) -> f64 {
+        apply(self.source.get(point), |t| gain(t, self.gain))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    fn biased(value: f64, b: f64) -> f64 {
+        Bias::new(&Constant::new(value), b).unwrap().get([0.0, 0.0])
+    }
+
+    fn gained(value: f64, g: f64) -> f64 {
+        Gain::new(&Constant::new(value), g).unwrap().get([0.0, 0.0])
+    }
+
+    #[test]
+    fn ends_are_fixed() {
+        for &parameter in &[0.05, 0.3, 0.5, 0.7, 0.95] {
+            assert_eq!(biased(-1.0, parameter), -1.0);
+            assert_eq!(biased(1.0, parameter), 1.0);
+            assert_eq!(gained(-1.0, parameter), -1.0);
+            assert_eq!(gained(1.0, parameter), 1.0);
+            assert_eq!(gained(0.0, parameter), 0.0);
+
+            // Values outside the range are clamped onto the ends.
+            assert_eq!(biased(3.0, parameter), 1.0);
+            assert_eq!(gained(-3.0, parameter), -1.0);
+        }
+    }
+
+    #[test]
+    fn curves_are_symmetric() {
+        for i in -20..=20 {
+            let value = i as f64 * 0.05;
+
+            for &parameter in &[0.1, 0.3, 0.8] {
+                // Opposite biases undo each other.
+                let undone = biased(biased(value, parameter), 1.0 - parameter);
+                assert!((undone - value).abs() < 1e-12);
+
+                // Gain is an odd function.
+                assert!((gained(-value, parameter) + gained(value, parameter)).abs() < 1e-12);
+            }
+
+            // A parameter of 0.5 is the identity.
+            assert!((biased(value, 0.5) - value).abs() < 1e-12);
+            assert!((gained(value, 0.5) - value).abs() < 1e-12);
+        }
+
+        assert!(biased(0.0, 0.8) > 0.0);
+        assert!(gained(0.5, 0.8) > 0.5);
+        assert!(gained(0.5, 0.2) < 0.5);
+    }
+
+    #[test]
+    fn parameters_outside_open_interval_are_rejected() {
+        let constant = Constant::new(0.0);
+
+        for &parameter in &[0.0, 1.0, -0.5, 1.5, f64::NAN] {
+            assert_eq!(
+                Bias::<[f64; 2]>::new(&constant, parameter).err(),
+                Some(Error::InvalidParameter)
+            );
+            assert_eq!(
+                Gain::<[f64; 2]>::new(&constant, parameter).err(),
+                Some(Error::InvalidParameter)
+            );
+        }
+    }
+}
@@ -1,12 +1,18 @@
-use crate::{math, noise_fns::NoiseFn};
+use crate::{math, noise_fns::NoiseFn, Error};
 
 /// Noise function that clamps the output value from the source function to a
 /// range of values.
+///
+/// A NaN output value from the source function is clamped to the lower bound,
+/// so the output is always within the range.
 pub struct Clamp<'a, T> {
     /// Outputs a value.
     pub source: &'a dyn NoiseFn<T>,
 
     /// Bound of the clamping range. Default is -1.0 to 1.0.
+    ///
+    /// The lower bound must not be greater than the upper bound, or the get()
+    /// method panics.
     pub bounds: (f64, f64),
 }
 
@@ -18,6 +24,19 @@ impl<'a, T> Clamp<'a, T> {
         }
     }
 
+    /// Creates a modifier that clamps to [-1,1].
+    pub fn unit(source: &'a dyn NoiseFn<T>) -> Self {
+        Self::new(source)
+    }
+
+    /// Creates a modifier that clamps to [0,1].
+    pub fn positive(source: &'a dyn NoiseFn<T>) -> Self {
+        Self {
+            source,
+            bounds: (0.0, 1.0),
+        }
+    }
+
     pub fn set_lower_bound(self, lower_bound: f64) -> Self {
         Self {
             bounds: (lower_bound, self.bounds.1),
@@ -32,18 +51,96 @@ impl<'a, T> Clamp<'a, T> {
         }
     }
 
+    /// Sets both bounds, without checking them. See `try_set_bounds`.
     pub fn set_bounds(self, lower_bound: f64, upper_bound: f64) -> Self {
         Self {
             bounds: (lower_bound, upper_bound),
             ..self
         }
     }
+
+    /// Sets both bounds.
+    ///
+    /// Returns an error if `lower_bound` is greater than `upper_bound`, or if
+    /// either is NaN. Equal bounds are allowed, and make the output constant.
+    pub fn try_set_bounds(self, lower_bound: f64, upper_bound: f64) -> Result<Self, Error> {
+        // Also false if either bound is NaN.
+        if lower_bound <= upper_bound {
+            Ok(self.set_bounds(lower_bound, upper_bound))
+        } else {
+            Err(Error::InvalidRange)
+        }
+    }
 }
 
 impl<'a, T> NoiseFn<T> for Clamp<'a, T> {
     fn get(&self, point: T) -> f64 {
         let value = self.source.get(point);
 
+        if value.is_nan() {
+            return self.bounds.0;
+        }
+
         math::clamp(value, self.bounds.0, self.bounds.1)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    fn clamp(value: f64, bounds: (f64, f64)) -> f64 {
+        Clamp::new(&Constant::new(value))
+            .try_set_bounds(bounds.0, bounds.1)
+            .unwrap()
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn boundary_values_are_kept() {
+        assert_eq!(clamp(-0.5, (-0.5, 0.25)), -0.5);
+        assert_eq!(clamp(0.25, (-0.5, 0.25)), 0.25);
+        assert_eq!(clamp(0.0, (-0.5, 0.25)), 0.0);
+        assert_eq!(clamp(-0.6, (-0.5, 0.25)), -0.5);
+        assert_eq!(clamp(f64::INFINITY, (-0.5, 0.25)), 0.25);
+        assert_eq!(clamp(0.7, (0.3, 0.3)), 0.3);
+    }
+
+    #[test]
+    fn nan_clamps_to_lower_bound() {
+        assert_eq!(clamp(f64::NAN, (-0.5, 0.25)), -0.5);
+
+        let constant = Constant::new(f64::NAN);
+        assert_eq!(Clamp::positive(&constant).get([0.0, 0.0]), 0.0);
+        assert_eq!(Clamp::unit(&constant).get([0.0, 0.0]), -1.0);
+    }
+
+    #[test]
+    fn convenience_constructors_set_bounds() {
+        let constant = Constant::new(0.0);
+        assert_eq!(Clamp::<[f64; 2]>::unit(&constant).bounds, (-1.0, 1.0));
+        assert_eq!(Clamp::<[f64; 2]>::positive(&constant).bounds, (0.0, 1.0));
+    }
+
+    #[test]
+    fn inverted_bounds_are_rejected() {
+        let constant = Constant::new(0.0);
+
+        for &(lower, upper) in &[(1.0, -1.0), (f64::NAN, 1.0), (0.0, f64::NAN)] {
+            assert_eq!(
+                Clamp::<[f64; 2]>::new(&constant)
+                    .try_set_bounds(lower, upper)
+                    .err(),
+                Some(Error::InvalidRange)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn unchecked_inverted_bounds_panic_on_get() {
+        let constant = Constant::new(0.0);
+        Clamp::new(&constant).set_bounds(1.0, -1.0).get([0.0, 0.0]);
+    }
+}
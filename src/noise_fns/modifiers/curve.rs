@@ -1,7 +1,4 @@
-use crate::{
-    math::{self, interpolate},
-    noise_fns::NoiseFn,
-};
+use crate::{math::interpolate, noise_fns::NoiseFn, Error};
 
 /// Noise function that maps the output value from the source function onto an
 /// arbitrary function curve.
@@ -11,39 +8,105 @@ use crate::{
 /// points_; each control point has an _input value_ that maps to an _output
 /// value_.
 ///
-/// To add control points to the curve, use the `add_control_point` method.
+/// To build a curve that is checked before use, use `CurveBuilder`.
+/// Otherwise, add control points to the curve with the `add_control_point`
+/// method.
 ///
-/// Since the curve is a cubic spline, an application must have a minimum of
-/// four control points to the curve. If there is less than four control
-/// points, the get() method panics. Each control point can have any input
-/// and output value, although no two control points can have the same input.
+/// The curve passes through every control point, and is interpolated between
+/// them as set by `interpolation`. The default cubic spline needs a minimum of
+/// four control points, while the other interpolations need two. Each control
+/// point can have any input and output value, although no two control points
+/// can have the same input.
+///
+/// # Panics
+///
+/// The get() method panics if there are too few control points for the
+/// interpolation.
+///
+/// Output values from the source function beyond the outermost control points
+/// are handled as set by `extrapolation`.
 pub struct Curve<'a, T> {
     /// Outputs a value.
     pub source: &'a dyn NoiseFn<T>,
 
+    /// How the curve is interpolated between two control points. The default
+    /// is `CurveInterpolation::Cubic`.
+    pub interpolation: CurveInterpolation,
+
+    /// How the curve continues beyond the outermost control points. The
+    /// default is `CurveExtrapolation::Clamp`.
+    pub extrapolation: CurveExtrapolation,
+
     /// Vec that stores the control points.
     control_points: Vec<ControlPoint<f64>>,
 }
 
+/// Ways a `Curve` can be interpolated between two control points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveInterpolation {
+    /// Cubic spline through the four nearest control points. The curve is
+    /// smooth, but can overshoot the control points on either side of it,
+    /// leaving the range they span.
+    Cubic,
+
+    /// Monotone cubic spline, with the tangents limited as described by
+    /// Fritsch and Carlson. The curve is smooth, never overshoots, and only
+    /// rises or falls where the control points do.
+    MonotoneCubic,
+
+    /// Straight lines between the control points.
+    Linear,
+}
+
+impl CurveInterpolation {
+    /// Fewest control points a curve needs with this interpolation.
+    fn min_control_points(self) -> usize {
+        match self {
+            Self::Cubic => 4,
+            Self::MonotoneCubic | Self::Linear => 2,
+        }
+    }
+}
+
+/// Ways a `Curve` can continue beyond its outermost control points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveExtrapolation {
+    /// Outputs the output value of the nearest outer control point.
+    Clamp,
+
+    /// Continues in a straight line through the two outermost control points
+    /// on that side, which meets the curve without a change in slope.
+    Extend,
+}
+
 struct ControlPoint<T> {
     input: T,
     output: T,
 }
 
 impl<'a, T> Curve<'a, T> {
+    pub const DEFAULT_INTERPOLATION: CurveInterpolation = CurveInterpolation::Cubic;
+    pub const DEFAULT_EXTRAPOLATION: CurveExtrapolation = CurveExtrapolation::Clamp;
+
     pub fn new(source: &'a dyn NoiseFn<T>) -> Self {
         Self {
             source,
+            interpolation: Self::DEFAULT_INTERPOLATION,
+            extrapolation: Self::DEFAULT_EXTRAPOLATION,
             control_points: Vec::with_capacity(4),
         }
     }
 
+    /// Adds a control point to the curve.
+    ///
+    /// A control point whose input value is equal to that of a control point
+    /// already on the curve is ignored.
     pub fn add_control_point(mut self, input_value: f64, output_value: f64) -> Self {
         // check to see if the vector already contains the input point.
         if !self
             .control_points
             .iter()
-            .any(|x| (x.input - input_value).abs() < std::f64::EPSILON)
+            .any(|x| (x.input - input_value).abs() < f64::EPSILON)
         {
             // it doesn't, so find the correct position to insert the new
             // control point.
@@ -51,7 +114,7 @@ impl<'a, T> Curve<'a, T> {
                 .control_points
                 .iter()
                 .position(|x| x.input >= input_value)
-                .unwrap_or_else(|| self.control_points.len());
+                .unwrap_or(self.control_points.len());
 
             // add the new control point at the correct position.
             self.control_points.insert(
@@ -65,61 +128,371 @@ impl<'a, T> Curve<'a, T> {
 
         self
     }
+
+    pub fn set_interpolation(self, interpolation: CurveInterpolation) -> Self {
+        Self {
+            interpolation,
+            ..self
+        }
+    }
+
+    pub fn set_extrapolation(self, extrapolation: CurveExtrapolation) -> Self {
+        Self {
+            extrapolation,
+            ..self
+        }
+    }
+
+    /// Slope of the straight line from control point `index` to the next.
+    fn secant(&self, index: usize) -> f64 {
+        let (a, b) = (&self.control_points[index], &self.control_points[index + 1]);
+
+        (b.output - a.output) / (b.input - a.input)
+    }
+
+    /// Slope of the monotone cubic spline at control point `index`.
+    ///
+    /// Interior tangents are the mean of the secants on either side, zero
+    /// where the curve changes direction, and limited to three times the
+    /// shallower secant. That keeps both ends of every segment within the
+    /// region Fritsch and Carlson show to be free of overshoot.
+    fn monotone_tangent(&self, index: usize) -> f64 {
+        let last = self.control_points.len() - 1;
+        if index == 0 {
+            return self.secant(0);
+        }
+        if index == last {
+            return self.secant(last - 1);
+        }
+
+        let before = self.secant(index - 1);
+        let after = self.secant(index);
+        if before * after <= 0.0 {
+            return 0.0;
+        }
+
+        let limit = 3.0 * before.abs().min(after.abs());
+        let tangent = (before + after) * 0.5;
+
+        tangent.signum() * tangent.abs().min(limit)
+    }
+}
+
+/// Builder for a `Curve` that checks the control points before building it.
+pub struct CurveBuilder<'a, T> {
+    source: &'a dyn NoiseFn<T>,
+    interpolation: CurveInterpolation,
+    extrapolation: CurveExtrapolation,
+    control_points: Vec<ControlPoint<f64>>,
+}
+
+impl<'a, T> CurveBuilder<'a, T> {
+    pub fn new(source: &'a dyn NoiseFn<T>) -> Self {
+        Self {
+            source,
+            interpolation: Curve::<T>::DEFAULT_INTERPOLATION,
+            extrapolation: Curve::<T>::DEFAULT_EXTRAPOLATION,
+            control_points: Vec::with_capacity(4),
+        }
+    }
+
+    /// Adds a control point to the curve. The control points may be added in
+    /// any order.
+    pub fn add_control_point(mut self, input_value: f64, output_value: f64) -> Self {
+        self.control_points.push(ControlPoint {
+            input: input_value,
+            output: output_value,
+        });
+
+        self
+    }
+
+    pub fn set_interpolation(self, interpolation: CurveInterpolation) -> Self {
+        Self {
+            interpolation,
+            ..self
+        }
+    }
+
+    pub fn set_extrapolation(self, extrapolation: CurveExtrapolation) -> Self {
+        Self {
+            extrapolation,
+            ..self
+        }
+    }
+
+    /// Builds the curve.
+    ///
+    /// Returns an error if there are too few control points for the
+    /// interpolation, if two control points have the same input value, or if
+    /// any value is not finite.
+    pub fn build(self) -> Result<Curve<'a, T>, Error> {
+        let mut control_points = self.control_points;
+
+        if control_points
+            .iter()
+            .any(|point| !point.input.is_finite() || !point.output.is_finite())
+        {
+            return Err(Error::NonFiniteValue);
+        }
+
+        let required = self.interpolation.min_control_points();
+        if control_points.len() < required {
+            return Err(Error::TooFewControlPoints {
+                required,
+                found: control_points.len(),
+            });
+        }
+
+        // The values are finite, so they are totally ordered.
+        control_points.sort_by(|a, b| a.input.partial_cmp(&b.input).unwrap());
+        if control_points
+            .windows(2)
+            .any(|pair| pair[0].input >= pair[1].input)
+        {
+            return Err(Error::DuplicateControlPoints);
+        }
+
+        Ok(Curve {
+            source: self.source,
+            interpolation: self.interpolation,
+            extrapolation: self.extrapolation,
+            control_points,
+        })
+    }
 }
 
 impl<'a, T> NoiseFn<T> for Curve<'a, T> {
     fn get(&self, point: T) -> f64 {
-        // confirm that there's at least 4 control points in the vector.
-        assert!(self.control_points.len() >= 4);
+        let points = &self.control_points;
+
+        // confirm that there are enough control points for the interpolation.
+        assert!(
+            points.len() >= self.interpolation.min_control_points(),
+            "a Curve with {:?} interpolation needs at least {} control points",
+            self.interpolation,
+            self.interpolation.min_control_points()
+        );
 
         // get output value from the source function
         let source_value = self.source.get(point);
+        let last = points.len() - 1;
 
         // Find the first element in the control point array that has a input
-        // value larger than the output value from the source function
-        let index_pos = self
-            .control_points
+        // value larger than the output value from the source function. The
+        // segment containing the value ends there.
+        let index = points
             .iter()
             .position(|x| x.input > source_value)
-            .unwrap_or_else(|| self.control_points.len());
+            .unwrap_or(points.len());
 
-        if index_pos < 2 {
-            println!(
-                "index_pos in curve was less than 2! source value was {}",
-                source_value
-            );
+        // The value lies beyond the outermost control points.
+        if index == 0 || index > last {
+            let (end, neighbour) = if index == 0 { (0, 1) } else { (last, last - 1) };
+
+            return match self.extrapolation {
+                CurveExtrapolation::Clamp => points[end].output,
+                CurveExtrapolation::Extend => {
+                    let slope = self.secant(end.min(neighbour));
+                    points[end].output + slope * (source_value - points[end].input)
+                }
+            };
+        }
+
+        let (index1, index2) = (index - 1, index);
+        let (start, end) = (&points[index1], &points[index2]);
+        let width = end.input - start.input;
+        let alpha = (source_value - start.input) / width;
+
+        match self.interpolation {
+            CurveInterpolation::Cubic => {
+                // Repeat the outermost control points where the neighbours
+                // are missing.
+                let index0 = index1.saturating_sub(1);
+                let index3 = (index2 + 1).min(last);
+
+                interpolate::cubic(
+                    points[index0].output,
+                    start.output,
+                    end.output,
+                    points[index3].output,
+                    alpha,
+                )
+            }
+            CurveInterpolation::MonotoneCubic => {
+                let tangent1 = self.monotone_tangent(index1) * width;
+                let tangent2 = self.monotone_tangent(index2) * width;
+
+                // Cubic Hermite basis functions.
+                let alpha2 = alpha * alpha;
+                let alpha3 = alpha2 * alpha;
+                let h00 = 2.0 * alpha3 - 3.0 * alpha2 + 1.0;
+                let h10 = alpha3 - 2.0 * alpha2 + alpha;
+                let h01 = 3.0 * alpha2 - 2.0 * alpha3;
+                let h11 = alpha3 - alpha2;
+
+                h00 * start.output + h10 * tangent1 + h01 * end.output + h11 * tangent2
+            }
+            CurveInterpolation::Linear => interpolate::linear(start.output, end.output, alpha),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    /// A step from 0.0 up to 1.0, with a flat run on either side.
+    fn step(value: f64, interpolation: CurveInterpolation) -> f64 {
+        Curve::new(&Constant::new(value))
+            .add_control_point(0.0, 0.0)
+            .add_control_point(1.0, 0.0)
+            .add_control_point(2.0, 1.0)
+            .add_control_point(3.0, 1.0)
+            .add_control_point(4.0, 1.0)
+            .set_interpolation(interpolation)
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn cubic_overshoots_control_points() {
+        assert_eq!(step(2.5, CurveInterpolation::Cubic), 1.125);
+        assert!(step(0.5, CurveInterpolation::Cubic) < 0.0);
+    }
+
+    #[test]
+    fn monotone_cubic_stays_within_control_points() {
+        let mut previous = f64::NEG_INFINITY;
+        for i in 0..=400 {
+            let value = step(i as f64 * 0.01, CurveInterpolation::MonotoneCubic);
+
+            assert!((0.0..=1.0).contains(&value), "{} at {}", value, i);
+            assert!(value >= previous, "{} falls below {}", value, previous);
+            previous = value;
         }
 
-        // ensure that the index is at least 2 and less than control_points.len()
-        let index_pos = math::clamp(index_pos, 2, self.control_points.len());
-
-        // Find the four nearest control points so that we can perform cubic
-        // interpolation.
-        let index0 = math::clamp(index_pos - 2, 0, self.control_points.len() - 1);
-        let index1 = math::clamp(index_pos - 1, 0, self.control_points.len() - 1);
-        let index2 = math::clamp(index_pos, 0, self.control_points.len() - 1);
-        let index3 = math::clamp(index_pos + 1, 0, self.control_points.len() - 1);
-
-        // If some control points are missing (which occurs if the value from
-        // the source function is greater than the largest input value or less
-        // than the smallest input value of the control point array), get the
-        // corresponding output value of the nearest control point and exit.
-        if index1 == index2 {
-            return self.control_points[index1].output;
-        }
-
-        // Compute the alpha value used for cubic interpolation
-        let input0 = self.control_points[index1].input;
-        let input1 = self.control_points[index2].input;
-        let alpha = (source_value - input0) / (input1 - input0);
-
-        // Now perform the cubic interpolation and return.
-        interpolate::cubic(
-            self.control_points[index0].output,
-            self.control_points[index1].output,
-            self.control_points[index2].output,
-            self.control_points[index3].output,
-            alpha,
-        )
+        assert_eq!(step(0.5, CurveInterpolation::MonotoneCubic), 0.0);
+        assert_eq!(step(1.5, CurveInterpolation::MonotoneCubic), 0.5);
+        assert_eq!(step(2.5, CurveInterpolation::MonotoneCubic), 1.0);
+    }
+
+    #[test]
+    fn interpolations_pass_through_control_points() {
+        for &interpolation in &[
+            CurveInterpolation::Cubic,
+            CurveInterpolation::MonotoneCubic,
+            CurveInterpolation::Linear,
+        ] {
+            for &(input, output) in &[(0.0, 0.0), (1.0, 0.0), (2.0, 1.0), (4.0, 1.0)] {
+                assert_eq!(step(input, interpolation), output);
+            }
+        }
+
+        assert_eq!(step(1.25, CurveInterpolation::Linear), 0.25);
+    }
+
+    #[test]
+    fn extrapolation_clamps_or_extends() {
+        let curve = |value: f64, extrapolation| {
+            let constant = Constant::new(value);
+            Curve::new(&constant)
+                .add_control_point(-1.0, 2.0)
+                .add_control_point(0.0, 1.0)
+                .add_control_point(1.0, 0.0)
+                .add_control_point(2.0, 0.5)
+                .set_extrapolation(extrapolation)
+                .get([0.0, 0.0])
+        };
+
+        assert_eq!(curve(-3.0, CurveExtrapolation::Clamp), 2.0);
+        assert_eq!(curve(5.0, CurveExtrapolation::Clamp), 0.5);
+        assert_eq!(curve(-3.0, CurveExtrapolation::Extend), 4.0);
+        assert_eq!(curve(5.0, CurveExtrapolation::Extend), 2.0);
+        assert_eq!(curve(2.0, CurveExtrapolation::Extend), 0.5);
+    }
+
+    #[test]
+    fn builder_sorts_control_points() {
+        let source = Constant::new(0.5);
+        let built = CurveBuilder::new(&source)
+            .add_control_point(1.0, 1.0)
+            .add_control_point(-1.0, -1.0)
+            .add_control_point(2.0, 0.0)
+            .add_control_point(0.0, 0.25)
+            .build()
+            .unwrap();
+        let added = Curve::new(&source)
+            .add_control_point(-1.0, -1.0)
+            .add_control_point(0.0, 0.25)
+            .add_control_point(1.0, 1.0)
+            .add_control_point(2.0, 0.0);
+
+        assert_eq!(built.get([0.0, 0.0]), added.get([0.0, 0.0]));
+        assert_eq!(built.interpolation, CurveInterpolation::Cubic);
+    }
+
+    #[test]
+    fn builder_rejects_invalid_control_points() {
+        let source = Constant::new(0.0);
+        let builder = || {
+            CurveBuilder::<[f64; 2]>::new(&source)
+                .add_control_point(0.0, 0.0)
+                .add_control_point(1.0, 1.0)
+                .add_control_point(2.0, 0.0)
+        };
+
+        assert_eq!(
+            builder().build().err(),
+            Some(Error::TooFewControlPoints {
+                required: 4,
+                found: 3
+            })
+        );
+        assert_eq!(
+            builder().add_control_point(1.0, 0.5).build().err(),
+            Some(Error::DuplicateControlPoints)
+        );
+        assert_eq!(
+            builder().add_control_point(f64::NAN, 0.5).build().err(),
+            Some(Error::NonFiniteValue)
+        );
+        assert_eq!(
+            builder()
+                .add_control_point(3.0, f64::INFINITY)
+                .build()
+                .err(),
+            Some(Error::NonFiniteValue)
+        );
+
+        // Fewer control points suffice for the other interpolations.
+        assert!(builder()
+            .set_interpolation(CurveInterpolation::Linear)
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn add_control_point_ignores_duplicate_inputs() {
+        let constant = Constant::new(1.0);
+        let curve = Curve::new(&constant)
+            .add_control_point(0.0, 0.0)
+            .add_control_point(1.0, 1.0)
+            .add_control_point(1.0, 5.0)
+            .set_interpolation(CurveInterpolation::Linear);
+
+        assert_eq!(curve.control_points.len(), 2);
+        assert_eq!(curve.get([0.0, 0.0]), 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "needs at least 4 control points")]
+    fn cubic_needs_four_control_points() {
+        let constant = Constant::new(0.0);
+        let curve = Curve::new(&constant)
+            .add_control_point(0.0, 0.0)
+            .add_control_point(1.0, 1.0);
+
+        curve.get([0.0, 0.0]);
     }
 }
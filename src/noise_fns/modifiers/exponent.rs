@@ -7,6 +7,10 @@ use crate::{math::scale_shift, noise_fns::NoiseFn};
 /// this noise function first normalizes the output value (the range becomes 0.0
 /// to 1.0), maps that value onto an exponential curve, then rescales that
 /// value back to the original range.
+///
+/// Output values from the source function below -1.0 are raised to -1.0
+/// first, so negative values never reach the exponent. Output values above 1.0
+/// are mapped onto the same curve, so the output may exceed 1.0.
 pub struct Exponent<'a, T> {
     /// Outputs a value.
     pub source: &'a dyn NoiseFn<T>,
@@ -31,10 +35,46 @@ impl<'a, T> Exponent<'a, T> {
 
 impl<'a, T> NoiseFn<T> for Exponent<'a, T> {
     fn get(&self, point: T) -> f64 {
-        let mut value = self.source.get(point);
-        value = (value + 1.0) / 2.0;
-        value = value.abs();
-        value = value.powf(self.exponent);
-        scale_shift(value, 2.0)
+        let value = ((self.source.get(point) + 1.0) / 2.0).max(0.0);
+        scale_shift(value.powf(self.exponent), 2.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    fn exponent(value: f64, exponent: f64) -> f64 {
+        Exponent::new(&Constant::new(value))
+            .set_exponent(exponent)
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn negative_values_stay_finite_and_in_range() {
+        for &e in &[0.5, 2.3] {
+            let mut previous = exponent(-3.0, e);
+            assert_eq!(previous, -1.0);
+
+            for i in -300..=100 {
+                let value = exponent(i as f64 * 0.01, e);
+                assert!(value.is_finite());
+                assert!(value >= previous);
+                assert!((-1.0..=1.0).contains(&value));
+                previous = value;
+            }
+
+            assert_eq!(previous, 1.0);
+        }
+
+        assert_eq!(exponent(-0.5, 0.5), 0.0);
+        assert_eq!(exponent(-0.5, 2.0), -0.875);
+    }
+
+    #[test]
+    fn values_above_one_follow_the_curve() {
+        assert_eq!(exponent(1.5, 2.0), 2.125);
+        assert_eq!(exponent(3.0, 0.5), 2.0_f64.sqrt() * 2.0 - 1.0);
     }
 }
@@ -0,0 +1,121 @@
+use crate::{math, noise_fns::NoiseFn};
+
+/// Noise function that applies a gamma curve to the output value from the
+/// source function.
+///
+/// The output value is clamped to [-1,1] and mapped to [0,1], raised to the
+/// power of the gamma, then mapped back to [-1,1]. Unlike `Exponent`, the
+/// curve is defined and continuous for every input value, and always outputs
+/// a value within [-1,1]. A gamma above 1.0 darkens the output, and a gamma
+/// below 1.0 brightens it.
+pub struct Gamma<'a, T> {
+    /// Outputs a value.
+    pub source: &'a dyn NoiseFn<T>,
+
+    /// Exponent the normalized output value is raised to. The default gamma
+    /// is 1.0, which leaves values within [-1,1] unchanged.
+    ///
+    /// Gammas below `MIN_GAMMA`, and NaN, are treated as `MIN_GAMMA`, since a
+    /// gamma of zero or less would output a constant or infinite value.
+    pub gamma: f64,
+}
+
+impl<'a, T> Gamma<'a, T> {
+    pub const DEFAULT_GAMMA: f64 = 1.0;
+    pub const MIN_GAMMA: f64 = 1e-3;
+
+    pub fn new(source: &'a dyn NoiseFn<T>) -> Self {
+        Self {
+            source,
+            gamma: Self::DEFAULT_GAMMA,
+        }
+    }
+
+    /// Sets the gamma. Gammas below `MIN_GAMMA`, and NaN, are clamped to
+    /// `MIN_GAMMA`.
+    pub fn set_gamma(self, gamma: f64) -> Self {
+        Self {
+            gamma: gamma.max(Self::MIN_GAMMA),
+            ..self
+        }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for Gamma<'a, T> {
+    fn get(&self, point: T) -> f64 {
+        let value = math::clamp(self.source.get(point), -1.0, 1.0);
+        let gamma = self.gamma.max(Self::MIN_GAMMA);
+
+        // Mapping to [0,1] and back rounds away the low bits of small values,
+        // so skip the round trip when it would not change the value.
+        if gamma == 1.0 {
+            return value;
+        }
+
+        let normalized = (value + 1.0) * 0.5;
+        normalized.powf(gamma) * 2.0 - 1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    fn gamma(value: f64, gamma: f64) -> f64 {
+        Gamma::new(&Constant::new(value))
+            .set_gamma(gamma)
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn unit_gamma_is_identity() {
+        for i in -100..=100 {
+            let value = i as f64 * 0.01 + 1e-17;
+            assert_eq!(gamma(value, 1.0), value);
+        }
+    }
+
+    #[test]
+    fn curve_is_monotonic_and_bounded() {
+        for &g in &[0.25, 0.5, 2.2, 4.0] {
+            let mut previous = gamma(-3.0, g);
+            assert_eq!(previous, -1.0);
+
+            for i in -300..=300 {
+                let value = gamma(i as f64 * 0.01, g);
+                assert!(value.is_finite());
+                assert!(value >= previous);
+                assert!((-1.0..=1.0).contains(&value));
+                previous = value;
+            }
+
+            assert_eq!(previous, 1.0);
+        }
+
+        assert!(gamma(0.0, 2.2) < 0.0);
+        assert!(gamma(0.0, 0.5) > 0.0);
+    }
+
+    #[test]
+    fn non_positive_gamma_is_clamped() {
+        assert_eq!(
+            Gamma::<[f64; 2]>::new(&Constant::new(0.0))
+                .set_gamma(-2.0)
+                .gamma,
+            Gamma::<[f64; 2]>::MIN_GAMMA
+        );
+
+        for &g in &[0.0, -0.5, -2.0, f64::NAN] {
+            assert_eq!(gamma(-1.0, g), -1.0);
+            assert_eq!(gamma(1.0, g), 1.0);
+            assert_eq!(gamma(0.0, g), gamma(0.0, Gamma::<[f64; 2]>::MIN_GAMMA));
+        }
+
+        // The public field is clamped too.
+        let constant = Constant::new(-1.0);
+        let mut noise = Gamma::new(&constant);
+        noise.gamma = -1.0;
+        assert_eq!(noise.get([0.0, 0.0]), -1.0);
+    }
+}
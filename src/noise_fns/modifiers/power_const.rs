@@ -0,0 +1,69 @@
+use crate::noise_fns::{combiners::power, NoiseFn};
+
+/// Noise function that raises the output value from the source function to a
+/// fixed exponent.
+///
+/// This is equivalent to a `Power` whose exponent comes from a `Constant`,
+/// without the second noise function. Negative values are handled the same
+/// way as in `Power`: where `f64::powf` would return NaN, the magnitude is
+/// raised to the exponent and the sign is restored, so `-0.25` to the power
+/// of `0.5` is `-0.5`.
+pub struct PowerConst<'a, T> {
+    /// Outputs the base.
+    pub source: &'a dyn NoiseFn<T>,
+
+    /// Exponent the output value is raised to.
+    pub exponent: f64,
+}
+
+impl<'a, T> PowerConst<'a, T> {
+    pub fn new(source: &'a dyn NoiseFn<T>, exponent: f64) -> Self {
+        Self { source, exponent }
+    }
+
+    pub fn set_exponent(self, exponent: f64) -> Self {
+        Self { exponent, ..self }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for PowerConst<'a, T> {
+    fn get(&self, point: T) -> f64 {
+        power(self.source.get(point), self.exponent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Abs, Constant, Perlin, Power};
+
+    #[test]
+    fn matches_power_of_constant_for_positive_values() {
+        let perlin = Perlin::new();
+        let base = Abs::new(&perlin);
+
+        for &exponent in &[0.5, 2.0, 3.7] {
+            let constant = Constant::new(exponent);
+            let expected = Power::new(&base, &constant);
+            let power = PowerConst::new(&base, exponent);
+
+            for i in 0..100 {
+                let point = [i as f64 * 0.137, i as f64 * -0.071, 0.25];
+                assert_eq!(power.get(point), expected.get(point));
+            }
+        }
+    }
+
+    #[test]
+    fn negative_values_keep_their_sign() {
+        let power = |value: f64, exponent: f64| {
+            PowerConst::new(&Constant::new(value), exponent).get([0.0, 0.0])
+        };
+
+        assert_eq!(power(-2.0, 2.0), 4.0);
+        assert_eq!(power(-2.0, 3.0), -8.0);
+        assert_eq!(power(-0.25, 0.5), -0.5);
+        assert_eq!(power(0.25, 0.5), 0.5);
+        assert!(!power(-0.3, 1.5).is_nan());
+    }
+}
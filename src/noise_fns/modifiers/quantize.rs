@@ -0,0 +1,244 @@
+use crate::{
+    math::{self, interpolate},
+    noise_fns::NoiseFn,
+    Error,
+};
+
+/// Noise function that snaps the output value from the source function to one
+/// of a number of evenly spaced levels.
+///
+/// The range [-1,1] is split into `levels` bands of equal width, and every
+/// value within a band is mapped onto the same level. With the default
+/// `QuantizeMode::Endpoints`, the lowest level is -1.0 and the highest is 1.0.
+/// With `QuantizeMode::BinCenter`, every value is mapped onto the center of
+/// its band instead. Values outside [-1,1] are mapped onto the nearest level.
+///
+/// With a smoothing width above zero, the hard steps between neighbouring
+/// levels are replaced with an S-curve that spans that fraction of a band,
+/// centred on the edge between the bands.
+pub struct Quantize<'a, T> {
+    /// Outputs a value.
+    pub source: &'a dyn NoiseFn<T>,
+
+    levels: usize,
+
+    mode: QuantizeMode,
+
+    /// Width of the transition between two levels, as a fraction of the width
+    /// of a band, within [0,1]. The default of 0.0 produces hard steps.
+    pub smoothing: f64,
+}
+
+/// Placements of the levels a `Quantize` snaps values to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QuantizeMode {
+    /// The levels are spread evenly from -1.0 to 1.0, so the outputs reach
+    /// both ends of the range. Needs at least two levels.
+    #[default]
+    Endpoints,
+
+    /// Each level is the center of its band, so the outputs stay clear of the
+    /// ends of the range. A single level maps every value onto 0.0.
+    BinCenter,
+}
+
+impl<'a, T> Quantize<'a, T> {
+    pub const DEFAULT_MODE: QuantizeMode = QuantizeMode::Endpoints;
+    pub const DEFAULT_SMOOTHING: f64 = 0.0;
+
+    /// Creates a modifier that snaps values to `levels` levels, spread from
+    /// -1.0 to 1.0.
+    ///
+    /// Returns an error if `levels` is less than 2.
+    pub fn new(source: &'a dyn NoiseFn<T>, levels: usize) -> Result<Self, Error> {
+        Self::with_mode(source, levels, Self::DEFAULT_MODE)
+    }
+
+    /// Creates a modifier that snaps values to `levels` levels, placed
+    /// according to `mode`.
+    ///
+    /// Returns an error if `levels` is less than 2, or less than 1 for
+    /// `QuantizeMode::BinCenter`.
+    pub fn with_mode(
+        source: &'a dyn NoiseFn<T>,
+        levels: usize,
+        mode: QuantizeMode,
+    ) -> Result<Self, Error> {
+        let min_levels = match mode {
+            QuantizeMode::Endpoints => 2,
+            QuantizeMode::BinCenter => 1,
+        };
+        if levels < min_levels {
+            return Err(Error::TooFewLevels {
+                required: min_levels,
+                found: levels,
+            });
+        }
+
+        Ok(Self {
+            source,
+            levels,
+            mode,
+            smoothing: Self::DEFAULT_SMOOTHING,
+        })
+    }
+
+    /// Sets the width of the transition between two levels. The width is
+    /// clamped to [0,1].
+    pub fn set_smoothing(self, smoothing: f64) -> Self {
+        Self {
+            smoothing: math::clamp(smoothing, 0.0, 1.0),
+            ..self
+        }
+    }
+
+    pub fn levels(&self) -> usize {
+        self.levels
+    }
+
+    pub fn mode(&self) -> QuantizeMode {
+        self.mode
+    }
+}
+
+impl<'a, T> NoiseFn<T> for Quantize<'a, T> {
+    fn get(&self, point: T) -> f64 {
+        let value = self.source.get(point);
+        let top = (self.levels - 1) as f64;
+
+        // Position of the value in units of bands, so the edges between the
+        // bands fall on the integers.
+        let position = (value + 1.0) * 0.5 * self.levels as f64;
+        let band = position.floor();
+        let offset = position - band;
+
+        let half_width = self.smoothing * 0.5;
+        let level = if offset < half_width {
+            // Rising from the level below, past the lower edge of the band.
+            band - 1.0 + interpolate::s_curve3((offset + half_width) / self.smoothing)
+        } else if offset > 1.0 - half_width {
+            // Rising towards the level above, before the upper edge of the
+            // band.
+            band + interpolate::s_curve3((offset - 1.0 + half_width) / self.smoothing)
+        } else {
+            band
+        };
+
+        let level = math::clamp(level, 0.0, top);
+        match self.mode {
+            QuantizeMode::Endpoints => level / top * 2.0 - 1.0,
+            QuantizeMode::BinCenter => (level + 0.5) / self.levels as f64 * 2.0 - 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Constant, Perlin};
+
+    fn quantize(value: f64, levels: usize, smoothing: f64) -> f64 {
+        Quantize::new(&Constant::new(value), levels)
+            .unwrap()
+            .set_smoothing(smoothing)
+            .get([0.0, 0.0])
+    }
+
+    fn bin_center(value: f64, levels: usize) -> f64 {
+        Quantize::with_mode(&Constant::new(value), levels, QuantizeMode::BinCenter)
+            .unwrap()
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn snaps_to_levels() {
+        let cases = [
+            (-1.0, 4, -1.0),
+            (-0.6, 4, -1.0),
+            (-0.4, 4, -1.0 / 3.0),
+            (0.2, 4, 1.0 / 3.0),
+            (0.9, 4, 1.0),
+            (1.0, 4, 1.0),
+            (7.0, 4, 1.0),
+            (-0.1, 2, -1.0),
+        ];
+
+        for &(value, levels, expected) in &cases {
+            assert!((quantize(value, levels, 0.0) - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn smoothing_is_continuous() {
+        let mut previous = quantize(-1.0, 5, 0.4);
+
+        for i in 1..=2000 {
+            let value = quantize(i as f64 / 1000.0 - 1.0, 5, 0.4);
+            assert!(value >= previous);
+            assert!(value - previous < 0.01);
+            previous = value;
+        }
+
+        // The middle of each band is not affected by the smoothing.
+        assert_eq!(quantize(0.0, 5, 0.4), 0.0);
+        assert!((quantize(-0.4, 5, 0.4) + 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn too_few_levels_are_rejected() {
+        let constant = Constant::new(0.0);
+
+        assert_eq!(
+            Quantize::<[f64; 2]>::new(&constant, 1).err(),
+            Some(Error::TooFewLevels {
+                required: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Quantize::<[f64; 2]>::with_mode(&constant, 0, QuantizeMode::BinCenter).err(),
+            Some(Error::TooFewLevels {
+                required: 1,
+                found: 0
+            })
+        );
+    }
+
+    #[test]
+    fn bin_center_with_two_levels_outputs_two_values() {
+        let perlin = Perlin::new();
+        let noise = Quantize::with_mode(&perlin, 2, QuantizeMode::BinCenter).unwrap();
+
+        for i in 0..1000 {
+            let point = [i as f64 * 0.173, i as f64 * -0.091, 0.5];
+            let value = noise.get(point);
+            assert!(value == -0.5 || value == 0.5, "unexpected value {}", value);
+        }
+
+        assert_eq!(bin_center(-1.0, 2), -0.5);
+        assert_eq!(bin_center(-0.01, 2), -0.5);
+        assert_eq!(bin_center(0.0, 2), 0.5);
+        assert_eq!(bin_center(1.0, 2), 0.5);
+        assert_eq!(bin_center(3.0, 2), 0.5);
+    }
+
+    #[test]
+    fn bin_center_with_single_level_outputs_zero() {
+        for &value in &[-2.0, -1.0, -0.3, 0.0, 0.8, 1.0] {
+            assert_eq!(bin_center(value, 1), 0.0);
+        }
+    }
+
+    #[test]
+    fn bin_center_stays_within_half_a_bin() {
+        let levels = usize::from(u16::MAX);
+        let half_bin = 1.0 / levels as f64;
+
+        for i in 0..=1000 {
+            let value = i as f64 / 500.0 - 1.0;
+            let rounded = bin_center(value, levels);
+            assert!((rounded - value).abs() <= half_bin + 1e-12);
+            assert!(rounded > -1.0 && rounded < 1.0);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{noise_fns::NoiseFn, Error};
+
+/// Noise function that linearly maps the output value from the source
+/// function from one range onto another.
+///
+/// Values outside the input range are extrapolated, unless clamping is
+/// enabled, in which case the output is clamped to the output range.
+pub struct Remap<'a, T> {
+    /// Outputs a value.
+    pub source: &'a dyn NoiseFn<T>,
+
+    /// Range of the output values from the source function. The two ends are
+    /// never equal, but may be given in either order.
+    from: [f64; 2],
+
+    /// Range the output value is mapped onto. `from[0]` maps to `to[0]`, and
+    /// `from[1]` maps to `to[1]`.
+    pub to: [f64; 2],
+
+    /// Whether the output value is clamped to the output range. The default is
+    /// false.
+    pub clamp: bool,
+}
+
+impl<'a, T> Remap<'a, T> {
+    pub const DEFAULT_CLAMP: bool = false;
+
+    /// Creates a modifier that maps `from` onto `to`.
+    ///
+    /// Returns an error if the two ends of `from` are equal.
+    pub fn new(source: &'a dyn NoiseFn<T>, from: [f64; 2], to: [f64; 2]) -> Result<Self, Error> {
+        let width = from[1] - from[0];
+        if width == 0.0 || !width.is_finite() {
+            return Err(Error::InvalidRange);
+        }
+
+        Ok(Self {
+            source,
+            from,
+            to,
+            clamp: Self::DEFAULT_CLAMP,
+        })
+    }
+
+    /// Creates a modifier that maps [-1,1] onto [0,1].
+    pub fn to_unit(source: &'a dyn NoiseFn<T>) -> Self {
+        Self {
+            source,
+            from: [-1.0, 1.0],
+            to: [0.0, 1.0],
+            clamp: Self::DEFAULT_CLAMP,
+        }
+    }
+
+    pub fn set_clamp(self, clamp: bool) -> Self {
+        Self { clamp, ..self }
+    }
+
+    pub fn from(&self) -> [f64; 2] {
+        self.from
+    }
+}
+
+impl<'a, T> NoiseFn<T> for Remap<'a, T> {
+    fn get(&self, point: T) -> f64 {
+        let value = self.source.get(point);
+        let t = (value - self.from[0]) / (self.from[1] - self.from[0]);
+
+        // Weighting both ends maps the ends of the input range exactly onto
+        // the ends of the output range.
+        let value = self.to[0] * (1.0 - t) + self.to[1] * t;
+
+        if self.clamp {
+            let (lower, upper) = if self.to[0] <= self.to[1] {
+                (self.to[0], self.to[1])
+            } else {
+                (self.to[1], self.to[0])
+            };
+            value.max(lower).min(upper)
+        } else {
+            value
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    fn remap(value: f64, from: [f64; 2], to: [f64; 2]) -> f64 {
+        Remap::new(&Constant::new(value), from, to)
+            .unwrap()
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn endpoints_map_exactly() {
+        assert_eq!(remap(-1.0, [-1.0, 1.0], [0.1, 0.7]), 0.1);
+        assert_eq!(remap(1.0, [-1.0, 1.0], [0.1, 0.7]), 0.7);
+        assert_eq!(remap(0.3, [0.3, -2.9], [-5.0, 1e-3]), -5.0);
+        assert_eq!(remap(-2.9, [0.3, -2.9], [-5.0, 1e-3]), 1e-3);
+        assert_eq!(remap(0.0, [-1.0, 1.0], [0.0, 1.0]), 0.5);
+
+        let constant = Constant::new(1.0);
+        assert_eq!(Remap::to_unit(&constant).get([0.0, 0.0]), 1.0);
+    }
+
+    #[test]
+    fn clamps_to_output_range() {
+        let constant = Constant::new(3.0);
+
+        let remap = Remap::new(&constant, [-1.0, 1.0], [1.0, 0.0]).unwrap();
+        assert_eq!(remap.get([0.0, 0.0]), -1.0);
+        assert_eq!(remap.set_clamp(true).get([0.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn degenerate_range_is_rejected() {
+        let constant = Constant::new(0.0);
+
+        assert_eq!(
+            Remap::<[f64; 2]>::new(&constant, [0.5, 0.5], [0.0, 1.0]).err(),
+            Some(Error::InvalidRange)
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::noise_fns::NoiseFn;
+use crate::{math::float, noise_fns::NoiseFn};
 
 /// Noise function that applies a scaling factor and a bias to the output value
 /// from the source function.
@@ -37,13 +37,7 @@ impl<'a, T> ScaleBias<'a, T> {
 }
 
 impl<'a, T> NoiseFn<T> for ScaleBias<'a, T> {
-    #[cfg(not(target_os = "emscripten"))]
     fn get(&self, point: T) -> f64 {
-        (self.source.get(point)).mul_add(self.scale, self.bias)
-    }
-
-    #[cfg(target_os = "emscripten")]
-    fn get(&self, point: T) -> f64 {
-        (self.source.get(point) * self.scale) + self.bias
+        float::mul_add(self.source.get(point), self.scale, self.bias)
     }
 }
@@ -0,0 +1,150 @@
+use crate::{
+    math::{self, interpolate},
+    noise_fns::NoiseFn,
+    Error,
+};
+
+/// Noise function that eases the output value from the source function
+/// between two edges with a Hermite smoothstep.
+///
+/// Values at or below the lower edge map to the lower end of the output range,
+/// and values at or above the upper edge map to the upper end. Unlike `Clamp`,
+/// the slope falls smoothly to zero at both edges, so the output has no
+/// creases where it saturates.
+pub struct SmoothStep<'a, T> {
+    /// Outputs a value.
+    pub source: &'a dyn NoiseFn<T>,
+
+    /// Edges of the transition. The lower edge is always less than the upper
+    /// edge.
+    edges: (f64, f64),
+
+    /// Range the eased value is mapped onto. Default is -1.0 to 1.0.
+    pub output_range: (f64, f64),
+
+    /// Polynomial used for the transition. The default is
+    /// `SmoothStepOrder::Cubic`.
+    pub order: SmoothStepOrder,
+}
+
+/// Polynomials a `SmoothStep` can ease with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SmoothStepOrder {
+    /// `3t² - 2t³`, whose first derivative is zero at both edges.
+    Cubic,
+
+    /// `6t⁵ - 15t⁴ + 10t³`, whose first and second derivatives are zero at
+    /// both edges.
+    Quintic,
+}
+
+impl<'a, T> SmoothStep<'a, T> {
+    pub const DEFAULT_OUTPUT_RANGE: (f64, f64) = (-1.0, 1.0);
+    pub const DEFAULT_ORDER: SmoothStepOrder = SmoothStepOrder::Cubic;
+
+    /// Creates a modifier that eases between `lower` and `upper`.
+    ///
+    /// Returns an error unless `lower` is less than `upper`.
+    pub fn new(source: &'a dyn NoiseFn<T>, lower: f64, upper: f64) -> Result<Self, Error> {
+        let width = upper - lower;
+        if !(lower.is_finite() && upper.is_finite() && width.is_finite()) || width <= 0.0 {
+            return Err(Error::InvalidRange);
+        }
+
+        Ok(Self {
+            source,
+            edges: (lower, upper),
+            output_range: Self::DEFAULT_OUTPUT_RANGE,
+            order: Self::DEFAULT_ORDER,
+        })
+    }
+
+    pub fn set_output_range(self, lower: f64, upper: f64) -> Self {
+        Self {
+            output_range: (lower, upper),
+            ..self
+        }
+    }
+
+    pub fn set_order(self, order: SmoothStepOrder) -> Self {
+        Self { order, ..self }
+    }
+
+    pub fn edges(&self) -> (f64, f64) {
+        self.edges
+    }
+}
+
+impl<'a, T> NoiseFn<T> for SmoothStep<'a, T> {
+    fn get(&self, point: T) -> f64 {
+        let value = self.source.get(point);
+        let (lower, upper) = self.edges;
+        let t = math::clamp((value - lower) / (upper - lower), 0.0, 1.0);
+
+        let t = match self.order {
+            SmoothStepOrder::Cubic => interpolate::s_curve3(t),
+            SmoothStepOrder::Quintic => interpolate::s_curve5(t),
+        };
+
+        interpolate::linear(self.output_range.0, self.output_range.1, t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    fn smooth_step(value: f64, order: SmoothStepOrder) -> f64 {
+        SmoothStep::new(&Constant::new(value), -0.5, 0.5)
+            .unwrap()
+            .set_order(order)
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn maps_edges_onto_output_range() {
+        for &order in &[SmoothStepOrder::Cubic, SmoothStepOrder::Quintic] {
+            assert_eq!(smooth_step(-0.5, order), -1.0);
+            assert_eq!(smooth_step(-0.9, order), -1.0);
+            assert_eq!(smooth_step(0.0, order), 0.0);
+            assert_eq!(smooth_step(0.5, order), 1.0);
+            assert_eq!(smooth_step(3.0, order), 1.0);
+        }
+
+        let constant = Constant::new(0.0);
+        let smooth_step = SmoothStep::new(&constant, -1.0, 1.0)
+            .unwrap()
+            .set_output_range(0.0, 10.0);
+        assert_eq!(smooth_step.get([0.0, 0.0]), 5.0);
+    }
+
+    #[test]
+    fn slope_is_zero_at_edges() {
+        const STEP: f64 = 1e-4;
+
+        for &order in &[SmoothStepOrder::Cubic, SmoothStepOrder::Quintic] {
+            for &edge in &[-0.5, 0.5] {
+                let slope =
+                    (smooth_step(edge + STEP, order) - smooth_step(edge - STEP, order)) / STEP;
+                assert!(slope.abs() < 1e-3, "slope {} at {}", slope, edge);
+            }
+
+            // The transition is steepest halfway between the edges.
+            let slope = (smooth_step(STEP, order) - smooth_step(-STEP, order)) / (2.0 * STEP);
+            assert!(slope > 2.9, "slope {} at 0.0", slope);
+        }
+    }
+
+    #[test]
+    fn invalid_edges_are_rejected() {
+        let constant = Constant::new(0.0);
+
+        for &(lower, upper) in &[(0.5, 0.5), (1.0, -1.0), (f64::NAN, 1.0)] {
+            assert_eq!(
+                SmoothStep::<[f64; 2]>::new(&constant, lower, upper).err(),
+                Some(Error::InvalidRange)
+            );
+        }
+    }
+}
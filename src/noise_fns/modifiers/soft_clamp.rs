@@ -0,0 +1,175 @@
+use crate::{noise_fns::NoiseFn, Error};
+
+/// Noise function that smoothly compresses the output value from the source
+/// function into a range of values.
+///
+/// Values further than `knee` from both bounds pass through unchanged. Within
+/// the knee of a bound, values are compressed with a `tanh` curve, so the
+/// output approaches the bound asymptotically instead of flattening against
+/// it at a hard corner like `Clamp` does. The slope of the output is
+/// continuous everywhere.
+///
+/// A NaN output value from the source function is clamped to the lower bound.
+pub struct SoftClamp<'a, T> {
+    /// Outputs a value.
+    pub source: &'a dyn NoiseFn<T>,
+
+    /// Lower bound of the output. Default is -1.0.
+    ///
+    /// Setting the field directly skips the checks of `try_set_bounds`. The
+    /// lower bound must not be greater than the upper bound, or the output is
+    /// meaningless.
+    pub lower: f64,
+
+    /// Upper bound of the output. Default is 1.0.
+    ///
+    /// Like `lower`, setting the field directly is not checked.
+    pub upper: f64,
+
+    /// Width of the region below each bound in which values are compressed.
+    /// Default is 0.25.
+    ///
+    /// A knee of 0.0 clamps values like `Clamp`. Knees wider than half the
+    /// range are treated as half the range.
+    pub knee: f64,
+}
+
+impl<'a, T> SoftClamp<'a, T> {
+    pub const DEFAULT_LOWER: f64 = -1.0;
+    pub const DEFAULT_UPPER: f64 = 1.0;
+    pub const DEFAULT_KNEE: f64 = 0.25;
+
+    pub fn new(source: &'a dyn NoiseFn<T>) -> Self {
+        Self {
+            source,
+            lower: Self::DEFAULT_LOWER,
+            upper: Self::DEFAULT_UPPER,
+            knee: Self::DEFAULT_KNEE,
+        }
+    }
+
+    /// Sets both bounds, without checking them. See `try_set_bounds`.
+    pub fn set_bounds(self, lower: f64, upper: f64) -> Self {
+        Self {
+            lower,
+            upper,
+            ..self
+        }
+    }
+
+    /// Sets both bounds.
+    ///
+    /// Returns an error unless both bounds are finite and `lower` is not
+    /// greater than `upper`.
+    pub fn try_set_bounds(self, lower: f64, upper: f64) -> Result<Self, Error> {
+        if !(lower.is_finite() && upper.is_finite()) || lower > upper {
+            return Err(Error::InvalidRange);
+        }
+
+        Ok(self.set_bounds(lower, upper))
+    }
+
+    /// Sets the width of the compressed region. Negative widths are treated
+    /// as zero.
+    pub fn set_knee(self, knee: f64) -> Self {
+        Self {
+            knee: knee.max(0.0),
+            ..self
+        }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for SoftClamp<'a, T> {
+    fn get(&self, point: T) -> f64 {
+        let value = self.source.get(point);
+
+        if value.is_nan() {
+            return self.lower;
+        }
+
+        let knee = self.knee.min((self.upper - self.lower) * 0.5);
+        if knee.is_nan() || knee <= 0.0 {
+            return value.max(self.lower).min(self.upper);
+        }
+
+        // Past the start of either knee, the output follows a `tanh` curve
+        // with the same value and slope as the identity at the start, which
+        // levels off at the bound.
+        let upper_knee = self.upper - knee;
+        let lower_knee = self.lower + knee;
+
+        if value > upper_knee {
+            upper_knee + knee * ((value - upper_knee) / knee).tanh()
+        } else if value < lower_knee {
+            lower_knee + knee * ((value - lower_knee) / knee).tanh()
+        } else {
+            value
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    fn soft_clamp(value: f64, knee: f64) -> f64 {
+        SoftClamp::new(&Constant::new(value))
+            .try_set_bounds(-1.0, 2.0)
+            .unwrap()
+            .set_knee(knee)
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn compresses_towards_bounds() {
+        assert_eq!(soft_clamp(0.5, 0.5), 0.5);
+        assert_eq!(soft_clamp(1.5, 0.5), 1.5);
+
+        let mut previous = soft_clamp(-10.0, 0.5);
+        for i in 1..=2000 {
+            let value = soft_clamp(-10.0 + i as f64 * 0.01, 0.5);
+            assert!(value >= previous && (-1.0..=2.0).contains(&value));
+            previous = value;
+        }
+
+        assert!(soft_clamp(2.2, 0.5) < 2.0);
+        assert!(soft_clamp(-1.2, 0.5) > -1.0);
+        assert_eq!(soft_clamp(f64::NAN, 0.5), -1.0);
+
+        // Without a knee, the bounds are hard.
+        assert_eq!(soft_clamp(3.0, 0.0), 2.0);
+        assert_eq!(soft_clamp(1.9, 0.0), 1.9);
+    }
+
+    #[test]
+    fn slope_is_continuous_at_knees() {
+        const STEP: f64 = 1e-6;
+
+        for &knee in &[0.25, 0.5, 10.0] {
+            let knee_width = f64::min(knee, 1.5);
+
+            for &edge in &[-1.0 + knee_width, 2.0 - knee_width] {
+                let below = (soft_clamp(edge, knee) - soft_clamp(edge - STEP, knee)) / STEP;
+                let above = (soft_clamp(edge + STEP, knee) - soft_clamp(edge, knee)) / STEP;
+
+                assert!((below - 1.0).abs() < 1e-4, "slope {} below {}", below, edge);
+                assert!((above - 1.0).abs() < 1e-4, "slope {} above {}", above, edge);
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_bounds_are_rejected() {
+        let constant = Constant::new(0.0);
+
+        for &(lower, upper) in &[(1.0, -1.0), (f64::NAN, 1.0), (0.0, f64::INFINITY)] {
+            assert_eq!(
+                SoftClamp::<[f64; 2]>::new(&constant)
+                    .try_set_bounds(lower, upper)
+                    .err(),
+                Some(Error::InvalidRange)
+            );
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     math::{clamp, interpolate},
     noise_fns::NoiseFn,
+    Error,
 };
 
 /// Noise function that maps the output value from the source function onto a
@@ -12,13 +13,13 @@ use crate::{
 /// which resets the slope to zero at that point, producing a "terracing"
 /// effect.
 ///
-/// To add control points to the curve, use the `add_control_point` method.
-///
-/// An application must add a minimum of two control points to the curve. If
-/// there are less than two control points, the get() method panics. The
-/// control points can have any value, although no two control points can
-/// have the same value. There is no limit to the number of control points
-/// that can be added to the curve.
+/// To build a curve from a list of control points, use
+/// `with_control_points`, which returns an error if there are fewer than two
+/// control points or if any two of them are equal. Control points can also be
+/// added one at a time with the `add_control_point` method, which ignores
+/// duplicates; if there are less than two control points, the get() method
+/// panics. There is no limit to the number of control points that can be
+/// added to the curve.
 ///
 /// The noise function clamps the output value from the source function if that
 /// value is less than the value of the lowest control point or greater than
@@ -34,19 +35,77 @@ pub struct Terrace<'a, T> {
     /// inverted.
     pub invert_terraces: bool,
 
+    /// Curve the output follows between two control points. The default is
+    /// `TerraceEasing::Quadratic`.
+    pub easing: TerraceEasing,
+
     /// Vec that stores the control points.
     control_points: Vec<f64>,
 }
 
+/// Curves a `Terrace` can follow between two control points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TerraceEasing {
+    /// Squares the position between the control points. The slope is zero at
+    /// the lower control point and steepest at the upper one, where the
+    /// terrace ends in a sharp cliff.
+    Quadratic,
+
+    /// Eases in and out with the cubic `3t² - 2t³`. The slope is zero at both
+    /// control points, producing rounded steps. The curve is symmetric, so
+    /// `invert_terraces` has no effect with this easing.
+    Cubic,
+}
+
 impl<'a, T> Terrace<'a, T> {
+    pub const DEFAULT_EASING: TerraceEasing = TerraceEasing::Quadratic;
+
     pub fn new(source: &'a dyn NoiseFn<T>) -> Self {
         Terrace {
             source,
             invert_terraces: false,
+            easing: Self::DEFAULT_EASING,
             control_points: Vec::with_capacity(2),
         }
     }
 
+    /// Creates a terrace-forming curve through the given control points,
+    /// which may be in any order.
+    ///
+    /// Returns an error if there are fewer than two control points, if any of
+    /// them is infinite or NaN, or if any two of them are equal.
+    pub fn with_control_points(
+        source: &'a dyn NoiseFn<T>,
+        control_points: &[f64],
+    ) -> Result<Self, Error> {
+        if control_points.len() < 2 {
+            return Err(Error::TooFewControlPoints {
+                required: 2,
+                found: control_points.len(),
+            });
+        }
+
+        if control_points.iter().any(|point| !point.is_finite()) {
+            return Err(Error::NonFiniteValue);
+        }
+
+        // The points are finite, so they are totally ordered.
+        let mut sorted = control_points.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        if sorted
+            .windows(2)
+            .any(|pair| (pair[1] - pair[0]).abs() < f64::EPSILON)
+        {
+            return Err(Error::DuplicateControlPoints);
+        }
+
+        Ok(Terrace {
+            control_points: sorted,
+            ..Self::new(source)
+        })
+    }
+
     /// Adds a control point to the terrace-forming curve.
     ///
     /// Two or more control points define the terrace-forming curve. The start
@@ -59,7 +118,7 @@ impl<'a, T> Terrace<'a, T> {
         if !self
             .control_points
             .iter()
-            .any(|&x| (x - control_point).abs() < std::f64::EPSILON)
+            .any(|&x| (x - control_point).abs() < f64::EPSILON)
         {
             // it doesn't, so find the correct position to insert the new
             // control point.
@@ -67,7 +126,7 @@ impl<'a, T> Terrace<'a, T> {
                 .control_points
                 .iter()
                 .position(|&x| x >= control_point)
-                .unwrap_or_else(|| self.control_points.len());
+                .unwrap_or(self.control_points.len());
 
             // add the new control point at the correct position.
             self.control_points.insert(insertion_point, control_point);
@@ -85,6 +144,14 @@ impl<'a, T> Terrace<'a, T> {
             ..self
         }
     }
+
+    pub fn set_easing(self, easing: TerraceEasing) -> Self {
+        Terrace { easing, ..self }
+    }
+
+    pub fn control_points(&self) -> &[f64] {
+        &self.control_points
+    }
 }
 
 impl<'a, T> NoiseFn<T> for Terrace<'a, T> {
@@ -101,7 +168,7 @@ impl<'a, T> NoiseFn<T> for Terrace<'a, T> {
             .control_points
             .iter()
             .position(|&x| x >= source_value)
-            .unwrap_or_else(|| self.control_points.len());
+            .unwrap_or(self.control_points.len());
 
         // Find the two nearest control points so that we can map their values
         // onto a quadratic curve.
@@ -126,8 +193,12 @@ impl<'a, T> NoiseFn<T> for Terrace<'a, T> {
             std::mem::swap(&mut input0, &mut input1);
         }
 
-        // Squaring the alpha produces the terrace effect.
-        alpha *= alpha;
+        // Flattening the curve at the control points produces the terrace
+        // effect.
+        alpha = match self.easing {
+            TerraceEasing::Quadratic => alpha * alpha,
+            TerraceEasing::Cubic => interpolate::s_curve3(alpha),
+        };
 
         // Now perform the cubic interpolation and return.
         interpolate::linear(input0, input1, alpha)
@@ -137,3 +208,89 @@ impl<'a, T> NoiseFn<T> for Terrace<'a, T> {
 fn clamp_index(index: isize, min: usize, max: usize) -> usize {
     clamp(index, min as isize, max as isize) as usize
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    fn terrace(value: f64, easing: TerraceEasing, invert: bool) -> f64 {
+        Terrace::with_control_points(&Constant::new(value), &[0.5, -1.0, 1.0])
+            .unwrap()
+            .set_easing(easing)
+            .invert_terraces(invert)
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn invalid_control_points_are_rejected() {
+        let constant = Constant::new(0.0);
+        let build =
+            |points: &[f64]| Terrace::<[f64; 2]>::with_control_points(&constant, points).err();
+
+        assert_eq!(
+            build(&[]),
+            Some(Error::TooFewControlPoints {
+                required: 2,
+                found: 0
+            })
+        );
+        assert_eq!(
+            build(&[0.5]),
+            Some(Error::TooFewControlPoints {
+                required: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            build(&[0.5, -1.0, 0.5]),
+            Some(Error::DuplicateControlPoints)
+        );
+        assert_eq!(build(&[0.5, 0.5]), Some(Error::DuplicateControlPoints));
+        assert_eq!(build(&[0.5, f64::NAN]), Some(Error::NonFiniteValue));
+        assert_eq!(
+            build(&[f64::NAN, 0.5, f64::NAN]),
+            Some(Error::NonFiniteValue)
+        );
+        assert_eq!(
+            build(&[0.0, f64::NEG_INFINITY]),
+            Some(Error::NonFiniteValue)
+        );
+        assert_eq!(build(&[0.5, -1.0]), None);
+    }
+
+    #[test]
+    fn control_points_are_sorted() {
+        let constant = Constant::new(0.0);
+        let terrace =
+            Terrace::<[f64; 2]>::with_control_points(&constant, &[0.5, -1.0, 1.0]).unwrap();
+
+        assert_eq!(terrace.control_points(), &[-1.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn easing_passes_through_control_points() {
+        for &easing in &[TerraceEasing::Quadratic, TerraceEasing::Cubic] {
+            for &invert in &[false, true] {
+                assert_eq!(terrace(-1.0, easing, invert), -1.0);
+                assert_eq!(terrace(0.5, easing, invert), 0.5);
+                assert_eq!(terrace(1.0, easing, invert), 1.0);
+                assert_eq!(terrace(-3.0, easing, invert), -1.0);
+                assert_eq!(terrace(3.0, easing, invert), 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn inverted_cubic_terraces_match_uninverted() {
+        // A quarter of the way up the step between -1.0 and 0.5.
+        let value = -0.625;
+
+        assert_eq!(terrace(value, TerraceEasing::Quadratic, false), -0.90625);
+        assert_eq!(terrace(value, TerraceEasing::Quadratic, true), -0.34375);
+
+        let cubic = terrace(value, TerraceEasing::Cubic, false);
+        assert_eq!(cubic, -1.0 + 1.5 * interpolate::s_curve3(0.25));
+        assert!((terrace(value, TerraceEasing::Cubic, true) - cubic).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{
+    math::{self, interpolate},
+    noise_fns::NoiseFn,
+};
+
+/// Noise function that turns the output value from the source function into a
+/// binary mask.
+///
+/// The output is 1.0 where the output value from the source function exceeds
+/// the threshold, and -1.0 elsewhere, including at the threshold itself.
+///
+/// With a smoothing width above zero, the step is replaced with an S-curve
+/// from -1.0 at `threshold - smoothing` to 1.0 at `threshold + smoothing`,
+/// which passes through 0.0 at the threshold.
+pub struct Threshold<'a, T> {
+    /// Outputs a value.
+    pub source: &'a dyn NoiseFn<T>,
+
+    /// Value the output value from the source function must exceed for the
+    /// output to be 1.0. The default threshold is 0.0.
+    pub threshold: f64,
+
+    /// Half the width of the transition between -1.0 and 1.0. The default
+    /// smoothing of 0.0 produces a hard step.
+    pub smoothing: f64,
+}
+
+impl<'a, T> Threshold<'a, T> {
+    pub const DEFAULT_THRESHOLD: f64 = 0.0;
+    pub const DEFAULT_SMOOTHING: f64 = 0.0;
+
+    pub fn new(source: &'a dyn NoiseFn<T>) -> Self {
+        Self {
+            source,
+            threshold: Self::DEFAULT_THRESHOLD,
+            smoothing: Self::DEFAULT_SMOOTHING,
+        }
+    }
+
+    pub fn set_threshold(self, threshold: f64) -> Self {
+        Self { threshold, ..self }
+    }
+
+    /// Sets half the width of the transition. Negative widths are treated as
+    /// 0.0.
+    pub fn set_smoothing(self, smoothing: f64) -> Self {
+        Self {
+            smoothing: smoothing.max(0.0),
+            ..self
+        }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for Threshold<'a, T> {
+    fn get(&self, point: T) -> f64 {
+        let value = self.source.get(point);
+
+        if self.smoothing > 0.0 {
+            let lower = self.threshold - self.smoothing;
+            let t = math::clamp((value - lower) / (self.smoothing * 2.0), 0.0, 1.0);
+
+            interpolate::s_curve3(t) * 2.0 - 1.0
+        } else if value > self.threshold {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    fn threshold(value: f64, threshold: f64, smoothing: f64) -> f64 {
+        Threshold::new(&Constant::new(value))
+            .set_threshold(threshold)
+            .set_smoothing(smoothing)
+            .get([0.0, 0.0, 0.0])
+    }
+
+    #[test]
+    fn hard_step() {
+        assert_eq!(threshold(0.3, 0.25, 0.0), 1.0);
+        assert_eq!(threshold(0.2, 0.25, 0.0), -1.0);
+        assert_eq!(threshold(-5.0, 0.25, 0.0), -1.0);
+
+        // Values at the threshold don't exceed it.
+        assert_eq!(threshold(0.25, 0.25, 0.0), -1.0);
+        assert_eq!(threshold(0.25, 0.25, -0.1), -1.0);
+    }
+
+    #[test]
+    fn smooth_band_endpoints() {
+        assert_eq!(threshold(0.1, 0.25, 0.15), -1.0);
+        assert_eq!(threshold(0.4, 0.25, 0.15), 1.0);
+        assert_eq!(threshold(0.25, 0.25, 0.15), 0.0);
+        assert_eq!(threshold(-1.0, 0.25, 0.15), -1.0);
+        assert_eq!(threshold(1.0, 0.25, 0.15), 1.0);
+
+        let mut previous = -1.0;
+        for i in 0..=100 {
+            let value = threshold(0.1 + i as f64 * 0.003, 0.25, 0.15);
+            assert!(value >= previous);
+            previous = value;
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{noise_fns::NoiseFn, Error};
+
+/// Noise function that wraps the output value from the source function into a
+/// repeating interval.
+///
+/// Wrapping the output of a smooth function produces repeating bands, such as
+/// contour rings over a fractal.
+pub struct Wrap<'a, T> {
+    /// Outputs a value.
+    pub source: &'a dyn NoiseFn<T>,
+
+    /// Interval the output value is wrapped into. Default is -1.0 to 1.0.
+    bounds: (f64, f64),
+
+    /// How values outside the interval are brought back into it. The default
+    /// is `WrapMode::Sawtooth`.
+    pub mode: WrapMode,
+}
+
+/// Ways of bringing a value back into the interval of a `Wrap`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WrapMode {
+    /// Jumps back to the lower bound on reaching the upper bound, like a
+    /// modulo. The output is discontinuous at every multiple of the interval.
+    Sawtooth,
+
+    /// Reflects off each bound in turn, like a triangle wave. The output is
+    /// continuous, and repeats every two widths of the interval.
+    Triangle,
+}
+
+impl<'a, T> Wrap<'a, T> {
+    pub const DEFAULT_BOUNDS: (f64, f64) = (-1.0, 1.0);
+    pub const DEFAULT_MODE: WrapMode = WrapMode::Sawtooth;
+
+    pub fn new(source: &'a dyn NoiseFn<T>) -> Self {
+        Self {
+            source,
+            bounds: Self::DEFAULT_BOUNDS,
+            mode: Self::DEFAULT_MODE,
+        }
+    }
+
+    /// Sets the interval the output value is wrapped into, without checking
+    /// it. See `try_set_bounds`.
+    ///
+    /// If `lower_bound` is not less than `upper_bound`, or either is not
+    /// finite, the output is meaningless and may be NaN.
+    pub fn set_bounds(self, lower_bound: f64, upper_bound: f64) -> Self {
+        Self {
+            bounds: (lower_bound, upper_bound),
+            ..self
+        }
+    }
+
+    /// Sets the interval the output value is wrapped into.
+    ///
+    /// Returns an error unless `lower_bound` is less than `upper_bound`.
+    pub fn try_set_bounds(self, lower_bound: f64, upper_bound: f64) -> Result<Self, Error> {
+        let width = upper_bound - lower_bound;
+        if !(lower_bound.is_finite() && upper_bound.is_finite() && width.is_finite())
+            || width <= 0.0
+        {
+            return Err(Error::InvalidRange);
+        }
+
+        Ok(self.set_bounds(lower_bound, upper_bound))
+    }
+
+    pub fn set_mode(self, mode: WrapMode) -> Self {
+        Self { mode, ..self }
+    }
+
+    pub fn bounds(&self) -> (f64, f64) {
+        self.bounds
+    }
+}
+
+impl<'a, T> NoiseFn<T> for Wrap<'a, T> {
+    fn get(&self, point: T) -> f64 {
+        let value = self.source.get(point);
+        let (lower, upper) = self.bounds;
+        let width = upper - lower;
+
+        // `rem_euclid` keeps negative offsets positive, where `%` would
+        // truncate them towards zero and leave them below the lower bound.
+        let offset = match self.mode {
+            WrapMode::Sawtooth => (value - lower).rem_euclid(width),
+            WrapMode::Triangle => {
+                let offset = (value - lower).rem_euclid(width * 2.0);
+                if offset > width {
+                    width * 2.0 - offset
+                } else {
+                    offset
+                }
+            }
+        };
+
+        // Rounding can leave tiny negative offsets equal to the full period.
+        if self.mode == WrapMode::Sawtooth && offset >= width {
+            lower
+        } else {
+            lower + offset
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    fn wrap(value: f64, bounds: (f64, f64), mode: WrapMode) -> f64 {
+        Wrap::new(&Constant::new(value))
+            .try_set_bounds(bounds.0, bounds.1)
+            .unwrap()
+            .set_mode(mode)
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn sawtooth_wraps_negative_values_upwards() {
+        let bounds = (-1.0, 1.0);
+        assert_eq!(wrap(0.25, bounds, WrapMode::Sawtooth), 0.25);
+        assert_eq!(wrap(1.5, bounds, WrapMode::Sawtooth), -0.5);
+        assert_eq!(wrap(-1.5, bounds, WrapMode::Sawtooth), 0.5);
+        assert_eq!(wrap(-3.25, bounds, WrapMode::Sawtooth), 0.75);
+        assert_eq!(wrap(1.0, bounds, WrapMode::Sawtooth), -1.0);
+        assert_eq!(wrap(-1.0, bounds, WrapMode::Sawtooth), -1.0);
+
+        // A truncated modulo would give -0.25 here.
+        assert_eq!(wrap(-0.25, (0.0, 1.0), WrapMode::Sawtooth), 0.75);
+        assert_eq!(wrap(-1e-17, (0.0, 1.0), WrapMode::Sawtooth), 0.0);
+    }
+
+    #[test]
+    fn triangle_folds_at_both_bounds() {
+        let bounds = (-1.0, 1.0);
+        assert_eq!(wrap(0.25, bounds, WrapMode::Triangle), 0.25);
+        assert_eq!(wrap(1.5, bounds, WrapMode::Triangle), 0.5);
+        assert_eq!(wrap(-1.5, bounds, WrapMode::Triangle), -0.5);
+        assert_eq!(wrap(3.5, bounds, WrapMode::Triangle), -0.5);
+        assert_eq!(wrap(-3.5, bounds, WrapMode::Triangle), 0.5);
+        assert_eq!(wrap(-0.25, (0.0, 1.0), WrapMode::Triangle), 0.25);
+    }
+
+    #[test]
+    fn output_stays_within_bounds() {
+        for &mode in &[WrapMode::Sawtooth, WrapMode::Triangle] {
+            for i in -400..400 {
+                let value = wrap(i as f64 * 0.37, (0.2, 0.9), mode);
+                assert!((0.2..=0.9).contains(&value), "{} out of bounds", value);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_interval_is_rejected() {
+        let constant = Constant::new(0.0);
+
+        for &(lower, upper) in &[(0.5, 0.5), (1.0, -1.0), (0.0, f64::INFINITY)] {
+            assert_eq!(
+                Wrap::<[f64; 2]>::new(&constant)
+                    .try_set_bounds(lower, upper)
+                    .err(),
+                Some(Error::InvalidRange)
+            );
+        }
+    }
+}
@@ -1,5 +1,9 @@
 pub use self::blend::*;
+pub use self::multi_select::*;
 pub use self::select::*;
+pub use self::switch::*;
 
 mod blend;
+mod multi_select;
 mod select;
+mod switch;
@@ -1,10 +1,17 @@
-use crate::{math::interpolate, noise_fns::NoiseFn};
+use crate::{
+    math::{self, interpolate},
+    noise_fns::NoiseFn,
+};
 
 /// Noise function that outputs a weighted blend of the output values from two
 /// source functions given the output value supplied by a control function.
 ///
-/// This noise function uses linear interpolation to perform the blending
-/// operation.
+/// By default the control value is used as the blend weight directly, clamped
+/// to [0, 1], so a control value of 0.0 outputs `source1` and 1.0 outputs
+/// `source2`. With `BlendControlRange::Signed` the control value is clamped
+/// to [-1, 1] and mapped onto [0, 1] instead. The weight is then passed
+/// through the blend's easing curve before interpolating between the two
+/// sources. The default easing is linear.
 pub struct Blend<'a, T> {
     /// Outputs one of the values to blend.
     pub source1: &'a dyn NoiseFn<T>,
@@ -12,14 +19,67 @@ pub struct Blend<'a, T> {
     /// Outputs one of the values to blend.
     pub source2: &'a dyn NoiseFn<T>,
 
-    /// Determines the weight of the blending operation. Negative values weight
-    /// the blend towards the output value from the `source1` function. Positive
+    /// Determines the weight of the blending operation. Lower values weight
+    /// the blend towards the output value from the `source1` function. Higher
     /// values weight the blend towards the output value from the `source2`
     /// function.
     pub control: &'a dyn NoiseFn<T>,
+
+    /// Range of control values that spans the two sources. The default is
+    /// `BlendControlRange::Unit`.
+    pub control_range: BlendControlRange,
+
+    /// Curve applied to the mapped control value. The default is
+    /// `BlendEasing::Linear`.
+    pub easing: BlendEasing,
+}
+
+/// Ranges of control values a `Blend` can interpolate over.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlendControlRange {
+    /// Control values within [0, 1] span the two sources.
+    #[default]
+    Unit,
+
+    /// Control values within [-1, 1] span the two sources, so the output of
+    /// most noise functions can be used as the control without remapping it.
+    Signed,
+}
+
+/// Curves a `Blend` can ease the control value with.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum BlendEasing {
+    /// Uses the clamped control value as-is.
+    #[default]
+    Linear,
+
+    /// `3t² - 2t³`, which flattens the blend near either source.
+    SmoothStep,
+
+    /// `6t⁵ - 15t⁴ + 10t³`, which flattens the blend further than
+    /// `SmoothStep`.
+    SmootherStep,
+
+    /// A user-supplied curve, given a value in [0, 1]. Curves that return
+    /// values outside [0, 1] extrapolate past the sources.
+    Custom(fn(f64) -> f64),
+}
+
+impl BlendEasing {
+    fn apply(self, t: f64) -> f64 {
+        match self {
+            Self::Linear => t,
+            Self::SmoothStep => interpolate::s_curve3(t),
+            Self::SmootherStep => interpolate::s_curve5(t),
+            Self::Custom(curve) => curve(t),
+        }
+    }
 }
 
 impl<'a, T> Blend<'a, T> {
+    pub const DEFAULT_CONTROL_RANGE: BlendControlRange = BlendControlRange::Unit;
+    pub const DEFAULT_EASING: BlendEasing = BlendEasing::Linear;
+
     pub fn new(
         source1: &'a dyn NoiseFn<T>,
         source2: &'a dyn NoiseFn<T>,
@@ -29,8 +89,21 @@ impl<'a, T> Blend<'a, T> {
             source1,
             source2,
             control,
+            control_range: Self::DEFAULT_CONTROL_RANGE,
+            easing: Self::DEFAULT_EASING,
         }
     }
+
+    pub fn set_control_range(self, control_range: BlendControlRange) -> Self {
+        Blend {
+            control_range,
+            ..self
+        }
+    }
+
+    pub fn set_easing(self, easing: BlendEasing) -> Self {
+        Blend { easing, ..self }
+    }
 }
 
 impl<'a, T> NoiseFn<T> for Blend<'a, T>
@@ -41,7 +114,87 @@ where
         let lower = self.source1.get(point);
         let upper = self.source2.get(point);
         let control = self.control.get(point);
+        let weight = match self.control_range {
+            BlendControlRange::Unit => math::clamp(control, 0.0, 1.0),
+            BlendControlRange::Signed => (math::clamp(control, -1.0, 1.0) + 1.0) * 0.5,
+        };
+        let alpha = self.easing.apply(weight);
+
+        interpolate::linear(lower, upper, alpha)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    fn blend(control: f64, range: BlendControlRange, easing: BlendEasing) -> f64 {
+        let source1 = Constant::new(-2.0);
+        let source2 = Constant::new(2.0);
+        let control = Constant::new(control);
+
+        Blend::new(&source1, &source2, &control)
+            .set_control_range(range)
+            .set_easing(easing)
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn default_weights_by_control() {
+        let blend = |control| blend(control, BlendControlRange::Unit, BlendEasing::Linear);
+
+        assert_eq!(blend(0.0), -2.0);
+        assert_eq!(blend(0.25), -1.0);
+        assert_eq!(blend(0.5), 0.0);
+        assert_eq!(blend(1.0), 2.0);
+    }
+
+    #[test]
+    fn signed_control_range_maps_onto_sources() {
+        let blend = |control| blend(control, BlendControlRange::Signed, BlendEasing::Linear);
+
+        assert_eq!(blend(-1.0), -2.0);
+        assert_eq!(blend(0.0), 0.0);
+        assert_eq!(blend(0.5), 1.0);
+        assert_eq!(blend(1.0), 2.0);
+    }
+
+    #[test]
+    fn control_outside_range_is_clamped() {
+        let easings = [
+            BlendEasing::Linear,
+            BlendEasing::SmoothStep,
+            BlendEasing::SmootherStep,
+        ];
+
+        for &easing in &easings {
+            assert_eq!(blend(-3.0, BlendControlRange::Unit, easing), -2.0);
+            assert_eq!(blend(5.0, BlendControlRange::Unit, easing), 2.0);
+            assert_eq!(blend(-3.0, BlendControlRange::Signed, easing), -2.0);
+            assert_eq!(blend(5.0, BlendControlRange::Signed, easing), 2.0);
+        }
+    }
+
+    #[test]
+    fn easing_shapes_transition() {
+        let unit = BlendControlRange::Unit;
+
+        // Smoothing pulls values towards the nearer source but keeps the
+        // midpoint.
+        assert_eq!(blend(0.5, unit, BlendEasing::SmoothStep), 0.0);
+        assert!(
+            blend(0.75, unit, BlendEasing::SmoothStep) > blend(0.75, unit, BlendEasing::Linear)
+        );
+        assert!(
+            blend(0.75, unit, BlendEasing::SmootherStep)
+                > blend(0.75, unit, BlendEasing::SmoothStep)
+        );
 
-        interpolate::linear(lower, upper, control)
+        let step = BlendEasing::Custom(|t| if t < 0.5 { 0.0 } else { 1.0 });
+        assert_eq!(blend(0.4, unit, step), -2.0);
+        assert_eq!(blend(0.6, unit, step), 2.0);
+        assert_eq!(blend(-0.1, BlendControlRange::Signed, step), -2.0);
+        assert_eq!(blend(0.1, BlendControlRange::Signed, step), 2.0);
     }
 }
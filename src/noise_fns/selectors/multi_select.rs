@@ -0,0 +1,218 @@
+use crate::{math::interpolate, noise_fns::NoiseFn, Error};
+
+/// Noise function that outputs the value from one of several source
+/// functions, chosen by the region of thresholds the output value from a
+/// control function falls into.
+///
+/// With thresholds `t0 < t1 < ... < tn`, control values below `t0` select the
+/// first source, values between `t0` and `t1` the second, and so on, with
+/// values at or above `tn` selecting the last source. Within the falloff of a
+/// threshold the two neighbouring sources are cross-faded.
+pub struct MultiSelect<'a, T> {
+    /// Determines which source to select.
+    pub control: &'a dyn NoiseFn<T>,
+
+    /// Strictly increasing thresholds separating the regions.
+    thresholds: Vec<f64>,
+
+    /// Outputs a value for each region. There is always exactly one more
+    /// source than there are thresholds.
+    sources: Vec<&'a dyn NoiseFn<T>>,
+
+    /// Half-width of the cross-fade around each threshold. Default is 0.0.
+    ///
+    /// The falloff is limited to half the gap between neighbouring
+    /// thresholds, so two cross-fades never overlap.
+    pub falloff: f64,
+}
+
+impl<'a, T> MultiSelect<'a, T> {
+    pub const DEFAULT_FALLOFF: f64 = 0.0;
+
+    /// Creates a selector that picks among `sources` by comparing the control
+    /// value against `thresholds`.
+    ///
+    /// Returns an error unless `thresholds` is strictly increasing and there
+    /// is exactly one more source than there are thresholds.
+    pub fn new(
+        control: &'a dyn NoiseFn<T>,
+        thresholds: Vec<f64>,
+        sources: Vec<&'a dyn NoiseFn<T>>,
+    ) -> Result<Self, Error> {
+        if thresholds.iter().any(|threshold| !threshold.is_finite())
+            || thresholds.windows(2).any(|pair| pair[0] >= pair[1])
+        {
+            return Err(Error::ThresholdsNotIncreasing);
+        }
+
+        if sources.len() != thresholds.len() + 1 {
+            return Err(Error::SourceCountMismatch {
+                expected: thresholds.len() + 1,
+                found: sources.len(),
+            });
+        }
+
+        Ok(Self {
+            control,
+            thresholds,
+            sources,
+            falloff: Self::DEFAULT_FALLOFF,
+        })
+    }
+
+    /// Sets the half-width of the cross-fade around each threshold. Negative
+    /// values are treated as zero.
+    pub fn set_falloff(self, falloff: f64) -> Self {
+        Self {
+            falloff: falloff.max(0.0),
+            ..self
+        }
+    }
+
+    pub fn thresholds(&self) -> &[f64] {
+        &self.thresholds
+    }
+
+    pub fn sources(&self) -> &[&'a dyn NoiseFn<T>] {
+        &self.sources
+    }
+
+    /// Falloff actually used around the threshold at `index`, limited so that
+    /// it does not reach past the midpoint to either neighbour.
+    fn falloff_at(&self, index: usize) -> f64 {
+        let threshold = self.thresholds[index];
+        let mut falloff = self.falloff;
+
+        if index > 0 {
+            falloff = falloff.min((threshold - self.thresholds[index - 1]) * 0.5);
+        }
+        if let Some(next) = self.thresholds.get(index + 1) {
+            falloff = falloff.min((next - threshold) * 0.5);
+        }
+
+        falloff
+    }
+}
+
+impl<'a, T> NoiseFn<T> for MultiSelect<'a, T>
+where
+    T: Copy,
+{
+    fn get(&self, point: T) -> f64 {
+        let control_value = self.control.get(point);
+
+        // Index of the first threshold above the control value, which is also
+        // the index of the selected region. NaN selects the first region.
+        let region = self
+            .thresholds
+            .iter()
+            .take_while(|&&threshold| control_value >= threshold)
+            .count();
+
+        // Only the thresholds bounding the region can be close enough to fade.
+        for index in region.saturating_sub(1)..(region + 1).min(self.thresholds.len()) {
+            let threshold = self.thresholds[index];
+            let falloff = self.falloff_at(index);
+            let lower_curve = threshold - falloff;
+            let upper_curve = threshold + falloff;
+
+            if falloff > 0.0 && control_value > lower_curve && control_value < upper_curve {
+                let alpha = interpolate::s_curve3(
+                    (control_value - lower_curve) / (upper_curve - lower_curve),
+                );
+
+                return interpolate::linear(
+                    self.sources[index].get(point),
+                    self.sources[index + 1].get(point),
+                    alpha,
+                );
+            }
+        }
+
+        self.sources[region].get(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    const SOURCES: [Constant; 4] = [
+        Constant { value: 0.0 },
+        Constant { value: 1.0 },
+        Constant { value: 2.0 },
+        Constant { value: 3.0 },
+    ];
+
+    fn select(control_value: f64, falloff: f64) -> f64 {
+        let control = Constant::new(control_value);
+        let sources = SOURCES
+            .iter()
+            .map(|s| s as &dyn NoiseFn<[f64; 2]>)
+            .collect();
+
+        MultiSelect::new(&control, vec![-0.5, 0.0, 0.5], sources)
+            .unwrap()
+            .set_falloff(falloff)
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn selects_region_of_control_value() {
+        assert_eq!(select(-1.0, 0.0), 0.0);
+        assert_eq!(select(-0.5, 0.0), 1.0);
+        assert_eq!(select(-0.25, 0.0), 1.0);
+        assert_eq!(select(0.25, 0.0), 2.0);
+        assert_eq!(select(0.75, 0.0), 3.0);
+        assert_eq!(select(f64::NAN, 0.0), 0.0);
+    }
+
+    #[test]
+    fn falloff_cross_fades_continuously() {
+        assert_eq!(select(-0.5, 0.1), 0.5);
+        assert_eq!(select(0.5, 0.1), 2.5);
+        assert_eq!(select(-0.35, 0.1), 1.0);
+
+        // Even with a falloff wider than the gaps, the output rises smoothly.
+        for &falloff in &[0.1, 0.25, 10.0] {
+            let mut previous = select(-1.0, falloff);
+            for step in 1..=400 {
+                let value = select(-1.0 + step as f64 * 0.005, falloff);
+                assert!(value >= previous && value - previous < 0.05);
+                previous = value;
+            }
+            assert_eq!(previous, 3.0);
+        }
+    }
+
+    #[test]
+    fn invalid_construction_is_rejected() {
+        let control = Constant::new(0.0);
+        let sources = || {
+            SOURCES
+                .iter()
+                .map(|s| s as &dyn NoiseFn<[f64; 2]>)
+                .collect()
+        };
+
+        for thresholds in [
+            vec![0.0, 0.0, 1.0],
+            vec![1.0, 0.0, 2.0],
+            vec![0.0, f64::NAN, 1.0],
+        ] {
+            assert_eq!(
+                MultiSelect::new(&control, thresholds, sources()).err(),
+                Some(Error::ThresholdsNotIncreasing)
+            );
+        }
+
+        assert_eq!(
+            MultiSelect::new(&control, vec![0.0, 1.0], sources()).err(),
+            Some(Error::SourceCountMismatch {
+                expected: 3,
+                found: 4
+            })
+        );
+    }
+}
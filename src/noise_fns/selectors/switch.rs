@@ -0,0 +1,128 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that outputs the value from one of two source functions,
+/// chosen by whether the output value from a control function reaches a
+/// threshold.
+///
+/// Unlike `Select`, there is no falloff: the output jumps from one source to
+/// the other at the threshold. Only the chosen source function is evaluated,
+/// so an expensive source costs nothing at points where it isn't selected.
+pub struct Switch<'a, T> {
+    /// Outputs the value where the control value is below the threshold.
+    pub source1: &'a dyn NoiseFn<T>,
+
+    /// Outputs the value where the control value is at or above the
+    /// threshold.
+    pub source2: &'a dyn NoiseFn<T>,
+
+    /// Determines which source function to evaluate.
+    pub control: &'a dyn NoiseFn<T>,
+
+    /// Control value from which `source2` is selected. Default is 0.0.
+    ///
+    /// A NaN control value never reaches the threshold, and selects
+    /// `source1`.
+    pub threshold: f64,
+}
+
+impl<'a, T> Switch<'a, T> {
+    pub const DEFAULT_THRESHOLD: f64 = 0.0;
+
+    pub fn new(
+        source1: &'a dyn NoiseFn<T>,
+        source2: &'a dyn NoiseFn<T>,
+        control: &'a dyn NoiseFn<T>,
+    ) -> Self {
+        Self {
+            source1,
+            source2,
+            control,
+            threshold: Self::DEFAULT_THRESHOLD,
+        }
+    }
+
+    pub fn set_threshold(self, threshold: f64) -> Self {
+        Self { threshold, ..self }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for Switch<'a, T>
+where
+    T: Copy,
+{
+    fn get(&self, point: T) -> f64 {
+        if self.control.get(point) >= self.threshold {
+            self.source2.get(point)
+        } else {
+            self.source1.get(point)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Constant, Perlin};
+    use std::cell::Cell;
+
+    // Outputs the value of its source, counting how often it is evaluated.
+    struct Probe<'a> {
+        source: &'a dyn NoiseFn<[f64; 2]>,
+        calls: Cell<usize>,
+    }
+
+    impl<'a> Probe<'a> {
+        fn new(source: &'a dyn NoiseFn<[f64; 2]>) -> Self {
+            Self {
+                source,
+                calls: Cell::new(0),
+            }
+        }
+    }
+
+    impl NoiseFn<[f64; 2]> for Probe<'_> {
+        fn get(&self, point: [f64; 2]) -> f64 {
+            self.calls.set(self.calls.get() + 1);
+            self.source.get(point)
+        }
+    }
+
+    #[test]
+    fn only_the_chosen_source_is_evaluated() {
+        let (low, high) = (Constant::new(-1.0), Constant::new(1.0));
+        let (low, high) = (Probe::new(&low), Probe::new(&high));
+        let perlin = Perlin::new();
+        let switch = Switch::new(&low, &high, &perlin).set_threshold(0.1);
+
+        let mut selected_high = 0;
+        for i in 0..500 {
+            let point = [i as f64 * 0.173, i as f64 * 0.071];
+            let value = switch.get(point);
+
+            assert_eq!(value, if perlin.get(point) >= 0.1 { 1.0 } else { -1.0 });
+            if value == 1.0 {
+                selected_high += 1;
+            }
+        }
+
+        assert!(selected_high > 0 && selected_high < 500);
+        assert_eq!(high.calls.get(), selected_high);
+        assert_eq!(low.calls.get(), 500 - selected_high);
+    }
+
+    #[test]
+    fn threshold_selects_second_source() {
+        let (low, high) = (Constant::new(-1.0), Constant::new(1.0));
+        let switch = |control: f64| {
+            let control = Constant::new(control);
+            Switch::new(&low, &high, &control)
+                .set_threshold(0.25)
+                .get([0.0, 0.0])
+        };
+
+        assert_eq!(switch(0.25), 1.0);
+        assert_eq!(switch(0.25 - f64::EPSILON), -1.0);
+        assert_eq!(switch(f64::INFINITY), 1.0);
+        assert_eq!(switch(f64::NAN), -1.0);
+    }
+}
@@ -1,11 +1,17 @@
+pub use self::box_filtered_noise::*;
 pub use self::displace::*;
+pub use self::domain_warp::*;
 pub use self::rotate_point::*;
 pub use self::scale_point::*;
+pub use self::tileable_noise_3d::*;
 pub use self::translate_point::*;
 pub use self::turbulence::*;
 
+mod box_filtered_noise;
 mod displace;
+mod domain_warp;
 mod rotate_point;
 mod scale_point;
+mod tileable_noise_3d;
 mod translate_point;
 mod turbulence;
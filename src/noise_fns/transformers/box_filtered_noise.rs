@@ -0,0 +1,170 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that averages the output values from the source function
+/// over a small box around the input point.
+///
+/// When noise is rendered with fewer samples than it has detail, features
+/// smaller than a pixel alias into noisy speckles, which shimmer as the noise
+/// moves. Averaging each pixel over its own area filters that detail out.
+///
+/// The box is centred on the _x_ and _y_ coordinates of the input value, and
+/// divided into `sample_count` by `sample_count` cells that are each sampled
+/// at their centre. The _z_ coordinate, if any, is left as is, so the filter
+/// works on the planes that `PlaneMapBuilder` renders. Each output value costs
+/// `sample_count²` evaluations of the source function.
+pub struct BoxFilteredNoise<Source> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Width of the box, in input units. For rendering, this is the distance
+    /// between neighbouring pixels. The default filter size is 1.0.
+    pub filter_size: f64,
+
+    /// Number of samples along each side of the box. The default sample count
+    /// is 4.
+    pub sample_count: usize,
+}
+
+impl<Source> BoxFilteredNoise<Source> {
+    pub const DEFAULT_FILTER_SIZE: f64 = 1.0;
+    pub const DEFAULT_SAMPLE_COUNT: usize = 4;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            filter_size: Self::DEFAULT_FILTER_SIZE,
+            sample_count: Self::DEFAULT_SAMPLE_COUNT,
+        }
+    }
+
+    /// Sets the width of the box. Negative widths are treated as 0.0, which
+    /// disables the filter.
+    pub fn set_filter_size(self, filter_size: f64) -> Self {
+        Self {
+            filter_size: filter_size.max(0.0),
+            ..self
+        }
+    }
+
+    /// Sets the number of samples along each side of the box. A count of 0 is
+    /// treated as 1.
+    pub fn set_sample_count(self, sample_count: usize) -> Self {
+        Self {
+            sample_count: sample_count.max(1),
+            ..self
+        }
+    }
+
+    /// Calls `sample` with the offset of the centre of each cell of the box
+    /// from the input point, and returns the average of the results.
+    fn filter(&self, mut sample: impl FnMut(f64, f64) -> f64) -> f64 {
+        if self.filter_size <= 0.0 {
+            return sample(0.0, 0.0);
+        }
+
+        let count = self.sample_count.max(1);
+        let step = self.filter_size / count as f64;
+        let start = (step - self.filter_size) * 0.5;
+
+        let mut total = 0.0;
+        for y in 0..count {
+            let dy = start + step * y as f64;
+            for x in 0..count {
+                total += sample(start + step * x as f64, dy);
+            }
+        }
+
+        total / (count * count) as f64
+    }
+}
+
+impl<Source> NoiseFn<[f64; 2]> for BoxFilteredNoise<Source>
+where
+    Source: NoiseFn<[f64; 2]>,
+{
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.filter(|dx, dy| self.source.get([point[0] + dx, point[1] + dy]))
+    }
+}
+
+impl<Source> NoiseFn<[f64; 3]> for BoxFilteredNoise<Source>
+where
+    Source: NoiseFn<[f64; 3]>,
+{
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.filter(|dx, dy| self.source.get([point[0] + dx, point[1] + dy, point[2]]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Constant, Perlin, ScalePoint};
+
+    /// Root mean square of the output of `noise` over a grid of 1024 pixels
+    /// spaced `pixel` apart, and of its difference from `reference`.
+    fn rms(
+        noise: &dyn NoiseFn<[f64; 2]>,
+        reference: &dyn NoiseFn<[f64; 2]>,
+        pixel: f64,
+    ) -> (f64, f64) {
+        let (mut power, mut difference) = (0.0, 0.0);
+        for y in 0..32 {
+            for x in 0..32 {
+                let point = [x as f64 * pixel + 0.31, y as f64 * pixel - 0.17];
+                let value = noise.get(point);
+                power += value * value;
+                difference += (value - reference.get(point)).powi(2);
+            }
+        }
+
+        ((power / 1024.0).sqrt(), (difference / 1024.0).sqrt())
+    }
+
+    #[test]
+    fn filtering_removes_detail_finer_than_a_pixel() {
+        const PIXEL: f64 = 0.1;
+
+        let mut differences = Vec::new();
+        for &scale in &[0.05, 1.3, 23.7, 213.1] {
+            let perlin = ScalePoint::new(Perlin::new()).set_scale(scale);
+            let filtered = BoxFilteredNoise::new(&perlin)
+                .set_filter_size(PIXEL)
+                .set_sample_count(8);
+
+            let (unfiltered_rms, _) = rms(&perlin, &perlin, PIXEL);
+            let (filtered_rms, difference) = rms(&filtered, &perlin, PIXEL);
+
+            if scale * PIXEL >= 2.0 {
+                // Several features fall within each pixel and mostly cancel.
+                assert!(
+                    filtered_rms < unfiltered_rms * 0.5,
+                    "{} vs {} at scale {}",
+                    filtered_rms,
+                    unfiltered_rms,
+                    scale
+                );
+            }
+            differences.push(difference);
+        }
+
+        // Features much larger than a pixel are left nearly untouched.
+        assert!(differences[0] < 1e-3, "{:?}", differences);
+        for pair in differences.windows(2) {
+            assert!(pair[0] < pair[1], "{:?} is not increasing", differences);
+        }
+    }
+
+    #[test]
+    fn filter_preserves_constants_and_handles_zero_size() {
+        let constant = Constant::new(0.4);
+        let filtered = BoxFilteredNoise::new(&constant).set_sample_count(0);
+        assert_eq!(filtered.sample_count, 1);
+        assert!((filtered.get([0.0, 0.0, 0.0]) - 0.4).abs() < 1e-12);
+
+        let perlin = Perlin::new();
+        let unfiltered = BoxFilteredNoise::new(&perlin).set_filter_size(-1.0);
+        let point = [0.37, 1.91, -0.5];
+        assert_eq!(unfiltered.get(point), perlin.get(point));
+    }
+}
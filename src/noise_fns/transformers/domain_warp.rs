@@ -0,0 +1,278 @@
+use crate::{
+    math,
+    noise_fns::{NoiseFn, Seedable},
+};
+
+/// Noise function that offsets the input value by the output of a warp
+/// function before returning the output value from the source function.
+///
+/// Each axis of the input value is offset by its own instance of the warp
+/// function, seeded with consecutive seeds starting from the seed of the
+/// `DomainWarp`. Using a fractal such as `Fbm` as the warp function
+/// produces the swirling, folded patterns described by Inigo Quilez in
+/// <https://iquilezles.org/articles/warp/>.
+///
+/// With more than one octave, the warp is applied progressively: each octave
+/// offsets the point produced by the previous one, at a higher frequency and
+/// a lower amplitude.
+#[derive(Clone, Debug)]
+pub struct DomainWarp<Source, Warp> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Scale of the offset applied to each axis of the input value. The
+    /// default amplitude is 1.0.
+    pub amplitude: f64,
+
+    /// Frequency at which the warp function is sampled. The default
+    /// frequency is 1.0.
+    pub frequency: f64,
+
+    /// Number of times the warp is applied to the input value. The default
+    /// of 1 warps the input value once.
+    pub octaves: usize,
+
+    /// Multiplier applied to the frequency of the warp after each octave.
+    pub lacunarity: f64,
+
+    /// Multiplier applied to the amplitude of the warp after each octave.
+    pub persistence: f64,
+
+    seed: u32,
+    warps: [Warp; 4],
+}
+
+/// Offsets added to the sampling point of each axis' warp function, to keep
+/// the sampled points away from the integer boundaries at which gradient
+/// noise is always zero.
+const AXIS_OFFSETS: [f64; 4] = [
+    12414.0 / 65536.0,
+    26519.0 / 65536.0,
+    53820.0 / 65536.0,
+    31337.0 / 65536.0,
+];
+
+impl<Source, Warp> DomainWarp<Source, Warp>
+where
+    Warp: Seedable + Clone,
+{
+    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_AMPLITUDE: f64 = 1.0;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_OCTAVES: usize = 1;
+    pub const DEFAULT_LACUNARITY: f64 = 2.0;
+    pub const DEFAULT_PERSISTENCE: f64 = 0.5;
+
+    pub fn new(source: Source, warp: Warp) -> Self {
+        Self {
+            source,
+            amplitude: Self::DEFAULT_AMPLITUDE,
+            frequency: Self::DEFAULT_FREQUENCY,
+            octaves: Self::DEFAULT_OCTAVES,
+            lacunarity: Self::DEFAULT_LACUNARITY,
+            persistence: Self::DEFAULT_PERSISTENCE,
+            seed: Self::DEFAULT_SEED,
+            warps: build_warps(&warp, Self::DEFAULT_SEED),
+        }
+    }
+
+    pub fn set_amplitude(self, amplitude: f64) -> Self {
+        Self { amplitude, ..self }
+    }
+
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    /// Sets the number of times the warp is applied. The octave count is
+    /// clamped to a minimum of 1.
+    pub fn set_octaves(self, octaves: usize) -> Self {
+        Self {
+            octaves: octaves.max(1),
+            ..self
+        }
+    }
+
+    pub fn set_lacunarity(self, lacunarity: f64) -> Self {
+        Self { lacunarity, ..self }
+    }
+
+    pub fn set_persistence(self, persistence: f64) -> Self {
+        Self {
+            persistence,
+            ..self
+        }
+    }
+}
+
+fn build_warps<Warp: Seedable + Clone>(warp: &Warp, seed: u32) -> [Warp; 4] {
+    [
+        warp.clone().set_seed(seed),
+        warp.clone().set_seed(seed.wrapping_add(1)),
+        warp.clone().set_seed(seed.wrapping_add(2)),
+        warp.clone().set_seed(seed.wrapping_add(3)),
+    ]
+}
+
+impl<Source, Warp> Seedable for DomainWarp<Source, Warp>
+where
+    Warp: Seedable + Clone,
+{
+    fn set_seed(self, seed: u32) -> Self {
+        if self.seed == seed {
+            return self;
+        }
+
+        Self {
+            seed,
+            warps: build_warps(&self.warps[0], seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed
+    }
+}
+
+impl<Source, Warp> NoiseFn<[f64; 2]> for DomainWarp<Source, Warp>
+where
+    Source: NoiseFn<[f64; 2]>,
+    Warp: NoiseFn<[f64; 2]>,
+{
+    fn get(&self, mut point: [f64; 2]) -> f64 {
+        let mut amplitude = self.amplitude;
+        let mut frequency = self.frequency;
+
+        for _ in 0..self.octaves {
+            let sample = math::mul2(point, frequency);
+            let offset = |axis: usize| {
+                let sample = math::map2(sample, |v| v + AXIS_OFFSETS[axis]);
+                self.warps[axis].get(sample) * amplitude
+            };
+
+            point = math::add2(point, [offset(0), offset(1)]);
+
+            amplitude *= self.persistence;
+            frequency *= self.lacunarity;
+        }
+
+        self.source.get(point)
+    }
+}
+
+impl<Source, Warp> NoiseFn<[f64; 3]> for DomainWarp<Source, Warp>
+where
+    Source: NoiseFn<[f64; 3]>,
+    Warp: NoiseFn<[f64; 3]>,
+{
+    fn get(&self, mut point: [f64; 3]) -> f64 {
+        let mut amplitude = self.amplitude;
+        let mut frequency = self.frequency;
+
+        for _ in 0..self.octaves {
+            let sample = math::mul3(point, frequency);
+            let offset = |axis: usize| {
+                let sample = math::map3(sample, |v| v + AXIS_OFFSETS[axis]);
+                self.warps[axis].get(sample) * amplitude
+            };
+
+            point = math::add3(point, [offset(0), offset(1), offset(2)]);
+
+            amplitude *= self.persistence;
+            frequency *= self.lacunarity;
+        }
+
+        self.source.get(point)
+    }
+}
+
+impl<Source, Warp> NoiseFn<[f64; 4]> for DomainWarp<Source, Warp>
+where
+    Source: NoiseFn<[f64; 4]>,
+    Warp: NoiseFn<[f64; 4]>,
+{
+    fn get(&self, mut point: [f64; 4]) -> f64 {
+        let mut amplitude = self.amplitude;
+        let mut frequency = self.frequency;
+
+        for _ in 0..self.octaves {
+            let sample = math::mul4(point, frequency);
+            let offset = |axis: usize| {
+                let sample = math::map4(sample, |v| v + AXIS_OFFSETS[axis]);
+                self.warps[axis].get(sample) * amplitude
+            };
+
+            point = math::add4(point, [offset(0), offset(1), offset(2), offset(3)]);
+
+            amplitude *= self.persistence;
+            frequency *= self.lacunarity;
+        }
+
+        self.source.get(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Perlin;
+
+    // Outputs the x coordinate of the input value, so the warped point can be
+    // read back from the output.
+    struct AxisX;
+
+    impl NoiseFn<[f64; 2]> for AxisX {
+        fn get(&self, point: [f64; 2]) -> f64 {
+            point[0]
+        }
+    }
+
+    // Warp function with a constant output of 0.5.
+    #[derive(Clone)]
+    struct Half;
+
+    impl NoiseFn<[f64; 2]> for Half {
+        fn get(&self, _point: [f64; 2]) -> f64 {
+            0.5
+        }
+    }
+
+    impl Seedable for Half {
+        fn set_seed(self, _seed: u32) -> Self {
+            self
+        }
+
+        fn seed(&self) -> u32 {
+            0
+        }
+    }
+
+    #[test]
+    fn zero_amplitude_is_identity() {
+        let warp = DomainWarp::new(AxisX, Perlin::new()).set_amplitude(0.0);
+
+        for i in 0..50 {
+            let x = i as f64 * 0.37 - 5.0;
+            assert_eq!(warp.get([x, 1.5]), x);
+        }
+    }
+
+    #[test]
+    fn constant_warp_offsets_every_octave() {
+        let warp = DomainWarp::new(AxisX, Half)
+            .set_amplitude(2.0)
+            .set_octaves(3);
+
+        // 2.0 * 0.5 + 1.0 * 0.5 + 0.5 * 0.5
+        assert_eq!(warp.get([0.0, 0.0]), 1.75);
+    }
+
+    #[test]
+    fn axes_use_different_seeds() {
+        let warp = DomainWarp::new(AxisX, Perlin::new()).set_seed(4);
+
+        let seeds: Vec<u32> = warp.warps.iter().map(Seedable::seed).collect();
+        assert_eq!(seeds, [4, 5, 6, 7]);
+    }
+}
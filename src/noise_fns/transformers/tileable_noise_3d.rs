@@ -0,0 +1,118 @@
+use crate::{math, noise_fns::NoiseFn};
+use std::f64::consts::PI;
+
+/// Noise function that makes the output value from a 4-dimensional source
+/// function tile seamlessly along all three axes of the input value.
+///
+/// Tiling in 3D is ideally done by mapping the input value onto a
+/// 6-dimensional torus and sampling 6-dimensional noise, but no generator in
+/// this crate supports six dimensions. Instead, each pair of axes (_xy_, _yz_
+/// and _zx_) is wrapped around its own 4-dimensional torus and sampled from
+/// the source function at a different offset, and the three output values are
+/// averaged. Every axis is periodic in the two samples it takes part in, so
+/// the average tiles in all three directions.
+///
+/// Each call to get() costs three 4-dimensional evaluations of the source
+/// function. Averaging three samples also narrows the spread of the output
+/// values compared to those of the source function.
+#[derive(Clone, Debug)]
+pub struct TileableNoise3D<Source> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Length of the tile along the _x_, _y_ and _z_ axes. The default tile
+    /// size is 1.0 along each axis.
+    pub tile_size: [f64; 3],
+}
+
+impl<Source> TileableNoise3D<Source> {
+    pub const DEFAULT_TILE_SIZE: [f64; 3] = [1.0; 3];
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            tile_size: Self::DEFAULT_TILE_SIZE,
+        }
+    }
+
+    /// Sets the length of the tile along each axis of the input value.
+    pub fn set_tile_size(self, tile_size: [f64; 3]) -> Self {
+        Self { tile_size, ..self }
+    }
+}
+
+impl<Source> NoiseFn<[f64; 3]> for TileableNoise3D<Source>
+where
+    Source: NoiseFn<[f64; 4]>,
+{
+    fn get(&self, point: [f64; 3]) -> f64 {
+        // Offset each torus so that the three samples are taken from
+        // unrelated regions of the source function.
+        const XY_OFFSET: [f64; 4] = [0.0, 0.0, 0.0, 0.0];
+        const YZ_OFFSET: [f64; 4] = [107.0, 31.0, 59.0, 83.0];
+        const ZX_OFFSET: [f64; 4] = [47.0, 97.0, 13.0, 71.0];
+
+        let [x, y, z] = point;
+        let [x_size, y_size, z_size] = self.tile_size;
+
+        let xy = math::add4(torus(x, y, x_size, y_size), XY_OFFSET);
+        let yz = math::add4(torus(y, z, y_size, z_size), YZ_OFFSET);
+        let zx = math::add4(torus(z, x, z_size, x_size), ZX_OFFSET);
+
+        (self.source.get(xy) + self.source.get(yz) + self.source.get(zx)) / 3.0
+    }
+}
+
+/// Maps a pair of coordinates onto the surface of a 4-dimensional torus, so
+/// that `a` repeats every `a_size` units and `b` every `b_size` units.
+///
+/// The radii of the torus are chosen so that distances along its surface
+/// match distances in the input space, keeping the scale of the features.
+fn torus(a: f64, b: f64, a_size: f64, b_size: f64) -> [f64; 4] {
+    let a_angle = a / a_size * 2.0 * PI;
+    let b_angle = b / b_size * 2.0 * PI;
+    let a_radius = a_size / (2.0 * PI);
+    let b_radius = b_size / (2.0 * PI);
+
+    [
+        a_angle.cos() * a_radius,
+        a_angle.sin() * a_radius,
+        b_angle.cos() * b_radius,
+        b_angle.sin() * b_radius,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::Perlin;
+    use super::*;
+
+    #[test]
+    fn opposite_faces_match() {
+        let tile_size = [2.0, 3.0, 5.0];
+        let tileable = TileableNoise3D::new(Perlin::new()).set_tile_size(tile_size);
+
+        for i in 0..=10 {
+            for j in 0..=10 {
+                let (u, v) = (i as f64 / 10.0, j as f64 / 10.0);
+
+                let x_faces = (
+                    tileable.get([0.0, u * tile_size[1], v * tile_size[2]]),
+                    tileable.get([tile_size[0], u * tile_size[1], v * tile_size[2]]),
+                );
+                let y_faces = (
+                    tileable.get([u * tile_size[0], 0.0, v * tile_size[2]]),
+                    tileable.get([u * tile_size[0], tile_size[1], v * tile_size[2]]),
+                );
+                let z_faces = (
+                    tileable.get([u * tile_size[0], v * tile_size[1], 0.0]),
+                    tileable.get([u * tile_size[0], v * tile_size[1], tile_size[2]]),
+                );
+
+                for &(near, far) in &[x_faces, y_faces, z_faces] {
+                    assert!((near - far).abs() < 1e-9, "{} != {}", near, far);
+                }
+            }
+        }
+    }
+}
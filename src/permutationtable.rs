@@ -1,43 +1,56 @@
+use core::fmt;
 use rand::seq::SliceRandom;
 use rand::{
     distributions::{Distribution, Standard},
     Rng, SeedableRng,
 };
 use rand_xorshift::XorShiftRng;
-use std::fmt;
 
-const TABLE_SIZE: usize = 256;
+const DEFAULT_TABLE_SIZE: usize = 256;
 
 /// A seed table, required by all noise functions.
 ///
+/// The table holds a permutation of the integers below `N`, which must be a
+/// power of two between 64 and 65536. Lattice coordinates are wrapped to the
+/// table size, so the output of a noise function built on a table repeats
+/// every `N` units along each axis.
+///
+/// Entries are stored as `u16`, so that tables of more than 256 entries can
+/// be built. `Perlin`, `Value` and `WhiteNoise` expose the table size through
+/// their `with_table_size` constructors. The skewed lattices of the simplex
+/// generators don't repeat at the size of the table, and Worley noise reads
+/// fixed bit fields out of each entry, so those always use 256 entries.
+///
 /// Table creation is expensive, so in most circumstances you'll only want to
 /// create one of these per generator.
 #[derive(Copy, Clone)]
-pub(crate) struct PermutationTable {
-    values: [u8; TABLE_SIZE],
+pub(crate) struct PermutationTable<const N: usize = DEFAULT_TABLE_SIZE> {
+    values: [u16; N],
 }
 
-impl Distribution<PermutationTable> for Standard {
+impl<const N: usize> Distribution<PermutationTable<N>> for Standard {
     /// Generates a PermutationTable using a random seed.
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> PermutationTable {
-        let mut seq: Vec<u8> = (0..TABLE_SIZE).map(|x| x as u8).collect();
-        seq.shuffle(rng);
-
-        // It's unfortunate that this double-initializes the array, but Rust
-        // doesn't currently provide a clean way to do this in one pass. Hopefully
-        // it won't matter, as Seed creation will usually be a one-time event.
-        let mut perm_table = PermutationTable {
-            values: [0; TABLE_SIZE],
-        };
-        let seq_it = seq.iter();
-        for (x, y) in perm_table.values.iter_mut().zip(seq_it) {
-            *x = *y
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> PermutationTable<N> {
+        let () = PermutationTable::<N>::VALID_SIZE;
+
+        // Shuffle the table in place, so that no allocation is needed.
+        let mut perm_table = PermutationTable { values: [0; N] };
+        for (x, value) in perm_table.values.iter_mut().enumerate() {
+            *value = x as u16;
         }
+        perm_table.values.shuffle(rng);
+
         perm_table
     }
 }
 
-impl PermutationTable {
+impl<const N: usize> PermutationTable<N> {
+    /// Fails to compile when the table is used with an unsupported size.
+    const VALID_SIZE: () = assert!(
+        N.is_power_of_two() && N >= 64 && N <= 65536,
+        "the size of a PermutationTable must be a power of two between 64 and 65536"
+    );
+
     /// Deterministically generates a new permutation table based on a `u32` seed value.
     ///
     /// Internally this uses a `XorShiftRng`, but we don't really need to worry
@@ -55,28 +68,37 @@ impl PermutationTable {
         rng.gen()
     }
 
-    pub fn get1(&self, x: isize) -> usize {
-        let x = (x & 0xff) as usize;
-        self.values[x] as usize
+    pub fn get1(&self, pos: [isize; 1]) -> usize {
+        self.hash_coords(&pos)
     }
 
     pub fn get2(&self, pos: [isize; 2]) -> usize {
-        let y = (pos[1] & 0xff) as usize;
-        self.values[self.get1(pos[0]) ^ y] as usize
+        self.hash_coords(&pos)
     }
 
     pub fn get3(&self, pos: [isize; 3]) -> usize {
-        let z = (pos[2] & 0xff) as usize;
-        self.values[self.get2([pos[0], pos[1]]) ^ z] as usize
+        self.hash_coords(&pos)
     }
 
     pub fn get4(&self, pos: [isize; 4]) -> usize {
-        let w = (pos[3] & 0xff) as usize;
-        self.values[self.get3([pos[0], pos[1], pos[2]]) ^ w] as usize
+        self.hash_coords(&pos)
+    }
+
+    /// Hashes the coordinates of a lattice point into an index of the table.
+    ///
+    /// Each coordinate is wrapped to the size of the table and mixed in with
+    /// the hash of the coordinates before it, so `hash_coords(&[x, y])` is
+    /// `values[values[x] ^ y]`.
+    #[inline(always)]
+    fn hash_coords(&self, pos: &[isize]) -> usize {
+        pos.iter().fold(0, |hash, &coord| {
+            let coord = (coord & (N as isize - 1)) as usize;
+            self.values[hash ^ coord] as usize
+        })
     }
 }
 
-impl fmt::Debug for PermutationTable {
+impl<const N: usize> fmt::Debug for PermutationTable<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "PermutationTable {{ .. }}")
     }
@@ -84,6 +106,7 @@ impl fmt::Debug for PermutationTable {
 
 #[cfg(test)]
 mod tests {
+    use super::PermutationTable;
     use crate::{NoiseFn, Perlin, Seedable};
     use rand::random;
 
@@ -93,6 +116,33 @@ mod tests {
         let _ = perlin.get([1.0, 2.0, 3.0]);
     }
 
+    #[test]
+    fn get1_matches_table() {
+        let perm_table = PermutationTable::<256>::new(0);
+
+        for x in -512..512 {
+            assert_eq!(
+                perm_table.get1([x]),
+                perm_table.values[(x & 0xff) as usize] as usize
+            );
+        }
+    }
+
+    #[test]
+    fn smaller_table_wraps_at_its_size() {
+        let perm_table = PermutationTable::<64>::new(0);
+
+        let mut values = perm_table.values.to_vec();
+        values.sort_unstable();
+        assert!(values.iter().copied().eq(0..64));
+
+        for x in -8..8 {
+            for y in -8..8 {
+                assert_eq!(perm_table.get2([x, y]), perm_table.get2([x + 64, y - 128]));
+            }
+        }
+    }
+
     #[test]
     fn test_negative_params() {
         let perlin = Perlin::new();
@@ -0,0 +1,175 @@
+//! Chains noise functions into a pipeline without storing each stage in its
+//! own variable.
+
+use crate::noise_fns::{Abs, Clamp, Negate, NoiseFn, ScaleBias, Turbulence};
+
+/// Noise function built by chaining modifiers and transformers onto a source
+/// function.
+///
+/// Each stage owns the stage before it, boxed as a `dyn NoiseFn`, so the
+/// pipeline can be stored and passed around like any other noise function:
+///
+/// ```
+/// use noice::{Fbm, NoiseFn, NoisePipeline};
+///
+/// let noise = NoisePipeline::source(Fbm::new())
+///     .scale_bias(1.0, 0.0)
+///     .clamp(-1.0, 1.0)
+///     .turbulence(0.5);
+///
+/// let value = noise.get([0.5, 1.5]);
+/// ```
+///
+/// The [`noise_pipeline!`](crate::noise_pipeline) macro builds the same
+/// pipeline from a list of stages.
+pub struct NoisePipeline<T = [f64; 2]> {
+    noise: Box<dyn NoiseFn<T>>,
+}
+
+impl<T> NoisePipeline<T>
+where
+    T: Copy + 'static,
+{
+    /// Starts a pipeline with the given source function.
+    pub fn source<N>(source: N) -> Self
+    where
+        N: NoiseFn<T> + 'static,
+    {
+        Self {
+            noise: Box::new(source),
+        }
+    }
+
+    /// Appends a stage that computes its output from the output of the
+    /// pipeline so far.
+    fn then<F>(self, stage: F) -> Self
+    where
+        F: Fn(&dyn NoiseFn<T>, T) -> f64 + 'static,
+    {
+        let source = self.noise;
+        Self::source(Stage { source, stage })
+    }
+
+    /// Appends a `ScaleBias` stage.
+    pub fn scale_bias(self, scale: f64, bias: f64) -> Self {
+        self.then(move |source, point| {
+            ScaleBias::new(source)
+                .set_scale(scale)
+                .set_bias(bias)
+                .get(point)
+        })
+    }
+
+    /// Appends a `Clamp` stage.
+    pub fn clamp(self, lower_bound: f64, upper_bound: f64) -> Self {
+        self.then(move |source, point| {
+            Clamp::new(source)
+                .set_bounds(lower_bound, upper_bound)
+                .get(point)
+        })
+    }
+
+    /// Appends an `Abs` stage.
+    pub fn abs(self) -> Self {
+        self.then(|source, point| Abs::new(source).get(point))
+    }
+
+    /// Appends a `Negate` stage.
+    pub fn negate(self) -> Self {
+        self.then(|source, point| Negate::new(source).get(point))
+    }
+
+    /// Appends a `Turbulence` stage with the given power.
+    pub fn turbulence(self, power: f64) -> Self
+    where
+        Turbulence<Box<dyn NoiseFn<T>>>: NoiseFn<T>,
+    {
+        Self::source(Turbulence::new(self.noise).set_power(power))
+    }
+}
+
+impl<T> NoiseFn<T> for NoisePipeline<T> {
+    fn get(&self, point: T) -> f64 {
+        self.noise.get(point)
+    }
+}
+
+/// A stage of a pipeline, holding the stages before it.
+struct Stage<T, F> {
+    source: Box<dyn NoiseFn<T>>,
+    stage: F,
+}
+
+impl<T, F> NoiseFn<T> for Stage<T, F>
+where
+    F: Fn(&dyn NoiseFn<T>, T) -> f64,
+{
+    fn get(&self, point: T) -> f64 {
+        (self.stage)(&*self.source, point)
+    }
+}
+
+/// Builds a [`NoisePipeline`](crate::NoisePipeline) from a source function
+/// followed by a list of stages.
+///
+/// ```
+/// use noice::{noise_pipeline, Fbm, NoiseFn};
+///
+/// let noise = noise_pipeline!(
+///     Fbm::new()
+///         => scale_bias(1.0, 0.0)
+///         => clamp(-1.0, 1.0)
+///         => turbulence(0.5)
+/// );
+///
+/// let value = noise.get([0.5, 1.5]);
+/// ```
+#[macro_export]
+macro_rules! noise_pipeline {
+    ($source:expr $(=> $stage:ident ( $($arg:expr),* $(,)? ))* $(,)?) => {
+        $crate::NoisePipeline::source($source) $(.$stage($($arg),*))*
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Perlin, Seedable, Simplex};
+
+    #[test]
+    fn matches_nested_modifiers() {
+        let simplex = Simplex::new().set_seed(9);
+        let scaled = ScaleBias::new(&simplex).set_scale(3.0).set_bias(0.5);
+        let clamped = Clamp::new(&scaled).set_bounds(-1.0, 1.0);
+        let nested = Negate::new(&clamped);
+
+        let pipeline = NoisePipeline::source(simplex)
+            .scale_bias(3.0, 0.5)
+            .clamp(-1.0, 1.0)
+            .negate();
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173 - 8.0, i as f64 * 0.091];
+            assert_eq!(pipeline.get(point), nested.get(point));
+        }
+    }
+
+    #[test]
+    fn macro_matches_builder() {
+        let built = NoisePipeline::source(Perlin::new())
+            .abs()
+            .scale_bias(2.0, -1.0)
+            .turbulence(0.25);
+        let from_macro = noise_pipeline!(
+            Perlin::new()
+                => abs()
+                => scale_bias(2.0, -1.0)
+                => turbulence(0.25)
+        );
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173 - 8.0, i as f64 * 0.091, 0.4];
+            assert_eq!(from_macro.get(point), built.get(point));
+        }
+    }
+}
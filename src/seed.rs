@@ -0,0 +1,29 @@
+const FNV_OFFSET_BASIS: u32 = 0x811c_9dc5;
+const FNV_PRIME: u32 = 0x0100_0193;
+
+/// Converts a name into a seed, so that seeds can be given as words such as
+/// `"forest"` or `"ocean"` rather than numbers.
+///
+/// The seed is the 32-bit FNV-1a hash of the UTF-8 bytes of `name`. The hash
+/// only uses wrapping integer arithmetic on fixed-width types, so the same
+/// name maps to the same seed on every platform, and will keep doing so in
+/// future versions of this crate and of Rust.
+pub fn seed_from_str(name: &str) -> u32 {
+    name.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::seed_from_str;
+
+    #[test]
+    fn known_hashes() {
+        assert_eq!(seed_from_str(""), 0x811c_9dc5);
+        assert_eq!(seed_from_str("a"), 0xe40c_292c);
+        assert_eq!(seed_from_str("foobar"), 0xbf9c_f968);
+        assert_eq!(seed_from_str("forest"), 0x4f29_2a60);
+        assert_eq!(seed_from_str("ocean"), 0xd470_5991);
+    }
+}
@@ -0,0 +1,254 @@
+//! Statistical checks of the quality of noise functions.
+//!
+//! Each check samples a 2-dimensional noise function on a grid or at random
+//! points, with neighbouring samples `SAMPLE_SPACING` apart. Noise functions
+//! whose features are much larger or smaller than that spacing can be scaled
+//! with `ScalePoint` before they are checked.
+
+use crate::noise_fns::NoiseFn;
+use rand::{Rng, SeedableRng};
+use rand_xorshift::XorShiftRng;
+use std::f64::consts::PI;
+
+/// Distance between neighbouring samples.
+pub const SAMPLE_SPACING: f64 = 0.1;
+
+/// Number of samples the autocorrelation is averaged over.
+const AUTOCORRELATION_SAMPLES: usize = 16_384;
+
+/// Distance between the two samples of each difference in `isotropy_test`.
+const DIFFERENCE_STEP: f64 = 0.01;
+
+/// Returns the radially averaged power spectrum of `noise` sampled on a
+/// `size` by `size` grid.
+///
+/// Element `k` is the mean power of the frequencies whose distance from zero
+/// rounds to `k` cycles across the grid, for `k` up to `size / 2`. Element 0
+/// holds the power of the mean of the samples. The powers are scaled so that
+/// their sum over every frequency equals the sum of the squared samples.
+///
+/// # Panics
+///
+/// Panics unless `size` is a power of two.
+pub fn power_spectrum<F>(noise: &F, size: usize) -> Vec<f64>
+where
+    F: NoiseFn<[f64; 2]> + ?Sized,
+{
+    assert!(size.is_power_of_two(), "size must be a power of two");
+
+    let mut grid: Vec<(f64, f64)> = (0..size * size)
+        .map(|index| {
+            let point = [
+                (index % size) as f64 * SAMPLE_SPACING,
+                (index / size) as f64 * SAMPLE_SPACING,
+            ];
+            (noise.get(point), 0.0)
+        })
+        .collect();
+
+    // Transform the rows, then the columns.
+    for row in grid.chunks_exact_mut(size) {
+        fft(row);
+    }
+
+    let mut column = vec![(0.0, 0.0); size];
+    for x in 0..size {
+        for (y, value) in column.iter_mut().enumerate() {
+            *value = grid[x + y * size];
+        }
+        fft(&mut column);
+        for (y, value) in column.iter().enumerate() {
+            grid[x + y * size] = *value;
+        }
+    }
+
+    let bins = size / 2 + 1;
+    let mut power = vec![0.0; bins];
+    let mut counts = vec![0usize; bins];
+
+    for (index, (re, im)) in grid.iter().enumerate() {
+        // Frequencies above the Nyquist frequency alias negative ones.
+        let signed = |f: usize| {
+            if f > size / 2 {
+                f as f64 - size as f64
+            } else {
+                f as f64
+            }
+        };
+        let u = signed(index % size);
+        let v = signed(index / size);
+        let bin = (u * u + v * v).sqrt().round() as usize;
+
+        if bin < bins {
+            power[bin] += (re * re + im * im) / (size * size) as f64;
+            counts[bin] += 1;
+        }
+    }
+
+    for (power, &count) in power.iter_mut().zip(counts.iter()) {
+        *power /= count as f64;
+    }
+
+    power
+}
+
+/// Returns the autocorrelation of `noise` along the _x_ axis, for each lag of
+/// 0 to `max_lag` samples.
+///
+/// Element 0 is always 1.0. Smooth noise stays close to 1.0 over short lags
+/// and falls towards 0.0 once the lag exceeds the size of its features, while
+/// uncorrelated noise drops to about 0.0 at a lag of one sample.
+pub fn autocorrelation<F>(noise: &F, max_lag: usize) -> Vec<f64>
+where
+    F: NoiseFn<[f64; 2]> + ?Sized,
+{
+    let samples: Vec<f64> = (0..AUTOCORRELATION_SAMPLES + max_lag)
+        .map(|index| noise.get([index as f64 * SAMPLE_SPACING, 0.5]))
+        .collect();
+
+    let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+    let deviations: Vec<f64> = samples.iter().map(|sample| sample - mean).collect();
+    let covariance = |lag: usize| -> f64 {
+        deviations[..AUTOCORRELATION_SAMPLES]
+            .iter()
+            .zip(&deviations[lag..])
+            .map(|(a, b)| a * b)
+            .sum()
+    };
+
+    let variance = covariance(0);
+    (0..=max_lag)
+        .map(|lag| covariance(lag) / variance)
+        .collect()
+}
+
+/// Returns the ratio of the variance of `noise` along the _x_ axis to its
+/// variance along the _y_ axis, measured from `samples` pairs of nearby
+/// points at random positions.
+///
+/// Noise that has no preferred direction has a ratio close to 1.0. The random
+/// positions are the same on every call.
+pub fn isotropy_test<F>(noise: &F, samples: usize) -> f64
+where
+    F: NoiseFn<[f64; 2]> + ?Sized,
+{
+    let mut rng = XorShiftRng::seed_from_u64(0);
+    let range = samples as f64 * SAMPLE_SPACING;
+
+    let mut variance_x = 0.0;
+    let mut variance_y = 0.0;
+
+    for _ in 0..samples {
+        let x = rng.gen_range(-range, range);
+        let y = rng.gen_range(-range, range);
+        let value = noise.get([x, y]);
+
+        variance_x += (noise.get([x + DIFFERENCE_STEP, y]) - value).powi(2);
+        variance_y += (noise.get([x, y + DIFFERENCE_STEP]) - value).powi(2);
+    }
+
+    variance_x / variance_y
+}
+
+/// Replaces `values` with their discrete Fourier transform, using the radix-2
+/// Cooley-Tukey algorithm. The length must be a power of two.
+fn fft(values: &mut [(f64, f64)]) {
+    let len = values.len();
+
+    // Reorder the values by the bit-reversed index.
+    let mut j = 0;
+    for i in 1..len {
+        let mut bit = len >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut width = 2;
+    while width <= len {
+        let angle = -2.0 * PI / width as f64;
+
+        for chunk in values.chunks_exact_mut(width) {
+            let (lower, upper) = chunk.split_at_mut(width / 2);
+
+            for (k, (a, b)) in lower.iter_mut().zip(upper.iter_mut()).enumerate() {
+                let (sin, cos) = (angle * k as f64).sin_cos();
+                let twiddled = (b.0 * cos - b.1 * sin, b.0 * sin + b.1 * cos);
+
+                *b = (a.0 - twiddled.0, a.1 - twiddled.1);
+                *a = (a.0 + twiddled.0, a.1 + twiddled.1);
+            }
+        }
+
+        width *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Constant, Fbm, Perlin, ScalePoint, Simplex};
+
+    #[test]
+    fn fft_matches_direct_transform() {
+        let input: Vec<(f64, f64)> = (0..16)
+            .map(|i| ((i as f64 * 0.7).sin(), (i as f64 * 0.3).cos()))
+            .collect();
+        let mut output = input.clone();
+        fft(&mut output);
+
+        for (k, value) in output.iter().enumerate() {
+            let mut expected = (0.0, 0.0);
+            for (n, (re, im)) in input.iter().enumerate() {
+                let (sin, cos) = (-2.0 * PI * (k * n) as f64 / 16.0).sin_cos();
+                expected.0 += re * cos - im * sin;
+                expected.1 += re * sin + im * cos;
+            }
+
+            assert!((value.0 - expected.0).abs() < 1e-9);
+            assert!((value.1 - expected.1).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn power_spectrum_of_known_signals() {
+        let constant = Constant::new(0.5);
+        let spectrum = power_spectrum(&constant, 8);
+
+        // All the power of a constant is at frequency zero.
+        assert_eq!(spectrum.len(), 5);
+        assert!((spectrum[0] - 0.25 * 64.0).abs() < 1e-9);
+        assert!(spectrum[1..].iter().all(|&power| power.abs() < 1e-9));
+
+        // Fractal noise has more power at low frequencies than high ones.
+        let spectrum = power_spectrum(&Fbm::new(), 64);
+        assert!(spectrum[2] > spectrum[16] * 10.0);
+    }
+
+    #[test]
+    fn autocorrelation_falls_with_lag() {
+        let perlin = Perlin::new();
+        let correlation = autocorrelation(&perlin, 40);
+
+        assert_eq!(correlation[0], 1.0);
+        assert!(correlation[1] > 0.9);
+        assert!(correlation[40].abs() < 0.2);
+    }
+
+    #[test]
+    fn simplex_is_isotropic() {
+        let ratio = isotropy_test(&Simplex::new(), 100_000);
+        assert!((ratio - 1.0).abs() < 0.05, "ratio is {}", ratio);
+
+        // Stretching the noise along one axis is detected.
+        let simplex = Simplex::new();
+        let stretched = ScalePoint::new(&simplex).set_x_scale(2.0);
+        assert!(isotropy_test(&stretched, 100_000) > 2.0);
+    }
+}
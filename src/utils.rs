@@ -4,10 +4,15 @@ pub use self::image_renderer::*;
 pub use self::noise_image::*;
 pub use self::noise_map::*;
 pub use self::noise_map_builder::*;
+pub use self::noise_map_rgb::*;
+pub use self::path_sampling::*;
 
 mod color_gradient;
+mod contours;
 #[cfg(feature = "image")]
 mod image_renderer;
 mod noise_image;
 mod noise_map;
 mod noise_map_builder;
+mod noise_map_rgb;
+mod path_sampling;
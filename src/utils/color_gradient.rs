@@ -8,6 +8,12 @@ struct GradientPoint {
     color: Color,
 }
 
+/// Maps values in the [-1,1] range onto colors.
+///
+/// The gradient is defined by a number of _gradient points_, each pairing a
+/// position with an RGBA color. Values between two gradient points are
+/// linearly interpolated between their colors, and values outside of the
+/// gradient take the color of the nearest point.
 #[derive(Clone, Debug, Default)]
 pub struct ColorGradient {
     gradient_points: Vec<GradientPoint>,
@@ -27,7 +33,7 @@ impl ColorGradient {
         if !self
             .gradient_points
             .iter()
-            .any(|&x| (x.pos - pos).abs() < std::f64::EPSILON)
+            .any(|&x| (x.pos - pos).abs() < f64::EPSILON)
         {
             // it doesn't, so find the correct position to insert the new
             // control point.
@@ -45,7 +51,7 @@ impl ColorGradient {
         self.gradient_points
             .iter()
             .position(|x| x.pos >= pos)
-            .unwrap_or_else(|| self.gradient_points.len())
+            .unwrap_or(self.gradient_points.len())
     }
 
     pub fn clear_gradient(mut self) -> Self {
@@ -100,8 +106,8 @@ impl ColorGradient {
         let index = self
             .gradient_points
             .iter()
-            .position(|&x| (x.pos > clamped_pos))
-            .unwrap_or_else(|| self.gradient_points.len());
+            .position(|&x| x.pos > clamped_pos)
+            .unwrap_or(self.gradient_points.len());
 
         if index < 1 {
             println!(
@@ -0,0 +1,231 @@
+use crate::utils::NoiseMap;
+use std::collections::BTreeMap;
+
+/// Identifies the point where a contour crosses the edge between two
+/// neighbouring cells of a noise map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum EdgeKey {
+    /// The edge between the cells `(x, y)` and `(x + 1, y)`.
+    Horizontal(usize, usize),
+    /// The edge between the cells `(x, y)` and `(x, y + 1)`.
+    Vertical(usize, usize),
+}
+
+impl NoiseMap {
+    /// Extracts the contour lines of the map at each of the given levels,
+    /// using marching squares.
+    ///
+    /// The result has one entry per level, holding the polylines that trace
+    /// that level. Contours that form a closed polygon end with the point
+    /// they start with. The points are in the coordinates the map was
+    /// sampled from, with `x_bounds` and `y_bounds` matching the bounds
+    /// given to the `PlaneMapBuilder` that built the map.
+    pub fn extract_contours(
+        &self,
+        levels: &[f64],
+        x_bounds: (f64, f64),
+        y_bounds: (f64, f64),
+    ) -> Vec<Vec<Vec<(f32, f32)>>> {
+        let (width, height) = self.size();
+        let x_step = (x_bounds.1 - x_bounds.0) / width as f64;
+        let y_step = (y_bounds.1 - y_bounds.0) / height as f64;
+
+        levels
+            .iter()
+            .map(|&level| {
+                let to_noise_space = |key: EdgeKey| {
+                    let (x, y) = self.edge_point(key, level);
+                    (
+                        (x_bounds.0 + x * x_step) as f32,
+                        (y_bounds.0 + y * y_step) as f32,
+                    )
+                };
+
+                join_segments(self.contour_segments(level))
+                    .into_iter()
+                    .map(|path| path.into_iter().map(to_noise_space).collect())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the segments of the contour at `level` within each square of
+    /// four neighbouring cells.
+    fn contour_segments(&self, level: f64) -> Vec<(EdgeKey, EdgeKey)> {
+        use self::EdgeKey::{Horizontal, Vertical};
+
+        let (width, height) = self.size();
+        let mut segments = Vec::new();
+
+        for y in 0..height.saturating_sub(1) {
+            for x in 0..width.saturating_sub(1) {
+                let corners = [
+                    self.get_value(x, y),
+                    self.get_value(x + 1, y),
+                    self.get_value(x + 1, y + 1),
+                    self.get_value(x, y + 1),
+                ];
+                let case = corners
+                    .iter()
+                    .fold(0, |case, &value| (case << 1) | (value >= level) as u8);
+
+                let top = Horizontal(x, y);
+                let right = Vertical(x + 1, y);
+                let bottom = Horizontal(x, y + 1);
+                let left = Vertical(x, y);
+
+                // Resolve the ambiguous saddle cases by the average of the
+                // corners, which decides whether the two corners above the
+                // level are connected through the middle of the square.
+                let center_above = corners.iter().sum::<f64>() / 4.0 >= level;
+
+                match case {
+                    0b0001 | 0b1110 => segments.push((left, bottom)),
+                    0b0010 | 0b1101 => segments.push((bottom, right)),
+                    0b0011 | 0b1100 => segments.push((left, right)),
+                    0b0100 | 0b1011 => segments.push((top, right)),
+                    0b0110 | 0b1001 => segments.push((top, bottom)),
+                    0b0111 | 0b1000 => segments.push((left, top)),
+                    0b0101 | 0b1010 => {
+                        // The top-right and bottom-left corners are above the
+                        // level in 0b0101, and below it in 0b1010.
+                        if center_above == (case == 0b0101) {
+                            segments.push((left, top));
+                            segments.push((bottom, right));
+                        } else {
+                            segments.push((top, right));
+                            segments.push((left, bottom));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        segments
+    }
+
+    /// Returns the point, in cell coordinates, where the contour at `level`
+    /// crosses the edge identified by `key`.
+    fn edge_point(&self, key: EdgeKey, level: f64) -> (f64, f64) {
+        let ((x0, y0), (x1, y1)) = match key {
+            EdgeKey::Horizontal(x, y) => ((x, y), (x + 1, y)),
+            EdgeKey::Vertical(x, y) => ((x, y), (x, y + 1)),
+        };
+
+        let a = self.get_value(x0, y0);
+        let b = self.get_value(x1, y1);
+        let t = if a == b { 0.5 } else { (level - a) / (b - a) };
+
+        (
+            x0 as f64 + (x1 - x0) as f64 * t,
+            y0 as f64 + (y1 - y0) as f64 * t,
+        )
+    }
+}
+
+/// Joins segments that share an end point into polylines.
+///
+/// Every edge point is shared by at most two segments, so each point has at
+/// most two neighbours. Open polylines are traced from their ends first,
+/// and the remaining points all lie on closed loops.
+fn join_segments(segments: Vec<(EdgeKey, EdgeKey)>) -> Vec<Vec<EdgeKey>> {
+    let mut neighbours: BTreeMap<EdgeKey, Vec<EdgeKey>> = BTreeMap::new();
+    for (a, b) in segments {
+        neighbours.entry(a).or_default().push(b);
+        neighbours.entry(b).or_default().push(a);
+    }
+
+    let mut starts: Vec<EdgeKey> = neighbours
+        .iter()
+        .filter(|(_, links)| links.len() == 1)
+        .map(|(&key, _)| key)
+        .collect();
+    starts.extend(neighbours.keys().copied());
+
+    let mut paths = Vec::new();
+
+    for start in starts {
+        if !neighbours.contains_key(&start) {
+            continue;
+        }
+
+        let mut path = vec![start];
+        let mut current = start;
+
+        while let Some(next) = neighbours.remove(&current).and_then(|mut links| {
+            // Remove the link back to the current point from the next point,
+            // so the path is not walked in reverse.
+            let next = links.pop()?;
+            if let Some(links) = neighbours.get_mut(&next) {
+                if let Some(index) = links.iter().position(|&key| key == current) {
+                    links.swap_remove(index);
+                }
+            }
+            Some(next)
+        }) {
+            path.push(next);
+            current = next;
+        }
+
+        paths.push(path);
+    }
+
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        utils::{NoiseMap, NoiseMapBuilder, PlaneMapBuilder},
+        Fbm, MultiFractal,
+    };
+
+    #[test]
+    fn bump_has_one_closed_contour() {
+        let mut noise_map = NoiseMap::new(9, 9);
+        for y in 0..9 {
+            for x in 0..9 {
+                let distance = ((x as f64 - 4.0).powi(2) + (y as f64 - 4.0).powi(2)).sqrt();
+                noise_map.set_value(x, y, 1.0 - distance / 2.0);
+            }
+        }
+
+        let contours = noise_map.extract_contours(&[0.0], (0.0, 9.0), (0.0, 9.0));
+
+        assert_eq!(contours.len(), 1);
+        assert_eq!(contours[0].len(), 1);
+
+        let path = &contours[0][0];
+        assert_eq!(path.first(), path.last());
+        for &(x, y) in path {
+            let distance = ((x - 4.0).powi(2) + (y - 4.0).powi(2)).sqrt();
+            assert!(
+                (distance - 2.0).abs() < 0.2,
+                "{} is off the circle",
+                distance
+            );
+        }
+    }
+
+    #[test]
+    fn fbm_has_closed_contour() {
+        let fbm = Fbm::new().set_octaves(3);
+        let noise_map = PlaneMapBuilder::new(&fbm)
+            .set_size(64, 64)
+            .set_x_bounds(-2.0, 2.0)
+            .set_y_bounds(-2.0, 2.0)
+            .build();
+
+        let contours = noise_map.extract_contours(&[0.0], (-2.0, 2.0), (-2.0, 2.0));
+
+        assert!(contours[0]
+            .iter()
+            .any(|path| path.len() > 3 && path.first() == path.last()));
+        for path in &contours[0] {
+            for &(x, y) in path {
+                assert!((-2.0..=2.0).contains(&x) && (-2.0..=2.0).contains(&y));
+            }
+        }
+    }
+}
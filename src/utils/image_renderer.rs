@@ -3,6 +3,8 @@ use std::{self, f64::consts::SQRT_2};
 
 use super::{color_gradient::*, noise_image::*, noise_map::*};
 
+/// Renders a `NoiseMap` into an RGBA `NoiseImage` by passing each value
+/// through a `ColorGradient`, optionally lit by a light source.
 pub struct ImageRenderer {
     // The color gradient used to specify the image colors.
     gradient: ColorGradient,
@@ -212,9 +214,9 @@ impl ImageRenderer {
         }
 
         // Clamp color channels to [0..1]
-        red = red.max(0.0).min(1.0);
-        green = green.max(0.0).min(1.0);
-        blue = blue.max(0.0).min(1.0);
+        red = red.clamp(0.0, 1.0);
+        green = green.clamp(0.0, 1.0);
+        blue = blue.clamp(0.0, 1.0);
 
         // Rescale color channels to u8 [0..255] and return the final color
         [
@@ -336,9 +338,9 @@ impl ImageRenderer {
         }
 
         // Clamp color channels to [0..1]
-        red = red.max(0.0).min(1.0);
-        green = green.max(0.0).min(1.0);
-        blue = blue.max(0.0).min(1.0);
+        red = red.clamp(0.0, 1.0);
+        green = green.clamp(0.0, 1.0);
+        blue = blue.clamp(0.0, 1.0);
 
         // Rescale color channels to u8 [0..255] and return the final color
         [
@@ -465,7 +467,7 @@ impl LightSource {
             self.recalculate_light_values = false;
         }
 
-        let i_max = 1.0;
+        let i_max = self.intensity;
 
         let io = i_max * SQRT_2 * self.elevation_sine / 2.0;
         let ix =
@@ -496,10 +498,71 @@ fn u8_array_to_f64_array(input: [u8; 4]) -> [f64; 4] {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{utils::*, Constant};
 
     #[test]
     fn array_conversion() {
         assert_eq!([0.0; 4], u8_array_to_f64_array([0; 4]));
         assert_eq!([1.0; 4], u8_array_to_f64_array([255; 4]));
     }
+
+    fn render_lit(noise_map: &NoiseMap, azimuth: f64) -> NoiseImage {
+        let mut renderer = ImageRenderer::new().set_light_azimuth(azimuth);
+        renderer.enable_light();
+        renderer.render(noise_map)
+    }
+
+    #[test]
+    fn flat_map_is_shaded_uniformly() {
+        let noise_map = PlaneMapBuilder::new(&Constant::new(0.25))
+            .set_size(16, 16)
+            .build();
+        let image = render_lit(&noise_map, 45.0);
+
+        let expected = image.get_value(0, 0);
+        for y in 0..16 {
+            for x in 0..16 {
+                assert_eq!(expected, image.get_value(x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn sloped_map_is_shaded_by_direction() {
+        let mut flat_map = NoiseMap::new(16, 16);
+        let mut sloped_map = NoiseMap::new(16, 16);
+        for y in 0..16 {
+            for x in 0..16 {
+                flat_map.set_value(x, y, 0.0);
+                sloped_map.set_value(x, y, x as f64 / 16.0 - 0.5);
+            }
+        }
+
+        let flat = render_lit(&flat_map, 0.0).get_value(8, 8)[0];
+        let facing_away = render_lit(&sloped_map, 0.0).get_value(8, 8)[0];
+        let facing_light = render_lit(&sloped_map, 180.0).get_value(8, 8)[0];
+
+        assert!(facing_away < flat && flat < facing_light);
+    }
+
+    #[test]
+    fn black_to_white_matches_grayscale() {
+        let mut noise_map = NoiseMap::new(17, 1);
+        for x in 0..17 {
+            noise_map.set_value(x, 0, x as f64 / 8.0 - 1.0);
+        }
+
+        let gradient = ColorGradient::new()
+            .clear_gradient()
+            .add_gradient_point(-1.0, [0, 0, 0, 255])
+            .add_gradient_point(1.0, [255, 255, 255, 255]);
+        let image = ImageRenderer::new()
+            .set_gradient(gradient)
+            .render(&noise_map);
+
+        for x in 0..17 {
+            let gray = ((noise_map.get_value(x, 0) * 0.5 + 0.5) * 255.0) as u8;
+            assert_eq!([gray, gray, gray, 255], image.get_value(x, 0));
+        }
+    }
 }
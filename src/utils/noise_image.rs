@@ -113,8 +113,8 @@ impl NoiseImage {
         }
 
         let _ = image::save_buffer(
-            &Path::new(&file_path),
-            &*result,
+            Path::new(&file_path),
+            &result,
             self.size.0 as u32,
             self.size.1 as u32,
             image::ColorType::Rgba8,
@@ -122,6 +122,45 @@ impl NoiseImage {
 
         println!("\nFinished generating {}", filename);
     }
+
+    /// Writes the image to a PNG file at `path`.
+    #[cfg(feature = "image")]
+    pub fn write_png<P: AsRef<Path>>(&self, path: P) -> image::ImageResult<()> {
+        self.write_with_format(path.as_ref(), image::ImageFormat::Png)
+    }
+
+    /// Writes the image to a BMP file at `path`.
+    #[cfg(feature = "image")]
+    pub fn write_bmp<P: AsRef<Path>>(&self, path: P) -> image::ImageResult<()> {
+        self.write_with_format(path.as_ref(), image::ImageFormat::Bmp)
+    }
+
+    /// Writes the image to a TGA file at `path`.
+    #[cfg(feature = "image")]
+    pub fn write_tga<P: AsRef<Path>>(&self, path: P) -> image::ImageResult<()> {
+        self.write_with_format(path.as_ref(), image::ImageFormat::Tga)
+    }
+
+    /// Writes the pixels as 8-bit RGBA in `format`, regardless of the
+    /// extension of `path`, so every format stores the same pixel values.
+    #[cfg(feature = "image")]
+    fn write_with_format(&self, path: &Path, format: image::ImageFormat) -> image::ImageResult<()> {
+        let (width, height) = self.size;
+        let pixels: Vec<u8> = self.map[..width * height]
+            .iter()
+            .flatten()
+            .copied()
+            .collect();
+
+        image::save_buffer_with_format(
+            path,
+            &pixels,
+            width as u32,
+            height as u32,
+            image::ColorType::Rgba8,
+            format,
+        )
+    }
 }
 
 impl Default for NoiseImage {
@@ -129,3 +168,40 @@ impl Default for NoiseImage {
         Self::initialize()
     }
 }
+
+#[cfg(all(test, feature = "image"))]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn written_formats_decode_to_same_pixels() {
+        let mut noise_image = NoiseImage::new(6, 4);
+        for y in 0..4 {
+            for x in 0..6 {
+                let gray = (x * 40 + y * 10) as u8;
+                noise_image.set_value(x, y, [gray, gray, gray, 255]);
+            }
+        }
+
+        let path =
+            |extension| std::env::temp_dir().join(format!("noice_noise_image.{}", extension));
+        noise_image.write_png(path("png")).unwrap();
+        noise_image.write_bmp(path("bmp")).unwrap();
+        noise_image.write_tga(path("tga")).unwrap();
+
+        for extension in ["png", "bmp", "tga"] {
+            let decoded = image::open(path(extension)).unwrap().to_rgba8();
+            fs::remove_file(path(extension)).unwrap();
+
+            assert_eq!(decoded.dimensions(), (6, 4), "{}", extension);
+            assert_eq!(
+                decoded.get_pixel(5, 3).0,
+                [230, 230, 230, 255],
+                "{}",
+                extension
+            );
+            assert_eq!(decoded.get_pixel(0, 0).0, [0, 0, 0, 255], "{}", extension);
+        }
+    }
+}
@@ -1,7 +1,10 @@
-#[cfg(feature = "image")]
-use crate::math;
-#[cfg(feature = "image")]
-use std::{self, path::Path};
+use crate::{math, utils::NoiseImage};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    ops::{Index, IndexMut},
+    path::Path,
+};
 
 const RASTER_MAX_WIDTH: u16 = 32_767;
 const RASTER_MAX_HEIGHT: u16 = 32_767;
@@ -13,6 +16,9 @@ pub struct NoiseMap {
 }
 
 impl NoiseMap {
+    /// Number of bins that `equalize` is usually given.
+    pub const DEFAULT_EQUALIZE_BINS: usize = 256;
+
     pub fn new(width: usize, height: usize) -> Self {
         Self::initialize().set_size(width, height)
     }
@@ -82,6 +88,300 @@ impl NoiseMap {
         }
     }
 
+    /// Returns an iterator over the coordinates and values of the map, in
+    /// row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), f64)> + '_ {
+        let width = self.size.0;
+
+        self.as_slice()
+            .iter()
+            .enumerate()
+            .map(move |(index, &value)| ((index % width, index / width), value))
+    }
+
+    /// Returns the index of `(x, y)` in `map`, panicking if it lies outside
+    /// the map.
+    fn offset(&self, (x, y): (usize, usize)) -> usize {
+        let (width, height) = self.size;
+
+        assert!(
+            x < width && y < height,
+            "point ({}, {}) is out of bounds for a {}x{} noise map",
+            x,
+            y,
+            width,
+            height
+        );
+
+        x + y * width
+    }
+
+    /// Returns the values of the map in row-major order.
+    pub fn as_slice(&self) -> &[f64] {
+        let (width, height) = self.size;
+
+        &self.map[..width * height]
+    }
+
+    /// Consumes the map, returning its values in row-major order.
+    pub fn into_vec(self) -> Vec<f64> {
+        let (width, height) = self.size;
+
+        let mut map = self.map;
+        map.truncate(width * height);
+        map
+    }
+
+    /// Returns the smallest and largest values in the map.
+    ///
+    /// NaN values are ignored. If the map is empty, or holds nothing but NaN,
+    /// returns `(f64::INFINITY, f64::NEG_INFINITY)`.
+    pub fn min_max(&self) -> (f64, f64) {
+        self.as_slice()
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| {
+                (min.min(value), max.max(value))
+            })
+    }
+
+    /// Rescales the values of the map so that they span exactly [-1,1].
+    ///
+    /// See `normalize_to`.
+    pub fn normalize(&mut self) {
+        self.normalize_to(-1.0, 1.0);
+    }
+
+    /// Rescales the values of the map linearly so that the smallest value
+    /// becomes `lower` and the largest becomes `upper`.
+    ///
+    /// If every value in the map is equal, there is no range to rescale, and
+    /// the map is left unchanged. NaN values are ignored when finding the
+    /// range, and stay NaN.
+    pub fn normalize_to(&mut self, lower: f64, upper: f64) {
+        let (min, max) = self.min_max();
+        let extent = max - min;
+
+        if extent.is_nan() || extent <= 0.0 {
+            return;
+        }
+
+        let (width, height) = self.size;
+        for value in self.map[..width * height].iter_mut() {
+            let t = (*value - min) / extent;
+
+            // Weighting both ends maps the extremes exactly onto the bounds.
+            *value = lower * (1.0 - t) + upper * t;
+        }
+    }
+
+    /// Returns a copy of the map with its values redistributed so that they
+    /// are spread evenly across the range they span.
+    ///
+    /// The values are sorted into `bins` bins of equal width, and each value
+    /// is replaced by its position in the cumulative distribution of the map,
+    /// interpolated linearly within its bin. The smallest and largest values
+    /// are kept, so the equalized map spans the same range. More bins follow
+    /// the distribution more closely; `DEFAULT_EQUALIZE_BINS` suits most maps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` is zero.
+    pub fn equalize(&self, bins: usize) -> NoiseMap {
+        assert!(bins > 0, "equalize needs at least one bin");
+
+        let (width, height) = self.size;
+        let mut equalized = NoiseMap::new(width, height).set_border_value(self.border_value);
+
+        let values = self.as_slice();
+        let (min, max) = self.min_max();
+        let extent = max - min;
+
+        if extent <= 0.0 {
+            equalized.map[..values.len()].copy_from_slice(values);
+            return equalized;
+        }
+
+        // Position of a value in units of bins.
+        let position = |value: f64| (value - min) / extent * bins as f64;
+        let bin = |position: f64| (position as usize).min(bins - 1);
+
+        let mut counts = vec![0usize; bins];
+        for &value in values.iter().filter(|value| !value.is_nan()) {
+            counts[bin(position(value))] += 1;
+        }
+
+        // The number of values below each bin.
+        let below: Vec<usize> = counts
+            .iter()
+            .scan(0, |total, &count| {
+                let below = *total;
+                *total += count;
+                Some(below)
+            })
+            .collect();
+        let total = counts.iter().sum::<usize>() as f64;
+
+        for (output, &value) in equalized.map.iter_mut().zip(values) {
+            let position = position(value);
+            let index = bin(position);
+            let fraction = math::clamp(position - index as f64, 0.0, 1.0);
+
+            let cdf = (below[index] as f64 + fraction * counts[index] as f64) / total;
+            *output = if value.is_nan() {
+                value
+            } else {
+                min + cdf * extent
+            };
+        }
+
+        equalized
+    }
+
+    /// Writes the map to `path` as raw little-endian data.
+    ///
+    /// The file starts with the width and height of the map, each as a `u32`,
+    /// followed by the values of the map as `f64`s in row-major order.
+    pub fn write_raw<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let (width, height) = self.size;
+
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        writer.write_all(&(width as u32).to_le_bytes())?;
+        writer.write_all(&(height as u32).to_le_bytes())?;
+
+        for value in self.as_slice() {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+
+        writer.flush()
+    }
+
+    /// Returns a copy of the map resampled to `new_width` by `new_height`
+    /// cells using bilinear interpolation.
+    ///
+    /// The corner cells of both maps are aligned, so the values along the
+    /// edges of the map are preserved. Cells that fall between the edge of
+    /// the map and the centre of the last cell are clamped to the edge.
+    pub fn resize(&self, new_width: usize, new_height: usize) -> NoiseMap {
+        let (width, height) = self.size;
+
+        let mut resized = NoiseMap::new(new_width, new_height).set_border_value(self.border_value);
+
+        if width == 0 || height == 0 {
+            // There is nothing to sample, so fill the map with the border value.
+            for value in resized.map.iter_mut() {
+                *value = self.border_value;
+            }
+            return resized;
+        }
+
+        // Maps a cell of the resized map onto the corresponding position in
+        // this map, aligning the first and last cells of both.
+        let source_position = |cell: usize, new_size: usize, size: usize| {
+            if new_size > 1 {
+                cell as f64 * (size - 1) as f64 / (new_size - 1) as f64
+            } else {
+                0.0
+            }
+        };
+
+        for y in 0..new_height {
+            let v = source_position(y, new_height, height);
+            let y0 = (v.floor() as usize).min(height - 1);
+            let y1 = (y0 + 1).min(height - 1);
+            let v_fract = v - y0 as f64;
+
+            for x in 0..new_width {
+                let u = source_position(x, new_width, width);
+                let x0 = (u.floor() as usize).min(width - 1);
+                let x1 = (x0 + 1).min(width - 1);
+                let u_fract = u - x0 as f64;
+
+                let top = math::interpolate::linear(
+                    self.get_value(x0, y0),
+                    self.get_value(x1, y0),
+                    u_fract,
+                );
+                let bottom = math::interpolate::linear(
+                    self.get_value(x0, y1),
+                    self.get_value(x1, y1),
+                    u_fract,
+                );
+
+                resized.set_value(x, y, math::interpolate::linear(top, bottom, v_fract));
+            }
+        }
+
+        resized
+    }
+
+    /// Returns a normal map of the map, treating its values as heights.
+    ///
+    /// The slope at each cell is found with central differences between its
+    /// neighbours, after multiplying the heights by `scale`, and edge cells
+    /// use the nearest cells inside the map instead. Each normal is packed
+    /// into the red, green and blue channels by mapping its components from
+    /// [-1,1] to [0,255], with _x_ pointing along the rows, _y_ along the
+    /// columns, and _z_ out of the map. The alpha channel is always opaque.
+    pub fn to_normal_map(&self, scale: f64) -> NoiseImage {
+        let (width, height) = self.size;
+        let mut normal_map = NoiseImage::new(width, height);
+
+        // Maps a normal component from [-1,1] to a color channel.
+        let encode = |value: f64| (math::clamp(value * 0.5 + 0.5, 0.0, 1.0) * 255.0).round() as u8;
+
+        for y in 0..height {
+            let (up, down) = (y.saturating_sub(1), (y + 1).min(height - 1));
+
+            for x in 0..width {
+                let (left, right) = (x.saturating_sub(1), (x + 1).min(width - 1));
+
+                // The neighbours are one cell apart at the edges of the map,
+                // and two cells apart everywhere else.
+                let dx = (self.get_value(right, y) - self.get_value(left, y)) * scale
+                    / (right - left).max(1) as f64;
+                let dy = (self.get_value(x, down) - self.get_value(x, up)) * scale
+                    / (down - up).max(1) as f64;
+
+                // The normal is the cross product of the tangents (1, 0, dx)
+                // and (0, 1, dy).
+                let length = (dx * dx + dy * dy + 1.0).sqrt();
+                let normal = [-dx / length, -dy / length, 1.0 / length];
+
+                normal_map.set_value(
+                    x,
+                    y,
+                    [encode(normal[0]), encode(normal[1]), encode(normal[2]), 255],
+                );
+            }
+        }
+
+        normal_map
+    }
+
+    /// Writes the normal map of the map, as returned by `to_normal_map`, to
+    /// an image file at `path`. The format is determined from the extension.
+    #[cfg(feature = "image")]
+    pub fn to_normalmap_file<P: AsRef<Path>>(&self, path: P, scale: f64) -> image::ImageResult<()> {
+        let (width, height) = self.size;
+        let normal_map = self.to_normal_map(scale);
+
+        let mut pixels = Vec::with_capacity(width * height * 4);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.extend_from_slice(&normal_map.get_value(x, y));
+            }
+        }
+
+        image::save_buffer(
+            path,
+            &pixels,
+            width as u32,
+            height as u32,
+            image::ColorType::Rgba8,
+        )
+    }
+
     #[cfg(feature = "image")]
     pub fn write_to_file(&self, filename: &str) {
         // Create the output directory for the images, if it doesn't already exist
@@ -99,13 +399,13 @@ impl NoiseMap {
         let (width, height) = self.size;
         let mut pixels: Vec<u8> = Vec::with_capacity(width * height);
 
-        for i in &self.map {
-            pixels.push((math::clamp(i * 0.5 + 0.5, 0.0, 1.0) * 255.0) as u8);
+        for &i in &self.map {
+            pixels.push(to_luma8(i));
         }
 
         let _ = image::save_buffer(
-            &Path::new(&file_path),
-            &*pixels,
+            Path::new(&file_path),
+            &pixels,
             self.size.0 as u32,
             self.size.1 as u32,
             image::ColorType::L8,
@@ -114,6 +414,38 @@ impl NoiseMap {
         println!("\nFinished generating {}", filename);
     }
 
+    /// Writes the map to a 16-bit grayscale PNG in the `example_images`
+    /// directory, mapping -1.0 to 1.0 linearly onto the full `u16` range.
+    ///
+    /// Height maps written this way don't show the banding of the 8-bit
+    /// images written by `write_to_file`.
+    #[cfg(feature = "image")]
+    pub fn write_to_file_16bit(&self, filename: &str) {
+        let target_dir = Path::new("example_images/");
+
+        if !target_dir.exists() {
+            std::fs::create_dir(target_dir).expect("failed to create example_images directory");
+        }
+
+        let _ = self.write_png_16bit(target_dir.join(filename));
+
+        println!("\nFinished generating {}", filename);
+    }
+
+    #[cfg(feature = "image")]
+    fn write_png_16bit<P: AsRef<Path>>(&self, path: P) -> image::ImageResult<()> {
+        let (width, height) = self.size;
+
+        let pixels: Vec<u16> = self.map[..width * height]
+            .iter()
+            .map(|&value| to_luma16(value))
+            .collect();
+
+        image::ImageBuffer::<image::Luma<u16>, _>::from_raw(width as u32, height as u32, pixels)
+            .expect("buffer matches the map size")
+            .save_with_format(path, image::ImageFormat::Png)
+    }
+
     fn initialize() -> Self {
         Self {
             size: (0, 0),
@@ -123,8 +455,300 @@ impl NoiseMap {
     }
 }
 
+/// Indexes the map by `(x, y)`.
+///
+/// # Panics
+///
+/// Panics if the point lies outside the map. Use `get_value` to read the
+/// border value instead.
+impl Index<(usize, usize)> for NoiseMap {
+    type Output = f64;
+
+    fn index(&self, point: (usize, usize)) -> &f64 {
+        &self.map[self.offset(point)]
+    }
+}
+
+/// Mutably indexes the map by `(x, y)`.
+///
+/// # Panics
+///
+/// Panics if the point lies outside the map.
+impl IndexMut<(usize, usize)> for NoiseMap {
+    fn index_mut(&mut self, point: (usize, usize)) -> &mut f64 {
+        let offset = self.offset(point);
+
+        &mut self.map[offset]
+    }
+}
+
 impl Default for NoiseMap {
     fn default() -> Self {
         Self::initialize()
     }
 }
+
+/// Maps a value from -1.0 to 1.0 onto an 8-bit gray level.
+#[cfg(feature = "image")]
+fn to_luma8(value: f64) -> u8 {
+    (math::clamp(value * 0.5 + 0.5, 0.0, 1.0) * 255.0) as u8
+}
+
+/// Maps a value from -1.0 to 1.0 onto a 16-bit gray level.
+#[cfg(feature = "image")]
+fn to_luma16(value: f64) -> u16 {
+    (math::clamp(value * 0.5 + 0.5, 0.0, 1.0) * 65535.0) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        utils::{NoiseMapBuilder, PlaneMapBuilder},
+        Fbm, Seedable,
+    };
+    use std::{convert::TryInto, fs};
+
+    #[test]
+    fn write_raw_round_trip() {
+        let mut noise_map = NoiseMap::new(7, 5);
+        for y in 0..5 {
+            for x in 0..7 {
+                noise_map.set_value(x, y, (x as f64 - y as f64) / 3.0);
+            }
+        }
+
+        let path = std::env::temp_dir().join("noice_write_raw_round_trip.bin");
+        noise_map.write_raw(&path).unwrap();
+        let bytes = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let width = u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as usize;
+        let height = u32::from_le_bytes(bytes[4..8].try_into().unwrap()) as usize;
+        let values: Vec<f64> = bytes[8..]
+            .chunks_exact(8)
+            .map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+
+        assert_eq!((width, height), noise_map.size());
+        assert_eq!(values, noise_map.into_vec());
+    }
+
+    #[test]
+    #[cfg(feature = "image")]
+    fn write_png_16bit_keeps_nearby_values_distinct() {
+        // Both values fall on the same 8-bit gray level.
+        assert_eq!(to_luma8(0.0), to_luma8(0.001));
+        assert_ne!(to_luma16(0.0), to_luma16(0.001));
+        assert_eq!((to_luma16(-1.0), to_luma16(1.0)), (0, u16::MAX));
+
+        let mut noise_map = NoiseMap::new(3, 2);
+        noise_map.set_value(1, 0, 0.0);
+        noise_map.set_value(2, 0, 0.001);
+        noise_map.set_value(0, 1, 1.0);
+
+        let path = std::env::temp_dir().join("noice_write_png_16bit.png");
+        noise_map.write_png_16bit(&path).unwrap();
+        let decoded = image::open(&path).unwrap().into_luma16();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(decoded.dimensions(), (3, 2));
+        assert_eq!(decoded.get_pixel(1, 0).0, [to_luma16(0.0)]);
+        assert_eq!(decoded.get_pixel(2, 0).0, [to_luma16(0.001)]);
+        assert_eq!(decoded.get_pixel(0, 1).0, [u16::MAX]);
+    }
+
+    #[test]
+    fn index_reads_and_writes_values() {
+        let mut noise_map = NoiseMap::new(5, 3);
+        noise_map.set_value(4, 1, 0.25);
+        noise_map[(2, 2)] = -0.5;
+        noise_map[(4, 1)] += 0.5;
+
+        assert_eq!(noise_map[(4, 1)], 0.75);
+        assert_eq!(noise_map.get_value(2, 2), -0.5);
+        assert_eq!(noise_map[(0, 0)], 0.0);
+
+        // Reading past the edge returns the border value instead.
+        let noise_map = noise_map.set_border_value(9.0);
+        assert_eq!(noise_map.get_value(5, 0), 9.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "point (5, 0) is out of bounds for a 5x3 noise map")]
+    fn index_out_of_bounds_panics() {
+        let noise_map = NoiseMap::new(5, 3);
+        let _ = noise_map[(5, 0)];
+    }
+
+    #[test]
+    fn iter_visits_every_cell_once() {
+        let mut noise_map = NoiseMap::new(7, 4);
+        for y in 0..4 {
+            for x in 0..7 {
+                noise_map[(x, y)] = (x * 10 + y) as f64;
+            }
+        }
+
+        let mut visits = [0; 7 * 4];
+        for ((x, y), value) in noise_map.iter() {
+            assert_eq!(value, (x * 10 + y) as f64);
+            visits[x + y * 7] += 1;
+        }
+
+        assert!(visits.iter().all(|&count| count == 1));
+        assert_eq!(noise_map.iter().count(), 28);
+        assert_eq!(noise_map.iter().next(), Some(((0, 0), 0.0)));
+        assert_eq!(noise_map.iter().nth(7), Some(((0, 1), 1.0)));
+    }
+
+    #[test]
+    fn normalize_spans_unit_range() {
+        let mut noise_map = NoiseMap::new(16, 8);
+        for y in 0..8 {
+            for x in 0..16 {
+                noise_map.set_value(x, y, ((x * 7 + y * 13) % 11) as f64 * 0.37 - 1.3);
+            }
+        }
+
+        let mut normalized = NoiseMap::new(16, 8);
+        normalized.map.copy_from_slice(noise_map.as_slice());
+        normalized.normalize();
+        assert_eq!(normalized.min_max(), (-1.0, 1.0));
+
+        noise_map.normalize_to(-3.0, 0.1);
+        assert_eq!(noise_map.min_max(), (-3.0, 0.1));
+    }
+
+    #[test]
+    fn normalize_maps_ramp_endpoints() {
+        let mut noise_map = NoiseMap::new(10, 3);
+        for y in 0..3 {
+            for x in 0..10 {
+                noise_map.set_value(x, y, x as f64 * 0.031 + 0.2);
+            }
+        }
+        assert_eq!(noise_map.min_max(), (0.2, 9.0 * 0.031 + 0.2));
+
+        noise_map.normalize();
+        assert_eq!(noise_map.min_max(), (-1.0, 1.0));
+        assert_eq!(noise_map.get_value(0, 1), -1.0);
+        assert_eq!(noise_map.get_value(9, 2), 1.0);
+    }
+
+    #[test]
+    fn normalize_leaves_flat_map_unchanged() {
+        let mut noise_map = NoiseMap::new(4, 4);
+        for y in 0..4 {
+            for x in 0..4 {
+                noise_map.set_value(x, y, 0.3);
+            }
+        }
+
+        noise_map.normalize();
+        assert_eq!(noise_map.min_max(), (0.3, 0.3));
+    }
+
+    #[test]
+    fn equalized_histogram_is_flat() {
+        let fbm = Fbm::new().set_seed(3);
+        let noise_map = PlaneMapBuilder::new(&fbm)
+            .set_size(512, 512)
+            .set_x_bounds(-16.0, 16.0)
+            .set_y_bounds(-16.0, 16.0)
+            .build()
+            .equalize(NoiseMap::DEFAULT_EQUALIZE_BINS);
+
+        let values = noise_map.as_slice();
+        let min = values.iter().copied().fold(f64::MAX, f64::min);
+        let max = values.iter().copied().fold(f64::MIN, f64::max);
+
+        let mut histogram = [0usize; 256];
+        for &value in values {
+            let bucket = ((value - min) / (max - min) * 256.0) as usize;
+            histogram[bucket.min(255)] += 1;
+        }
+
+        let expected = values.len() as f64 / 256.0;
+        for (bucket, &count) in histogram.iter().enumerate() {
+            assert!(
+                (count as f64 - expected).abs() < expected * 0.1,
+                "bucket {} holds {} values instead of {}",
+                bucket,
+                count,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn resize_to_same_size_is_identity() {
+        let mut noise_map = NoiseMap::new(9, 6);
+        for y in 0..6 {
+            for x in 0..9 {
+                noise_map.set_value(x, y, ((x * 7 + y * 13) % 11) as f64 / 5.0 - 1.0);
+            }
+        }
+
+        let resized = noise_map.resize(9, 6);
+
+        assert_eq!(resized.size(), noise_map.size());
+        assert_eq!(resized.as_slice(), noise_map.as_slice());
+    }
+
+    #[test]
+    fn downsampling_preserves_ramp() {
+        let mut noise_map = NoiseMap::new(101, 3);
+        for y in 0..3 {
+            for x in 0..101 {
+                noise_map.set_value(x, y, x as f64 / 50.0 - 1.0);
+            }
+        }
+
+        let resized = noise_map.resize(11, 2);
+
+        for y in 0..2 {
+            assert_eq!(resized.get_value(0, y), -1.0);
+            assert_eq!(resized.get_value(10, y), 1.0);
+            for x in 0..11 {
+                let expected = x as f64 / 5.0 - 1.0;
+                assert!((resized.get_value(x, y) - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn flat_map_has_upward_normals() {
+        let normal_map = NoiseMap::new(4, 4).to_normal_map(10.0);
+
+        for y in 0..4 {
+            for x in 0..4 {
+                assert_eq!(normal_map.get_value(x, y), [128, 128, 255, 255]);
+            }
+        }
+    }
+
+    #[test]
+    fn slope_tilts_normals_away() {
+        let mut noise_map = NoiseMap::new(5, 5);
+        for y in 0..5 {
+            for x in 0..5 {
+                noise_map.set_value(x, y, x as f64);
+            }
+        }
+
+        // A 45 degree slope rising along x tilts every normal towards -x.
+        let normal_map = noise_map.to_normal_map(1.0);
+        let expected = (0.5 - 0.5 * std::f64::consts::FRAC_1_SQRT_2) * 255.0;
+
+        for y in 0..5 {
+            for x in 0..5 {
+                let [r, g, b, a] = normal_map.get_value(x, y);
+                assert_eq!(r, expected.round() as u8);
+                assert_eq!(g, 128);
+                assert_eq!(b, (255.0 - expected).round() as u8);
+                assert_eq!(a, 255);
+            }
+        }
+    }
+}
@@ -124,8 +124,10 @@ impl<'a> NoiseMapBuilder<'a> for CylinderMapBuilder<'a> {
     }
 }
 
+#[derive(Clone)]
 pub struct PlaneMapBuilder<'a> {
     is_seamless: bool,
+    jitter: Option<Jitter>,
     x_bounds: (f64, f64),
     y_bounds: (f64, f64),
     size: (usize, usize),
@@ -136,6 +138,7 @@ impl<'a> PlaneMapBuilder<'a> {
     pub fn new(source_module: &'a dyn NoiseFn<[f64; 3]>) -> Self {
         PlaneMapBuilder {
             is_seamless: false,
+            jitter: None,
             x_bounds: (-1.0, 1.0),
             y_bounds: (-1.0, 1.0),
             size: (100, 100),
@@ -164,6 +167,40 @@ impl<'a> PlaneMapBuilder<'a> {
         }
     }
 
+    /// Enables jittered supersampling.
+    ///
+    /// Without jitter, each pixel takes the value of the source function at
+    /// its lower corner. With jitter, each pixel instead averages
+    /// `samples_per_pixel` samples, each at a uniformly random offset within
+    /// the area of the pixel. Detail finer than a pixel then blurs into noise
+    /// with less structure, instead of aliasing into false patterns. It is
+    /// cheaper than a `BoxFilteredNoise` with the same number of samples, but
+    /// noisier.
+    ///
+    /// The offsets are hashed from `seed` and the position of the pixel, so
+    /// the same seed always builds the same map. As no pixel depends on the
+    /// offsets of another, the pixels can be evaluated in any order, or in
+    /// parallel, with identical results. Building costs `samples_per_pixel`
+    /// times as many evaluations of the source function. A count of 0 is
+    /// treated as 1.
+    pub fn with_jitter(self, seed: u64, samples_per_pixel: usize) -> Self {
+        PlaneMapBuilder {
+            jitter: Some(Jitter {
+                seed,
+                samples_per_pixel: samples_per_pixel.max(1),
+            }),
+            ..self
+        }
+    }
+
+    /// Disables jittered supersampling.
+    pub fn without_jitter(self) -> Self {
+        PlaneMapBuilder {
+            jitter: None,
+            ..self
+        }
+    }
+
     pub fn x_bounds(&self) -> (f64, f64) {
         self.x_bounds
     }
@@ -171,6 +208,52 @@ impl<'a> PlaneMapBuilder<'a> {
     pub fn y_bounds(&self) -> (f64, f64) {
         self.y_bounds
     }
+
+    /// Returns the value of the pixel at `(x, y)`, whose lower corner is at
+    /// `(current_x, current_y)` and whose size is `step`.
+    fn sample_pixel(&self, x: usize, y: usize, current: (f64, f64), step: (f64, f64)) -> f64 {
+        let jitter = match self.jitter {
+            Some(jitter) => jitter,
+            None => return self.sample(current.0, current.1),
+        };
+
+        let mut total = 0.0;
+        for index in 0..jitter.samples_per_pixel {
+            let (dx, dy) = jitter.offset(x, y, index);
+            total += self.sample(current.0 + dx * step.0, current.1 + dy * step.1);
+        }
+
+        total / jitter.samples_per_pixel as f64
+    }
+
+    /// Returns the value of the map at a point within the bounds.
+    ///
+    /// When the map is seamless, the value is blended from the source
+    /// function sampled at the point and at its copies one extent to the
+    /// right, above, and to the upper right. The point at the lower bound of
+    /// an axis then takes its value from the copy at the upper bound, so the
+    /// values wrap around the edges of the map.
+    fn sample(&self, x: f64, y: f64) -> f64 {
+        if !self.is_seamless {
+            return self.source_module.get([x, y, 0.0]);
+        }
+
+        let x_extent = self.x_bounds.1 - self.x_bounds.0;
+        let y_extent = self.y_bounds.1 - self.y_bounds.0;
+
+        let sw_value = self.source_module.get([x, y, 0.0]);
+        let se_value = self.source_module.get([x + x_extent, y, 0.0]);
+        let nw_value = self.source_module.get([x, y + y_extent, 0.0]);
+        let ne_value = self.source_module.get([x + x_extent, y + y_extent, 0.0]);
+
+        let x_blend = 1.0 - ((x - self.x_bounds.0) / x_extent);
+        let y_blend = 1.0 - ((y - self.y_bounds.0) / y_extent);
+
+        let y0 = interpolate::linear(sw_value, se_value, x_blend);
+        let y1 = interpolate::linear(nw_value, ne_value, x_blend);
+
+        interpolate::linear(y0, y1, y_blend)
+    }
 }
 
 impl<'a> NoiseMapBuilder<'a> for PlaneMapBuilder<'a> {
@@ -197,11 +280,212 @@ impl<'a> NoiseMapBuilder<'a> for PlaneMapBuilder<'a> {
 
         let mut result_map = NoiseMap::new(width, height);
 
-        let x_extent = self.x_bounds.1 - self.x_bounds.0;
-        let y_extent = self.y_bounds.1 - self.y_bounds.0;
+        if self.jitter.is_some() || self.is_seamless {
+            for (x, y, value) in self.clone() {
+                result_map.set_value(x, y, value);
+            }
+
+            return result_map;
+        }
+
+        // Pass each row to the source function as a single batch, so that
+        // sources with a batched implementation, such as `Simplex` with the
+        // `simd` feature, can evaluate several pixels at once.
+        let x_step = (self.x_bounds.1 - self.x_bounds.0) / width as f64;
+        let y_step = (self.y_bounds.1 - self.y_bounds.0) / height as f64;
+
+        let mut points = Vec::with_capacity(width);
+        let mut row = vec![0.0; width];
+
+        for y in 0..height {
+            let current_y = self.y_bounds.0 + y_step * y as f64;
+
+            points.clear();
+            points.extend((0..width).map(|x| {
+                let current_x = self.x_bounds.0 + x_step * x as f64;
+                [current_x, current_y, 0.0]
+            }));
+
+            self.source_module.get_batch(&points, &mut row);
+
+            for (x, &value) in row.iter().enumerate() {
+                result_map.set_value(x, y, value);
+            }
+        }
+
+        result_map
+    }
+}
+
+impl<'a> IntoIterator for PlaneMapBuilder<'a> {
+    type Item = (usize, usize, f64);
+    type IntoIter = NoiseIter<'a>;
+
+    /// Returns an iterator that evaluates the map one pixel at a time, in the
+    /// same order as `build`. The values are the same as those of `build`,
+    /// unless the source function evaluates batches of points differently.
+    fn into_iter(self) -> NoiseIter<'a> {
+        let (width, height) = self.size;
+
+        NoiseIter {
+            x_step: (self.x_bounds.1 - self.x_bounds.0) / width as f64,
+            y_step: (self.y_bounds.1 - self.y_bounds.0) / height as f64,
+            position: (0, 0),
+            builder: self,
+        }
+    }
+}
+
+/// Iterator over the pixels of a `PlaneMapBuilder`, yielding the _x_ and _y_
+/// position of each pixel with its value, row by row.
+///
+/// Each value is computed when it is reached, so maps too large to hold in
+/// memory can be streamed. Standard iterator adaptors pick out parts of the
+/// map without storing the rest, such as the pixels above a threshold:
+///
+/// ```
+/// use noice::{utils::PlaneMapBuilder, Perlin};
+///
+/// let perlin = Perlin::new();
+/// let peaks: Vec<(usize, usize)> = PlaneMapBuilder::new(&perlin)
+///     .into_iter()
+///     .filter_map(|(x, y, value)| if value > 0.5 { Some((x, y)) } else { None })
+///     .collect();
+/// ```
+pub struct NoiseIter<'a> {
+    builder: PlaneMapBuilder<'a>,
+    x_step: f64,
+    y_step: f64,
+    position: (usize, usize),
+}
+
+impl<'a> Iterator for NoiseIter<'a> {
+    type Item = (usize, usize, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (width, height) = self.builder.size;
+        let (x, y) = self.position;
+
+        if width == 0 || y >= height {
+            return None;
+        }
+
+        self.position = if x + 1 < width {
+            (x + 1, y)
+        } else {
+            (0, y + 1)
+        };
+
+        let current_x = self.builder.x_bounds.0 + self.x_step * x as f64;
+        let current_y = self.builder.y_bounds.0 + self.y_step * y as f64;
+        let value =
+            self.builder
+                .sample_pixel(x, y, (current_x, current_y), (self.x_step, self.y_step));
+
+        Some((x, y, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (width, height) = self.builder.size;
+        let (x, y) = self.position;
+        let remaining = (width * height).saturating_sub(x + y * width);
+
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for NoiseIter<'a> {}
+
+/// Builds a map from a plane through 4-dimensional space, at fixed _z_ and _w_
+/// coordinates.
+///
+/// Moving the _z_ and _w_ coordinates animates the map smoothly. Walking them
+/// around a closed curve, such as a circle in the _zw_ plane, produces an
+/// animation that loops.
+pub struct PlaneMapBuilder4D<'a> {
+    x_bounds: (f64, f64),
+    y_bounds: (f64, f64),
+    z: f64,
+    w: f64,
+    size: (usize, usize),
+    source_module: &'a dyn NoiseFn<[f64; 4]>,
+}
+
+impl<'a> PlaneMapBuilder4D<'a> {
+    pub fn new(source_module: &'a dyn NoiseFn<[f64; 4]>) -> Self {
+        PlaneMapBuilder4D {
+            x_bounds: (-1.0, 1.0),
+            y_bounds: (-1.0, 1.0),
+            z: 0.0,
+            w: 0.0,
+            size: (100, 100),
+            source_module,
+        }
+    }
+
+    pub fn set_size(self, width: usize, height: usize) -> Self {
+        PlaneMapBuilder4D {
+            size: (width, height),
+            ..self
+        }
+    }
+
+    pub fn set_source_module(self, source_module: &'a dyn NoiseFn<[f64; 4]>) -> Self {
+        PlaneMapBuilder4D {
+            source_module,
+            ..self
+        }
+    }
+
+    pub fn set_x_bounds(self, lower_x_bound: f64, upper_x_bound: f64) -> Self {
+        PlaneMapBuilder4D {
+            x_bounds: (lower_x_bound, upper_x_bound),
+            ..self
+        }
+    }
+
+    pub fn set_y_bounds(self, lower_y_bound: f64, upper_y_bound: f64) -> Self {
+        PlaneMapBuilder4D {
+            y_bounds: (lower_y_bound, upper_y_bound),
+            ..self
+        }
+    }
+
+    pub fn set_z(self, z: f64) -> Self {
+        PlaneMapBuilder4D { z, ..self }
+    }
+
+    pub fn set_w(self, w: f64) -> Self {
+        PlaneMapBuilder4D { w, ..self }
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    pub fn x_bounds(&self) -> (f64, f64) {
+        self.x_bounds
+    }
+
+    pub fn y_bounds(&self) -> (f64, f64) {
+        self.y_bounds
+    }
+
+    pub fn z(&self) -> f64 {
+        self.z
+    }
 
-        let x_step = x_extent / width as f64;
-        let y_step = y_extent / height as f64;
+    pub fn w(&self) -> f64 {
+        self.w
+    }
+
+    pub fn build(&self) -> NoiseMap {
+        let (width, height) = self.size;
+
+        let mut result_map = NoiseMap::new(width, height);
+
+        let x_step = (self.x_bounds.1 - self.x_bounds.0) / width as f64;
+        let y_step = (self.y_bounds.1 - self.y_bounds.0) / height as f64;
 
         for y in 0..height {
             let current_y = self.y_bounds.0 + y_step * y as f64;
@@ -209,30 +493,10 @@ impl<'a> NoiseMapBuilder<'a> for PlaneMapBuilder<'a> {
             for x in 0..width {
                 let current_x = self.x_bounds.0 + x_step * x as f64;
 
-                let final_value = if self.is_seamless {
-                    let sw_value = self.source_module.get([current_x, current_y, 0.0]);
-                    let se_value = self
-                        .source_module
-                        .get([current_x + x_extent, current_y, 0.0]);
-                    let nw_value = self
-                        .source_module
-                        .get([current_x, current_y + y_extent, 0.0]);
-                    let ne_value =
-                        self.source_module
-                            .get([current_x + x_extent, current_y + y_extent, 0.0]);
-
-                    let x_blend = 1.0 - ((current_x - self.x_bounds.0) / x_extent);
-                    let y_blend = 1.0 - ((current_y - self.y_bounds.0) / y_extent);
-
-                    let y0 = interpolate::linear(sw_value, se_value, x_blend);
-                    let y1 = interpolate::linear(nw_value, ne_value, x_blend);
-
-                    interpolate::linear(y0, y1, y_blend)
-                } else {
-                    self.source_module.get([current_x, current_y, 0.0])
-                };
-
-                result_map.set_value(x, y, final_value);
+                let value = self
+                    .source_module
+                    .get([current_x, current_y, self.z, self.w]);
+                result_map.set_value(x, y, value);
             }
         }
 
@@ -240,6 +504,136 @@ impl<'a> NoiseMapBuilder<'a> for PlaneMapBuilder<'a> {
     }
 }
 
+/// Builds the frames of a looping animation of a plane through 4-dimensional
+/// space.
+///
+/// Each frame is a `PlaneMapBuilder4D` map whose _z_ and _w_ coordinates lie
+/// on a circle in the _zw_ plane. The animation phase `t` runs from 0.0 to 1.0
+/// once around the circle, so the frame at 1.0 is the frame at 0.0 and the
+/// animation loops without a jump. A larger radius makes the noise change
+/// more over one loop.
+pub struct LoopingPlaneBuilder<'a> {
+    x_bounds: (f64, f64),
+    y_bounds: (f64, f64),
+    center: (f64, f64),
+    radius: f64,
+    size: (usize, usize),
+    source_module: &'a dyn NoiseFn<[f64; 4]>,
+}
+
+impl<'a> LoopingPlaneBuilder<'a> {
+    pub const DEFAULT_RADIUS: f64 = 1.0;
+
+    pub fn new(source_module: &'a dyn NoiseFn<[f64; 4]>) -> Self {
+        LoopingPlaneBuilder {
+            x_bounds: (-1.0, 1.0),
+            y_bounds: (-1.0, 1.0),
+            center: (0.0, 0.0),
+            radius: Self::DEFAULT_RADIUS,
+            size: (100, 100),
+            source_module,
+        }
+    }
+
+    pub fn set_size(self, width: usize, height: usize) -> Self {
+        LoopingPlaneBuilder {
+            size: (width, height),
+            ..self
+        }
+    }
+
+    pub fn set_x_bounds(self, lower_x_bound: f64, upper_x_bound: f64) -> Self {
+        LoopingPlaneBuilder {
+            x_bounds: (lower_x_bound, upper_x_bound),
+            ..self
+        }
+    }
+
+    pub fn set_y_bounds(self, lower_y_bound: f64, upper_y_bound: f64) -> Self {
+        LoopingPlaneBuilder {
+            y_bounds: (lower_y_bound, upper_y_bound),
+            ..self
+        }
+    }
+
+    /// Sets the centre of the circle in the _zw_ plane. Different centres
+    /// produce different animations.
+    pub fn set_center(self, z: f64, w: f64) -> Self {
+        LoopingPlaneBuilder {
+            center: (z, w),
+            ..self
+        }
+    }
+
+    pub fn set_radius(self, radius: f64) -> Self {
+        LoopingPlaneBuilder { radius, ..self }
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// Returns the _z_ and _w_ coordinates of the frame at phase `t`.
+    ///
+    /// Only the fractional part of `t` is used, so whole numbers of loops
+    /// later give exactly the same coordinates.
+    pub fn frame_coordinates(&self, t: f64) -> (f64, f64) {
+        let angle = 2.0 * std::f64::consts::PI * t.rem_euclid(1.0);
+
+        (
+            self.center.0 + angle.cos() * self.radius,
+            self.center.1 + angle.sin() * self.radius,
+        )
+    }
+
+    /// Builds the frame at phase `t`.
+    pub fn build_frame(&self, t: f64) -> NoiseMap {
+        let (z, w) = self.frame_coordinates(t);
+
+        PlaneMapBuilder4D::new(self.source_module)
+            .set_size(self.size.0, self.size.1)
+            .set_x_bounds(self.x_bounds.0, self.x_bounds.1)
+            .set_y_bounds(self.y_bounds.0, self.y_bounds.1)
+            .set_z(z)
+            .set_w(w)
+            .build()
+    }
+}
+
+/// Settings of the jittered supersampling of a `PlaneMapBuilder`.
+#[derive(Clone, Copy, Debug)]
+struct Jitter {
+    seed: u64,
+    samples_per_pixel: usize,
+}
+
+impl Jitter {
+    /// Returns the offset of sample `index` of the pixel at `(x, y)` from the
+    /// lower corner of the pixel, as a fraction of the pixel size in [0,1).
+    fn offset(&self, x: usize, y: usize, index: usize) -> (f64, f64) {
+        let hash = splitmix64(
+            self.seed ^ splitmix64(x as u64 ^ splitmix64(y as u64 ^ splitmix64(index as u64))),
+        );
+
+        // The top 26 bits of each half, scaled into [0,1).
+        let unit = |bits: u64| (bits >> 6) as f64 / (1u64 << 26) as f64;
+
+        (unit(hash >> 32), unit(hash & 0xffff_ffff))
+    }
+}
+
+/// Mixes the bits of `x`, as in the SplitMix64 generator.
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
 pub struct SphereMapBuilder<'a> {
     latitude_bounds: (f64, f64),
     longitude_bounds: (f64, f64),
@@ -348,3 +742,251 @@ fn lat_lon_to_xyz(lat: f64, lon: f64) -> [f64; 3] {
 
     [x, y, z]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Perlin, Seedable, Simplex};
+
+    /// Outputs the _x_ coordinate of the input point.
+    struct XCoordinate;
+
+    impl NoiseFn<[f64; 3]> for XCoordinate {
+        fn get(&self, point: [f64; 3]) -> f64 {
+            point[0]
+        }
+    }
+
+    #[test]
+    fn jittered_samples_stay_within_pixels() {
+        let builder = || {
+            PlaneMapBuilder::new(&XCoordinate)
+                .set_size(16, 4)
+                .set_x_bounds(0.0, 16.0)
+        };
+
+        let corners = builder().build();
+        let single = builder().with_jitter(7, 1).build();
+        let averaged = builder().with_jitter(7, 64).build();
+
+        for ((x, y), value) in single.iter() {
+            assert_eq!(corners[(x, y)], x as f64);
+            assert!(
+                (x as f64..x as f64 + 1.0).contains(&value),
+                "{} at {}",
+                value,
+                x
+            );
+            assert_ne!(value, averaged[(x, y)]);
+
+            // The average of many samples approaches the pixel centre.
+            assert!((averaged[(x, y)] - (x as f64 + 0.5)).abs() < 0.1);
+        }
+    }
+
+    #[test]
+    fn plane_4d_samples_at_fixed_z_and_w() {
+        let simplex = Simplex::new();
+        let builder = || {
+            PlaneMapBuilder4D::new(&simplex)
+                .set_size(16, 16)
+                .set_x_bounds(-2.0, 2.0)
+                .set_y_bounds(-2.0, 2.0)
+                .set_z(0.3)
+        };
+
+        let map = builder().set_w(0.0).build();
+        let moved = builder().set_w(0.7).build();
+        assert_ne!(map.as_slice(), moved.as_slice());
+        assert_eq!(map.as_slice(), builder().build().as_slice());
+
+        assert_eq!(map[(4, 12)], simplex.get([-1.0, 1.0, 0.3, 0.0]));
+        assert_eq!(moved[(4, 12)], simplex.get([-1.0, 1.0, 0.3, 0.7]));
+    }
+
+    #[test]
+    fn looping_frames_repeat_every_cycle() {
+        let simplex = Simplex::new();
+        let builder = LoopingPlaneBuilder::new(&simplex)
+            .set_size(16, 16)
+            .set_center(3.0, -2.0)
+            .set_radius(0.5);
+
+        let first = builder.build_frame(0.0);
+        assert_eq!(first.as_slice(), builder.build_frame(1.0).as_slice());
+        assert_eq!(first.as_slice(), builder.build_frame(-2.0).as_slice());
+        assert_ne!(first.as_slice(), builder.build_frame(0.5).as_slice());
+
+        assert_eq!(builder.frame_coordinates(0.0), (3.5, -2.0));
+        let (z, w) = builder.frame_coordinates(0.25);
+        assert!((z - 3.0).abs() < 1e-12 && (w + 1.5).abs() < 1e-12);
+
+        // Frames close together in time are similar.
+        let next = builder.build_frame(0.999);
+        for (value, next) in first.as_slice().iter().zip(next.as_slice()) {
+            assert!((value - next).abs() < 0.05);
+        }
+    }
+
+    #[test]
+    fn iterator_matches_build() {
+        let perlin = Perlin::new();
+        let builder = PlaneMapBuilder::new(&perlin)
+            .set_size(13, 7)
+            .set_x_bounds(-3.0, 2.0)
+            .set_y_bounds(0.5, 4.0)
+            .with_jitter(1, 2);
+
+        let map = builder.build();
+        let iter = builder.into_iter();
+        assert_eq!(iter.len(), 91);
+
+        let pixels: Vec<(usize, usize, f64)> = iter.collect();
+        assert_eq!(pixels.len(), 91);
+        assert_eq!(&pixels[..2], &[(0, 0, map[(0, 0)]), (1, 0, map[(1, 0)])]);
+        assert_eq!(pixels[13], (0, 1, map[(0, 1)]));
+
+        for (x, y, value) in pixels {
+            assert_eq!(value, map[(x, y)]);
+        }
+    }
+
+    #[test]
+    fn batched_build_matches_iterator() {
+        let simplex = Simplex::new().set_seed(4);
+        let builder = PlaneMapBuilder::new(&simplex)
+            .set_size(37, 11)
+            .set_x_bounds(-3.0, 2.0)
+            .set_y_bounds(0.5, 4.0);
+
+        let map = builder.build();
+        for (x, y, value) in builder {
+            assert!((value - map[(x, y)]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn filter_map_extracts_sparse_pixels() {
+        let perlin = Perlin::new();
+        let builder = || PlaneMapBuilder::new(&perlin).set_size(32, 32);
+
+        let map = builder().build();
+        let above: Vec<(usize, usize)> = builder()
+            .into_iter()
+            .filter_map(|(x, y, value)| if value > 0.2 { Some((x, y)) } else { None })
+            .collect();
+
+        let expected = map.iter().filter(|&(_, value)| value > 0.2).count();
+        assert_eq!(above.len(), expected);
+        assert!(above.iter().all(|&point| map[point] > 0.2));
+    }
+
+    #[test]
+    fn jitter_is_deterministic() {
+        let perlin = Perlin::new();
+        let builder = || {
+            PlaneMapBuilder::new(&perlin)
+                .set_size(32, 32)
+                .set_x_bounds(-40.0, 40.0)
+                .set_y_bounds(-40.0, 40.0)
+        };
+
+        let map = builder().with_jitter(3, 4).build();
+        assert_eq!(
+            map.as_slice(),
+            builder().with_jitter(3, 4).build().as_slice()
+        );
+        assert_ne!(
+            map.as_slice(),
+            builder().with_jitter(4, 4).build().as_slice()
+        );
+        assert_eq!(
+            builder()
+                .with_jitter(3, 4)
+                .without_jitter()
+                .build()
+                .as_slice(),
+            builder().build().as_slice()
+        );
+    }
+
+    // Mean absolute difference between horizontally neighbouring values.
+    fn roughness(noise_map: &NoiseMap) -> f64 {
+        let (width, height) = noise_map.size();
+        let mut sum = 0.0;
+
+        for y in 0..height {
+            for x in 1..width {
+                sum += (noise_map.get_value(x, y) - noise_map.get_value(x - 1, y)).abs();
+            }
+        }
+
+        sum / ((width - 1) * height) as f64
+    }
+
+    #[test]
+    fn plane_bounds_map_pixels_linearly() {
+        let perlin = Perlin::new();
+        let noise_map = PlaneMapBuilder::new(&perlin)
+            .set_size(40, 20)
+            .set_x_bounds(-2.0, 2.0)
+            .set_y_bounds(1.0, 3.0)
+            .build();
+
+        assert_eq!(noise_map.size(), (40, 20));
+        assert_eq!(noise_map.get_value(0, 0), perlin.get([-2.0, 1.0, 0.0]));
+        assert_eq!(noise_map.get_value(30, 5), perlin.get([1.0, 1.5, 0.0]));
+    }
+
+    #[test]
+    fn narrower_bounds_zoom_in() {
+        let perlin = Perlin::new();
+        let build = |extent: f64| {
+            PlaneMapBuilder::new(&perlin)
+                .set_size(64, 64)
+                .set_x_bounds(-extent, extent)
+                .set_y_bounds(-extent, extent)
+                .build()
+        };
+
+        assert!(roughness(&build(0.5)) < roughness(&build(8.0)) / 4.0);
+    }
+
+    #[test]
+    fn seamless_plane_wraps_at_bounds() {
+        let perlin = Perlin::new();
+        let builder = PlaneMapBuilder::new(&perlin)
+            .set_x_bounds(-1.3, 2.1)
+            .set_y_bounds(0.4, 3.0);
+        let seamless = PlaneMapBuilder::new(&perlin)
+            .set_x_bounds(-1.3, 2.1)
+            .set_y_bounds(0.4, 3.0)
+            .set_is_seamless(true);
+
+        let mut difference = 0.0_f64;
+        for i in 0..=20 {
+            let t = i as f64 / 20.0;
+            let x = -1.3 + t * 3.4;
+            let y = 0.4 + t * 2.6;
+
+            assert!((seamless.sample(-1.3, y) - seamless.sample(2.1, y)).abs() < 1e-9);
+            assert!((seamless.sample(x, 0.4) - seamless.sample(x, 3.0)).abs() < 1e-9);
+
+            difference = difference.max((builder.sample(-1.3, y) - builder.sample(2.1, y)).abs());
+        }
+
+        assert!(difference > 0.01);
+
+        // The first column of the map continues on from the last one.
+        let noise_map = PlaneMapBuilder::new(&perlin)
+            .set_x_bounds(-1.3, 2.1)
+            .set_y_bounds(0.4, 3.0)
+            .set_is_seamless(true)
+            .set_size(34, 26)
+            .build();
+        for y in 0..26 {
+            let next = seamless.sample(2.1, 0.4 + y as f64 * 0.1);
+            assert!((noise_map.get_value(0, y) - next).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use crate::{
+    noise_fns::{NoiseFn, Seedable, TranslatePoint},
+    utils::NoiseMap,
+};
+#[cfg(feature = "image")]
+use std::path::Path;
+
+/// Three noise maps of the same size, holding the red, green and blue channels
+/// of a texture.
+#[derive(Default)]
+pub struct NoiseMapsRGB {
+    pub red: NoiseMap,
+    pub green: NoiseMap,
+    pub blue: NoiseMap,
+}
+
+impl NoiseMapsRGB {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            red: NoiseMap::new(width, height),
+            green: NoiseMap::new(width, height),
+            blue: NoiseMap::new(width, height),
+        }
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        self.red.size()
+    }
+
+    /// Writes the maps to `filename` in the `example_images` directory as an
+    /// RGB image, mapping each channel from [-1,1] to [0,255].
+    #[cfg(feature = "image")]
+    pub fn write_to_file(&self, filename: &str) {
+        // Create the output directory for the images, if it doesn't already exist
+        let target_dir = Path::new("example_images/");
+
+        if !target_dir.exists() {
+            std::fs::create_dir(target_dir).expect("failed to create example_images directory");
+        }
+
+        let file_path = target_dir.join(filename);
+
+        let (width, height) = self.size();
+        let mut pixels: Vec<u8> = Vec::with_capacity(width * height * 3);
+
+        let encode = |value: f64| (crate::math::clamp(value * 0.5 + 0.5, 0.0, 1.0) * 255.0) as u8;
+        let channels = [&self.red, &self.green, &self.blue];
+
+        for y in 0..height {
+            for x in 0..width {
+                for channel in &channels {
+                    pixels.push(encode(channel.get_value(x, y)));
+                }
+            }
+        }
+
+        let _ = image::save_buffer(
+            &file_path,
+            &pixels,
+            width as u32,
+            height as u32,
+            image::ColorType::Rgb8,
+        );
+
+        println!("\nFinished generating {}", filename);
+    }
+}
+
+/// Builds the three channels of a `NoiseMapsRGB` from a plane, in the same way
+/// as `PlaneMapBuilder` builds a single `NoiseMap`.
+pub struct RGBMapBuilder<'a> {
+    channels: [Box<dyn NoiseFn<[f64; 3]> + 'a>; 3],
+    x_bounds: (f64, f64),
+    y_bounds: (f64, f64),
+    size: (usize, usize),
+}
+
+impl<'a> RGBMapBuilder<'a> {
+    /// Offsets that `from_noise_fn_with_offsets` is usually given, far enough
+    /// apart that the channels look unrelated.
+    pub const DEFAULT_OFFSETS: [[f64; 3]; 3] = [
+        [0.0, 0.0, 0.0],
+        [5_393.1, -2_719.7, 1_183.3],
+        [-8_807.9, 4_111.3, -6_029.9],
+    ];
+
+    /// Creates a builder that samples each channel from its own noise
+    /// function.
+    pub fn new(
+        red: &'a dyn NoiseFn<[f64; 3]>,
+        green: &'a dyn NoiseFn<[f64; 3]>,
+        blue: &'a dyn NoiseFn<[f64; 3]>,
+    ) -> Self {
+        Self::from_channels([Box::new(red), Box::new(green), Box::new(blue)])
+    }
+
+    /// Creates a builder that samples each channel from a copy of `source`,
+    /// seeded with the matching seed.
+    pub fn from_seeds<N>(source: &N, seeds: [u32; 3]) -> Self
+    where
+        N: NoiseFn<[f64; 3]> + Seedable + Clone + 'a,
+    {
+        Self::from_channels([
+            Box::new(source.clone().set_seed(seeds[0])),
+            Box::new(source.clone().set_seed(seeds[1])),
+            Box::new(source.clone().set_seed(seeds[2])),
+        ])
+    }
+
+    /// Creates a builder that samples each channel from `source`, with the
+    /// input value moved by the matching offset.
+    ///
+    /// Noise functions are only correlated over short distances, so offsets
+    /// far apart produce channels that are pseudo-independent, without the
+    /// cost of building a seeded copy of the source for each one.
+    pub fn from_noise_fn_with_offsets(
+        source: &'a dyn NoiseFn<[f64; 3]>,
+        offsets: [[f64; 3]; 3],
+    ) -> Self {
+        let channel = |[x, y, z]: [f64; 3]| -> Box<dyn NoiseFn<[f64; 3]> + 'a> {
+            Box::new(
+                TranslatePoint::new(source)
+                    .set_x_translation(x)
+                    .set_y_translation(y)
+                    .set_z_translation(z),
+            )
+        };
+
+        Self::from_channels([
+            channel(offsets[0]),
+            channel(offsets[1]),
+            channel(offsets[2]),
+        ])
+    }
+
+    fn from_channels(channels: [Box<dyn NoiseFn<[f64; 3]> + 'a>; 3]) -> Self {
+        Self {
+            channels,
+            x_bounds: (-1.0, 1.0),
+            y_bounds: (-1.0, 1.0),
+            size: (100, 100),
+        }
+    }
+
+    pub fn set_size(self, width: usize, height: usize) -> Self {
+        Self {
+            size: (width, height),
+            ..self
+        }
+    }
+
+    pub fn set_x_bounds(self, lower_x_bound: f64, upper_x_bound: f64) -> Self {
+        Self {
+            x_bounds: (lower_x_bound, upper_x_bound),
+            ..self
+        }
+    }
+
+    pub fn set_y_bounds(self, lower_y_bound: f64, upper_y_bound: f64) -> Self {
+        Self {
+            y_bounds: (lower_y_bound, upper_y_bound),
+            ..self
+        }
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    pub fn x_bounds(&self) -> (f64, f64) {
+        self.x_bounds
+    }
+
+    pub fn y_bounds(&self) -> (f64, f64) {
+        self.y_bounds
+    }
+
+    /// Builds all three channels in a single pass over the plane.
+    pub fn build(&self) -> NoiseMapsRGB {
+        let (width, height) = self.size;
+
+        let mut result = NoiseMapsRGB::new(width, height);
+
+        let x_step = (self.x_bounds.1 - self.x_bounds.0) / width as f64;
+        let y_step = (self.y_bounds.1 - self.y_bounds.0) / height as f64;
+
+        for y in 0..height {
+            let current_y = self.y_bounds.0 + y_step * y as f64;
+
+            for x in 0..width {
+                let current_x = self.x_bounds.0 + x_step * x as f64;
+                let point = [current_x, current_y, 0.0];
+
+                result.red.set_value(x, y, self.channels[0].get(point));
+                result.green.set_value(x, y, self.channels[1].get(point));
+                result.blue.set_value(x, y, self.channels[2].get(point));
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        noise_fns::Perlin,
+        utils::{NoiseMapBuilder, PlaneMapBuilder},
+    };
+
+    #[test]
+    fn channels_match_plane_maps() {
+        let perlin = Perlin::new();
+        let maps = RGBMapBuilder::from_seeds(&perlin, [1, 2, 3])
+            .set_size(24, 16)
+            .set_x_bounds(-2.0, 2.0)
+            .build();
+
+        for (channel, seed) in [&maps.red, &maps.green, &maps.blue].iter().zip(1..) {
+            let perlin = Perlin::new().set_seed(seed);
+            let expected = PlaneMapBuilder::new(&perlin)
+                .set_size(24, 16)
+                .set_x_bounds(-2.0, 2.0)
+                .build();
+
+            assert_eq!(channel.as_slice(), expected.as_slice());
+        }
+    }
+
+    #[test]
+    fn offset_channels_differ() {
+        let perlin = Perlin::new();
+        let maps =
+            RGBMapBuilder::from_noise_fn_with_offsets(&perlin, RGBMapBuilder::DEFAULT_OFFSETS)
+                .set_size(16, 16)
+                .build();
+
+        let moved = TranslatePoint::new(&perlin)
+            .set_x_translation(5_393.1)
+            .set_y_translation(-2_719.7)
+            .set_z_translation(1_183.3);
+        assert_eq!(maps.green.get_value(3, 5), moved.get([-0.625, -0.375, 0.0]));
+
+        assert_ne!(maps.red.as_slice(), maps.green.as_slice());
+        assert_ne!(maps.green.as_slice(), maps.blue.as_slice());
+    }
+}
@@ -0,0 +1,90 @@
+use crate::noise_fns::NoiseFn;
+use std::f64::consts::PI;
+
+/// Samples `noise` at each point of `path`, writing the values to `output`.
+///
+/// The path can be any sequence of points, such as the points along a line or
+/// a Bezier curve, and turns an N-dimensional noise function into a 1D slice
+/// through it. The points are evaluated with `get_batch`, so noise functions
+/// that speed up batches speed up paths too.
+///
+/// # Panics
+///
+/// Panics if `path` and `output` have different lengths.
+pub fn sample_along_path<F, const N: usize>(noise: &F, path: &[[f64; N]], output: &mut [f64])
+where
+    F: NoiseFn<[f64; N]> + ?Sized,
+{
+    noise.get_batch(path, output);
+}
+
+/// Samples `noise` at `samples` points evenly spaced around a circle, starting
+/// from the positive _x_ axis and turning counter-clockwise.
+///
+/// The circle closes on itself, so the values loop seamlessly when repeated:
+/// the value that would follow the last one is the first. The radius sets how
+/// much the values vary over one loop.
+pub fn sample_along_circle<F>(noise: &F, center: [f64; 2], radius: f64, samples: usize) -> Vec<f64>
+where
+    F: NoiseFn<[f64; 2]> + ?Sized,
+{
+    let path: Vec<[f64; 2]> = (0..samples)
+        .map(|index| {
+            let angle = 2.0 * PI * index as f64 / samples as f64;
+            [
+                center[0] + radius * angle.cos(),
+                center[1] + radius * angle.sin(),
+            ]
+        })
+        .collect();
+
+    let mut output = vec![0.0; samples];
+    sample_along_path(noise, &path, &mut output);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Perlin, Simplex};
+
+    #[test]
+    fn path_matches_get() {
+        let simplex = Simplex::new();
+        let path: Vec<[f64; 3]> = (0..50)
+            .map(|i| [i as f64 * 0.1, i as f64 * -0.07, 1.5])
+            .collect();
+        let mut output = vec![0.0; path.len()];
+
+        sample_along_path(&simplex, &path, &mut output);
+
+        for (point, value) in path.iter().zip(&output) {
+            assert_eq!(*value, simplex.get(*point));
+        }
+    }
+
+    #[test]
+    fn circle_loops_seamlessly() {
+        let perlin = Perlin::new();
+        let center = [0.3, -1.2];
+        let samples = sample_along_circle(&perlin, center, 2.0, 360);
+
+        assert_eq!(samples.len(), 360);
+        assert_eq!(samples[0], perlin.get([2.3, -1.2]));
+        assert!((samples[90] - perlin.get([0.3, 0.8])).abs() < 1e-12);
+
+        // The step from the last sample back to the first is no larger than
+        // the steps between the other neighbours.
+        let largest_step = samples
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]).abs())
+            .fold(0.0, f64::max);
+        assert!((samples[0] - samples[359]).abs() <= largest_step);
+    }
+
+    #[test]
+    #[should_panic(expected = "points and output must have the same length")]
+    fn mismatched_output_panics() {
+        sample_along_path(&Simplex::new(), &[[0.0, 0.0]; 3], &mut [0.0; 2]);
+    }
+}
@@ -0,0 +1,85 @@
+//! JavaScript bindings, enabled by the `wasm` feature.
+//!
+//! ```js
+//! import init, { WasmNoise } from "./pkg/noice.js";
+//!
+//! await init();
+//! const noise = new WasmNoise(42);
+//! const map = noise.build_plane_map(256, 256, -1.0, 1.0, -1.0, 1.0); // Float64Array
+//! ```
+
+use crate::{
+    noise_fns::{NoiseFn, Seedable, Simplex},
+    utils::{NoiseMapBuilder, PlaneMapBuilder},
+};
+use wasm_bindgen::prelude::*;
+
+/// Simplex noise exported to JavaScript.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub struct WasmNoise {
+    simplex: Simplex,
+}
+
+#[wasm_bindgen]
+impl WasmNoise {
+    #[wasm_bindgen(constructor)]
+    pub fn new(seed: u32) -> Self {
+        Self {
+            simplex: Simplex::new().set_seed(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u32 {
+        self.simplex.seed()
+    }
+
+    pub fn get_2d(&self, x: f64, y: f64) -> f64 {
+        self.simplex.get([x, y])
+    }
+
+    pub fn get_3d(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.simplex.get([x, y, z])
+    }
+
+    /// Samples a `width` by `height` plane over the given bounds, in row-major
+    /// order. The result is received as a `Float64Array` in JavaScript.
+    pub fn build_plane_map(
+        &self,
+        width: u32,
+        height: u32,
+        x0: f64,
+        x1: f64,
+        y0: f64,
+        y1: f64,
+    ) -> Vec<f64> {
+        PlaneMapBuilder::new(&self.simplex)
+            .set_size(width as usize, height as usize)
+            .set_x_bounds(x0, x1)
+            .set_y_bounds(y0, y1)
+            .build()
+            .into_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plane_map_matches_builder() {
+        let noise = WasmNoise::new(7);
+        let simplex = Simplex::new().set_seed(7);
+        let expected = PlaneMapBuilder::new(&simplex)
+            .set_size(8, 4)
+            .set_x_bounds(-2.0, 2.0)
+            .set_y_bounds(0.0, 1.0)
+            .build();
+
+        let map = noise.build_plane_map(8, 4, -2.0, 2.0, 0.0, 1.0);
+
+        assert_eq!(map.len(), 32);
+        assert_eq!(map.as_slice(), expected.as_slice());
+        assert_eq!(noise.get_2d(0.3, 0.7), simplex.get([0.3, 0.7]));
+    }
+}